@@ -0,0 +1,1105 @@
+use chrono::{TimeZone, Utc};
+use pulldown_cmark::{CodeBlockKind, Event, Options, Parser, Tag, TagEnd};
+use serde_json::{json, Map, Value};
+
+// Conversão entre ADF (Atlassian Document Format), Markdown e HTML.
+// Menções são representadas em Markdown como links com esquema próprio:
+// [@Nome](accountid:5b10ac8d82e05b22cc7d4ef5)
+const MENTION_SCHEME: &str = "accountid:";
+
+// Esquemas aceitos em links vindos do JIRA; os demais (javascript:, data:...)
+// seriam executados pelo webview ao renderizar o HTML
+const SAFE_LINK_SCHEMES: [&str; 3] = ["http", "https", "mailto"];
+
+// === ADF -> MARKDOWN ===
+
+pub fn to_markdown(doc: &Value) -> String {
+    // Jira Server/Data Center (API v2) devolve texto simples em vez de ADF
+    if let Some(text) = doc.as_str() {
+        return text.to_string();
+    }
+
+    blocks_to_markdown(children(doc)).trim_end().to_string()
+}
+
+fn blocks_to_markdown(nodes: &[Value]) -> String {
+    nodes.iter()
+        .map(block_to_markdown)
+        .filter(|block| !block.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+fn block_to_markdown(node: &Value) -> String {
+    match node_type(node) {
+        "paragraph" => inline_to_markdown(children(node)),
+        "heading" => {
+            let level = attr(node, "level").and_then(Value::as_u64).unwrap_or(1).clamp(1, 6) as usize;
+            format!("{} {}", "#".repeat(level), inline_to_markdown(children(node)))
+        }
+        "bulletList" => list_to_markdown(node, |_| "- ".to_string()),
+        "orderedList" => {
+            let start = attr(node, "order").and_then(Value::as_u64).unwrap_or(1);
+            list_to_markdown(node, |i| format!("{}. ", start + i as u64))
+        }
+        "taskList" => children(node).iter()
+            .map(|item| {
+                let done = attr(item, "state").and_then(Value::as_str) == Some("DONE");
+                let marker = if done { "- [x] " } else { "- [ ] " };
+                if node_type(item) == "taskList" {
+                    indent(&block_to_markdown(item), "  ")
+                } else {
+                    format!("{}{}", marker, inline_to_markdown(children(item)))
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+        "decisionList" => children(node).iter()
+            .map(|item| format!("- {}", inline_to_markdown(children(item))))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        "codeBlock" => {
+            let language = attr(node, "language").and_then(Value::as_str).unwrap_or("");
+            let code: String = children(node).iter()
+                .filter_map(|n| n.get("text").and_then(Value::as_str))
+                .collect();
+            let fence = if code.contains("```") { "````" } else { "```" };
+            format!("{}{}\n{}\n{}", fence, language, code, fence)
+        }
+        "blockquote" | "panel" => blocks_to_markdown(children(node))
+            .lines()
+            .map(|line| if line.is_empty() { ">".to_string() } else { format!("> {}", line) })
+            .collect::<Vec<_>>()
+            .join("\n"),
+        "rule" => "---".to_string(),
+        "table" => table_to_markdown(node),
+        "expand" | "nestedExpand" => {
+            let content = blocks_to_markdown(children(node));
+            match attr(node, "title").and_then(Value::as_str).filter(|t| !t.is_empty()) {
+                Some(title) => format!("**{}**\n\n{}", escape_markdown(title), content),
+                None => content,
+            }
+        }
+        "mediaSingle" | "mediaGroup" | "media" => "[anexo]".to_string(),
+        _ if node.get("content").is_some() => {
+            // Nós desconhecidos: preservar o conteúdo textual
+            if children(node).iter().all(is_inline) {
+                inline_to_markdown(children(node))
+            } else {
+                blocks_to_markdown(children(node))
+            }
+        }
+        _ => inline_to_markdown(std::slice::from_ref(node)),
+    }
+}
+
+fn list_to_markdown(node: &Value, marker: impl Fn(usize) -> String) -> String {
+    children(node).iter()
+        .enumerate()
+        .map(|(i, item)| {
+            let prefix = marker(i);
+            let content = item_to_markdown(children(item));
+            let padding = " ".repeat(prefix.len());
+            let mut lines = content.lines();
+            let first = lines.next().unwrap_or("");
+            let rest: Vec<String> = lines
+                .map(|line| if line.is_empty() { String::new() } else { format!("{}{}", padding, line) })
+                .collect();
+
+            if rest.is_empty() {
+                format!("{}{}", prefix, first)
+            } else {
+                format!("{}{}\n{}", prefix, first, rest.join("\n"))
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// Sublistas ficam coladas ao texto do item para manter a lista compacta
+fn item_to_markdown(nodes: &[Value]) -> String {
+    let mut out = String::new();
+    for node in nodes {
+        let block = block_to_markdown(node);
+        if block.is_empty() {
+            continue;
+        }
+        if !out.is_empty() {
+            let is_list = matches!(node_type(node), "bulletList" | "orderedList" | "taskList");
+            out.push_str(if is_list { "\n" } else { "\n\n" });
+        }
+        out.push_str(&block);
+    }
+    out
+}
+
+fn table_to_markdown(node: &Value) -> String {
+    let rows: Vec<Vec<String>> = children(node).iter()
+        .map(|row| children(row).iter()
+            .map(|cell| blocks_to_markdown(children(cell))
+                .replace('|', "\\|")
+                .replace("\n\n", "<br>")
+                .replace('\n', " "))
+            .collect())
+        .collect();
+
+    let columns = rows.iter().map(Vec::len).max().unwrap_or(0);
+    if columns == 0 {
+        return String::new();
+    }
+
+    let format_row = |cells: &[String]| {
+        let mut padded: Vec<&str> = cells.iter().map(String::as_str).collect();
+        padded.resize(columns, "");
+        format!("| {} |", padded.join(" | "))
+    };
+
+    // GFM exige linha de cabeçalho; a primeira linha do ADF é usada como tal
+    let mut lines = vec![
+        format_row(&rows[0]),
+        format!("|{}", " --- |".repeat(columns)),
+    ];
+    lines.extend(rows[1..].iter().map(|row| format_row(row)));
+    lines.join("\n")
+}
+
+fn inline_to_markdown(nodes: &[Value]) -> String {
+    let mut out = String::new();
+
+    for node in nodes {
+        match node_type(node) {
+            "text" => {
+                let text = node.get("text").and_then(Value::as_str).unwrap_or("");
+                out.push_str(&apply_markdown_marks(text, marks(node)));
+            }
+            "hardBreak" => out.push_str("  \n"),
+            "mention" => {
+                let id = attr(node, "id").and_then(Value::as_str).unwrap_or("");
+                let text = attr(node, "text").and_then(Value::as_str).unwrap_or("@");
+                let text = if text.starts_with('@') { text.to_string() } else { format!("@{}", text) };
+                out.push_str(&format!("[{}]({}{})", escape_markdown(&text), MENTION_SCHEME, id));
+            }
+            "emoji" => out.push_str(&emoji_text(node)),
+            "inlineCard" | "blockCard" => {
+                if let Some(url) = attr(node, "url").and_then(Value::as_str).and_then(safe_href) {
+                    out.push_str(&format!("<{}>", url));
+                }
+            }
+            "date" => out.push_str(&date_text(node)),
+            "status" => {
+                let text = attr(node, "text").and_then(Value::as_str).unwrap_or("");
+                out.push_str(&format!("`{}`", text.to_uppercase()));
+            }
+            "placeholder" => {}
+            _ => out.push_str(&block_to_markdown(node)),
+        }
+    }
+
+    out
+}
+
+fn apply_markdown_marks(text: &str, marks: &[Value]) -> String {
+    if text.is_empty() {
+        return String::new();
+    }
+
+    let has = |name: &str| marks.iter().any(|m| node_type(m) == name);
+
+    let mut result = if has("code") {
+        let ticks = if text.contains('`') { "``" } else { "`" };
+        format!("{}{}{}", ticks, text, ticks)
+    } else {
+        escape_markdown(text)
+    };
+
+    if has("em") {
+        result = format!("*{}*", result);
+    }
+    if has("strong") {
+        result = format!("**{}**", result);
+    }
+    if has("strike") {
+        result = format!("~~{}~~", result);
+    }
+    if let Some(href) = marks.iter()
+        .find(|m| node_type(m) == "link")
+        .and_then(|link| attr(link, "href").and_then(Value::as_str))
+        .and_then(safe_href)
+    {
+        result = format!("[{}]({})", result, href);
+    }
+
+    result
+}
+
+fn escape_markdown(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '*' | '_' | '`' | '[' | ']' | '~') {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+fn indent(text: &str, padding: &str) -> String {
+    text.lines()
+        .map(|line| format!("{}{}", padding, line))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// === ADF -> HTML ===
+
+pub fn to_html(doc: &Value) -> String {
+    if let Some(text) = doc.as_str() {
+        return format!("<p>{}</p>", escape_html(text).replace('\n', "<br>"));
+    }
+
+    blocks_to_html(children(doc))
+}
+
+fn blocks_to_html(nodes: &[Value]) -> String {
+    nodes.iter().map(block_to_html).collect()
+}
+
+fn block_to_html(node: &Value) -> String {
+    match node_type(node) {
+        "paragraph" => format!("<p>{}</p>", inline_to_html(children(node))),
+        "heading" => {
+            let level = attr(node, "level").and_then(Value::as_u64).unwrap_or(1).clamp(1, 6);
+            format!("<h{0}>{1}</h{0}>", level, inline_to_html(children(node)))
+        }
+        "bulletList" => format!("<ul>{}</ul>", blocks_to_html(children(node))),
+        "orderedList" => match attr(node, "order").and_then(Value::as_u64) {
+            Some(start) if start != 1 => format!("<ol start=\"{}\">{}</ol>", start, blocks_to_html(children(node))),
+            _ => format!("<ol>{}</ol>", blocks_to_html(children(node))),
+        },
+        "listItem" => format!("<li>{}</li>", blocks_to_html(children(node))),
+        "taskList" => format!("<ul class=\"task-list\">{}</ul>", blocks_to_html(children(node))),
+        "taskItem" => {
+            let checked = if attr(node, "state").and_then(Value::as_str) == Some("DONE") { " checked" } else { "" };
+            format!(
+                "<li><input type=\"checkbox\" disabled{}> {}</li>",
+                checked,
+                inline_to_html(children(node))
+            )
+        }
+        "decisionList" => format!("<ul class=\"decision-list\">{}</ul>", blocks_to_html(children(node))),
+        "decisionItem" => format!("<li>{}</li>", inline_to_html(children(node))),
+        "codeBlock" => {
+            let code: String = children(node).iter()
+                .filter_map(|n| n.get("text").and_then(Value::as_str))
+                .collect();
+            match attr(node, "language").and_then(Value::as_str).filter(|l| !l.is_empty()) {
+                Some(language) => format!(
+                    "<pre><code class=\"language-{}\">{}</code></pre>",
+                    escape_html(language),
+                    escape_html(&code)
+                ),
+                None => format!("<pre><code>{}</code></pre>", escape_html(&code)),
+            }
+        }
+        "blockquote" => format!("<blockquote>{}</blockquote>", blocks_to_html(children(node))),
+        "panel" => {
+            let panel_type = attr(node, "panelType").and_then(Value::as_str).unwrap_or("info");
+            format!(
+                "<div class=\"panel panel-{}\">{}</div>",
+                escape_html(panel_type),
+                blocks_to_html(children(node))
+            )
+        }
+        "rule" => "<hr>".to_string(),
+        "table" => format!("<table><tbody>{}</tbody></table>", blocks_to_html(children(node))),
+        "tableRow" => format!("<tr>{}</tr>", blocks_to_html(children(node))),
+        "tableHeader" => format!("<th>{}</th>", blocks_to_html(children(node))),
+        "tableCell" => format!("<td>{}</td>", blocks_to_html(children(node))),
+        "expand" | "nestedExpand" => {
+            let title = attr(node, "title").and_then(Value::as_str).unwrap_or("");
+            format!(
+                "<details><summary>{}</summary>{}</details>",
+                escape_html(title),
+                blocks_to_html(children(node))
+            )
+        }
+        "mediaSingle" | "mediaGroup" | "media" => "<span class=\"media\">[anexo]</span>".to_string(),
+        _ if node.get("content").is_some() => {
+            if children(node).iter().all(is_inline) {
+                inline_to_html(children(node))
+            } else {
+                blocks_to_html(children(node))
+            }
+        }
+        _ => inline_to_html(std::slice::from_ref(node)),
+    }
+}
+
+fn inline_to_html(nodes: &[Value]) -> String {
+    let mut out = String::new();
+
+    for node in nodes {
+        match node_type(node) {
+            "text" => {
+                let text = node.get("text").and_then(Value::as_str).unwrap_or("");
+                out.push_str(&apply_html_marks(text, marks(node)));
+            }
+            "hardBreak" => out.push_str("<br>"),
+            "mention" => {
+                let id = attr(node, "id").and_then(Value::as_str).unwrap_or("");
+                let text = attr(node, "text").and_then(Value::as_str).unwrap_or("@");
+                out.push_str(&format!(
+                    "<span class=\"mention\" data-account-id=\"{}\">{}</span>",
+                    escape_html(id),
+                    escape_html(text)
+                ));
+            }
+            "emoji" => out.push_str(&escape_html(&emoji_text(node))),
+            "inlineCard" | "blockCard" => {
+                if let Some(url) = attr(node, "url").and_then(Value::as_str).and_then(safe_href) {
+                    out.push_str(&format!("<a href=\"{0}\">{0}</a>", escape_html(url)));
+                }
+            }
+            "date" => out.push_str(&format!("<time>{}</time>", date_text(node))),
+            "status" => {
+                let text = attr(node, "text").and_then(Value::as_str).unwrap_or("");
+                let color = attr(node, "color").and_then(Value::as_str).unwrap_or("neutral");
+                out.push_str(&format!(
+                    "<span class=\"status status-{}\">{}</span>",
+                    escape_html(color),
+                    escape_html(text)
+                ));
+            }
+            "placeholder" => {}
+            _ => out.push_str(&block_to_html(node)),
+        }
+    }
+
+    out
+}
+
+fn apply_html_marks(text: &str, marks: &[Value]) -> String {
+    let mut result = escape_html(text);
+
+    for mark in marks {
+        result = match node_type(mark) {
+            "code" => format!("<code>{}</code>", result),
+            "em" => format!("<em>{}</em>", result),
+            "strong" => format!("<strong>{}</strong>", result),
+            "strike" => format!("<s>{}</s>", result),
+            "underline" => format!("<u>{}</u>", result),
+            "subsup" => match attr(mark, "type").and_then(Value::as_str) {
+                Some("sup") => format!("<sup>{}</sup>", result),
+                _ => format!("<sub>{}</sub>", result),
+            },
+            // Apenas cores hexadecimais: o valor vai para um atributo style
+            "textColor" => match attr(mark, "color").and_then(Value::as_str).filter(|c| is_hex_color(c)) {
+                Some(color) => format!("<span style=\"color: {}\">{}</span>", color, result),
+                None => result,
+            },
+            // Link com esquema não permitido: manter só o texto
+            "link" => match attr(mark, "href").and_then(Value::as_str).and_then(safe_href) {
+                Some(href) => format!("<a href=\"{}\">{}</a>", escape_html(href), result),
+                None => result,
+            },
+            _ => result,
+        };
+    }
+
+    result
+}
+
+// URL de link aceitável: relativa ou com esquema em SAFE_LINK_SCHEMES.
+// Espaços e caracteres de controle são ignorados pelos navegadores ao ler o
+// esquema ("java\tscript:"), então também são ignorados aqui.
+fn safe_href(href: &str) -> Option<&str> {
+    let href = href.trim();
+    let normalized: String = href.chars()
+        .filter(|c| !c.is_whitespace() && !c.is_control())
+        .collect();
+
+    let scheme_end = normalized.find([':', '/', '?', '#']);
+    match scheme_end {
+        Some(end) if normalized[end..].starts_with(':') => {
+            let scheme = normalized[..end].to_ascii_lowercase();
+            SAFE_LINK_SCHEMES.contains(&scheme.as_str()).then_some(href)
+        }
+        _ => Some(href),
+    }
+}
+
+fn is_hex_color(color: &str) -> bool {
+    color.strip_prefix('#')
+        .is_some_and(|hex| matches!(hex.len(), 3 | 4 | 6 | 8) && hex.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+// === MARKDOWN -> WIKI MARKUP ===
+
+// Jira Server/Data Center (API v2) recebe textos ricos em wiki markup, não ADF.
+// A conversão passa pelo ADF para reaproveitar o parser de Markdown.
+pub fn markdown_to_wiki(markdown: &str) -> String {
+    to_wiki(&from_markdown(markdown))
+}
+
+pub fn to_wiki(doc: &Value) -> String {
+    if let Some(text) = doc.as_str() {
+        return text.to_string();
+    }
+
+    blocks_to_wiki(children(doc)).trim_end().to_string()
+}
+
+fn blocks_to_wiki(nodes: &[Value]) -> String {
+    nodes.iter()
+        .map(|node| block_to_wiki(node, ""))
+        .filter(|block| !block.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+// `bullets` acumula os marcadores das listas externas, ex.: "*#" para item numerado dentro de lista
+fn block_to_wiki(node: &Value, bullets: &str) -> String {
+    match node_type(node) {
+        "paragraph" => inline_to_wiki(children(node)),
+        "heading" => {
+            let level = attr(node, "level").and_then(Value::as_u64).unwrap_or(1).clamp(1, 6);
+            format!("h{}. {}", level, inline_to_wiki(children(node)))
+        }
+        "bulletList" | "taskList" | "decisionList" => list_to_wiki(node, &format!("{}*", bullets)),
+        "orderedList" => list_to_wiki(node, &format!("{}#", bullets)),
+        "codeBlock" => {
+            let code: String = children(node).iter()
+                .filter_map(|n| n.get("text").and_then(Value::as_str))
+                .collect();
+            match attr(node, "language").and_then(Value::as_str).filter(|l| !l.is_empty()) {
+                Some(language) => format!("{{code:{}}}\n{}\n{{code}}", language, code),
+                None => format!("{{code}}\n{}\n{{code}}", code),
+            }
+        }
+        "blockquote" | "panel" => format!("{{quote}}\n{}\n{{quote}}", blocks_to_wiki(children(node))),
+        "rule" => "----".to_string(),
+        "table" => children(node).iter()
+            .map(|row| {
+                let cells: Vec<(bool, String)> = children(row).iter()
+                    .map(|cell| (
+                        node_type(cell) == "tableHeader",
+                        blocks_to_wiki(children(cell)).replace("\n\n", " \\\\ ").replace('\n', " "),
+                    ))
+                    .collect();
+                let mut line = String::new();
+                for (header, text) in &cells {
+                    line.push_str(if *header { "||" } else { "|" });
+                    line.push_str(text);
+                }
+                let closing = cells.last().map(|(header, _)| if *header { "||" } else { "|" }).unwrap_or("|");
+                line.push_str(closing);
+                line
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+        "mediaSingle" | "mediaGroup" | "media" => "[anexo]".to_string(),
+        _ if node.get("content").is_some() => {
+            if children(node).iter().all(is_inline) {
+                inline_to_wiki(children(node))
+            } else {
+                blocks_to_wiki(children(node))
+            }
+        }
+        _ => inline_to_wiki(std::slice::from_ref(node)),
+    }
+}
+
+// Wiki markup não tem indentação: o nível da sublista vem da repetição dos marcadores
+fn list_to_wiki(node: &Value, bullets: &str) -> String {
+    let mut lines = Vec::new();
+
+    for item in children(node) {
+        if matches!(node_type(item), "bulletList" | "orderedList" | "taskList") {
+            lines.push(block_to_wiki(item, bullets));
+            continue;
+        }
+
+        let mut text = Vec::new();
+        let mut sublists = Vec::new();
+        let inline_item = children(item).iter().all(is_inline);
+
+        if inline_item {
+            text.push(inline_to_wiki(children(item)));
+        } else {
+            for child in children(item) {
+                match node_type(child) {
+                    "bulletList" | "orderedList" | "taskList" => sublists.push(block_to_wiki(child, bullets)),
+                    _ => text.push(block_to_wiki(child, bullets)),
+                }
+            }
+        }
+
+        let prefix = if node_type(item) == "taskItem" {
+            let done = attr(item, "state").and_then(Value::as_str) == Some("DONE");
+            if done { "☑ " } else { "☐ " }
+        } else {
+            ""
+        };
+
+        lines.push(format!("{} {}{}", bullets, prefix, text.join(" \\\\ ")));
+        lines.extend(sublists);
+    }
+
+    lines.join("\n")
+}
+
+fn inline_to_wiki(nodes: &[Value]) -> String {
+    let mut out = String::new();
+
+    for node in nodes {
+        match node_type(node) {
+            "text" => {
+                let text = node.get("text").and_then(Value::as_str).unwrap_or("");
+                out.push_str(&apply_wiki_marks(text, marks(node)));
+            }
+            "hardBreak" => out.push_str("\\\\\n"),
+            // No Data Center o identificador da menção é o nome de usuário
+            "mention" => {
+                let id = attr(node, "id").and_then(Value::as_str).unwrap_or("");
+                out.push_str(&format!("[~{}]", id));
+            }
+            "emoji" => out.push_str(&emoji_text(node)),
+            "inlineCard" | "blockCard" => {
+                if let Some(url) = attr(node, "url").and_then(Value::as_str) {
+                    out.push_str(&format!("[{}]", url));
+                }
+            }
+            "date" => out.push_str(&date_text(node)),
+            "status" => {
+                let text = attr(node, "text").and_then(Value::as_str).unwrap_or("");
+                out.push_str(&format!("*{}*", text.to_uppercase()));
+            }
+            "placeholder" => {}
+            _ => out.push_str(&block_to_wiki(node, "")),
+        }
+    }
+
+    out
+}
+
+fn apply_wiki_marks(text: &str, marks: &[Value]) -> String {
+    if text.is_empty() {
+        return String::new();
+    }
+
+    let has = |name: &str| marks.iter().any(|m| node_type(m) == name);
+
+    let mut result = if has("code") {
+        format!("{{{{{}}}}}", text)
+    } else {
+        escape_wiki(text)
+    };
+
+    if has("em") {
+        result = format!("_{}_", result);
+    }
+    if has("strong") {
+        result = format!("*{}*", result);
+    }
+    if has("strike") {
+        result = format!("-{}-", result);
+    }
+    if let Some(link) = marks.iter().find(|m| node_type(m) == "link") {
+        let href = attr(link, "href").and_then(Value::as_str).unwrap_or("");
+        result = format!("[{}|{}]", result, href);
+    }
+
+    result
+}
+
+fn escape_wiki(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '*' | '_' | '{' | '}' | '[' | ']' | '|') {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+// === MARKDOWN -> ADF ===
+
+pub fn from_markdown(markdown: &str) -> Value {
+    let mut options = Options::empty();
+    options.insert(Options::ENABLE_TABLES);
+    options.insert(Options::ENABLE_STRIKETHROUGH);
+    options.insert(Options::ENABLE_TASKLISTS);
+
+    let mut builder = AdfBuilder::new();
+    for event in Parser::new_ext(markdown, options) {
+        builder.handle(event);
+    }
+    builder.finish()
+}
+
+struct PendingNode {
+    kind: &'static str,
+    attrs: Map<String, Value>,
+    content: Vec<Value>,
+    // Parágrafo criado automaticamente para envolver texto solto
+    implicit: bool,
+}
+
+impl PendingNode {
+    fn new(kind: &'static str) -> Self {
+        Self { kind, attrs: Map::new(), content: Vec::new(), implicit: false }
+    }
+
+    fn with_attr(mut self, key: &str, value: Value) -> Self {
+        self.attrs.insert(key.to_string(), value);
+        self
+    }
+
+    fn into_value(self) -> Value {
+        let mut node = json!({ "type": self.kind });
+        if !self.attrs.is_empty() {
+            node["attrs"] = Value::Object(self.attrs);
+        }
+        // Nós folha como "rule" não possuem conteúdo
+        if !matches!(self.kind, "rule") {
+            node["content"] = Value::Array(self.content);
+        }
+        node
+    }
+}
+
+struct AdfBuilder {
+    stack: Vec<PendingNode>,
+    marks: Vec<Value>,
+    // Menção em construção: (accountId, texto acumulado)
+    mention: Option<(String, String)>,
+    in_table_head: bool,
+    in_code_block: bool,
+}
+
+impl AdfBuilder {
+    fn new() -> Self {
+        Self {
+            stack: vec![PendingNode::new("doc")],
+            marks: Vec::new(),
+            mention: None,
+            in_table_head: false,
+            in_code_block: false,
+        }
+    }
+
+    fn finish(mut self) -> Value {
+        while self.stack.len() > 1 {
+            self.close();
+        }
+
+        let doc = self.stack.pop().expect("documento raiz");
+        json!({ "type": "doc", "version": 1, "content": doc.content })
+    }
+
+    fn handle(&mut self, event: Event) {
+        match event {
+            Event::Start(tag) => self.start(tag),
+            Event::End(tag) => self.end(tag),
+            Event::Text(text) => self.text(&text),
+            Event::Code(code) => {
+                let mut marks = vec![json!({ "type": "code" })];
+                marks.extend(self.marks.iter().filter(|m| node_type(m) == "link").cloned());
+                self.push_inline(text_node(&code, &marks));
+            }
+            Event::Html(html) | Event::InlineHtml(html) => self.text(&html),
+            // Quebras simples viram quebras de linha, como o usuário digitou
+            Event::SoftBreak | Event::HardBreak => {
+                if self.in_code_block {
+                    self.text("\n");
+                } else {
+                    self.push_inline(json!({ "type": "hardBreak" }));
+                }
+            }
+            Event::Rule => {
+                self.close_implicit_paragraph();
+                self.push_block(PendingNode::new("rule").into_value());
+            }
+            Event::TaskListMarker(done) => self.mark_task_item(done),
+            _ => {}
+        }
+    }
+
+    fn start(&mut self, tag: Tag) {
+        match tag {
+            Tag::Paragraph => self.open_block(PendingNode::new("paragraph")),
+            Tag::Heading { level, .. } => {
+                self.open_block(PendingNode::new("heading").with_attr("level", json!(level as u8)));
+            }
+            Tag::BlockQuote(_) => self.open_block(PendingNode::new("blockquote")),
+            Tag::CodeBlock(kind) => {
+                let mut node = PendingNode::new("codeBlock");
+                if let CodeBlockKind::Fenced(language) = kind {
+                    let language = language.split_whitespace().next().unwrap_or("");
+                    if !language.is_empty() {
+                        node = node.with_attr("language", json!(language));
+                    }
+                }
+                self.open_block(node);
+                self.in_code_block = true;
+            }
+            Tag::List(Some(start)) => self.open_block(PendingNode::new("orderedList").with_attr("order", json!(start))),
+            Tag::List(None) => self.open_block(PendingNode::new("bulletList")),
+            Tag::Item => self.open_block(PendingNode::new("listItem")),
+            Tag::Table(_) => self.open_block(PendingNode::new("table")),
+            Tag::TableHead => {
+                self.in_table_head = true;
+                self.open_block(PendingNode::new("tableRow"));
+            }
+            Tag::TableRow => self.open_block(PendingNode::new("tableRow")),
+            Tag::TableCell => {
+                let kind = if self.in_table_head { "tableHeader" } else { "tableCell" };
+                self.open_block(PendingNode::new(kind));
+            }
+            Tag::Emphasis => self.marks.push(json!({ "type": "em" })),
+            Tag::Strong => self.marks.push(json!({ "type": "strong" })),
+            Tag::Strikethrough => self.marks.push(json!({ "type": "strike" })),
+            Tag::Link { dest_url, .. } | Tag::Image { dest_url, .. } => {
+                match dest_url.strip_prefix(MENTION_SCHEME) {
+                    Some(account_id) => self.mention = Some((account_id.to_string(), String::new())),
+                    None => self.marks.push(json!({ "type": "link", "attrs": { "href": dest_url.to_string() } })),
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn end(&mut self, tag: TagEnd) {
+        match tag {
+            TagEnd::Emphasis | TagEnd::Strong | TagEnd::Strikethrough => {
+                self.marks.pop();
+            }
+            TagEnd::Link | TagEnd::Image => {
+                if let Some((id, text)) = self.mention.take() {
+                    let text = if text.starts_with('@') { text } else { format!("@{}", text) };
+                    self.push_inline(json!({ "type": "mention", "attrs": { "id": id, "text": text } }));
+                } else {
+                    self.marks.pop();
+                }
+            }
+            TagEnd::CodeBlock => {
+                self.in_code_block = false;
+                // O parser inclui a quebra de linha final do bloco
+                if let Some(last) = self.top().content.last_mut() {
+                    if let Some(text) = last.get("text").and_then(Value::as_str) {
+                        let trimmed = text.strip_suffix('\n').unwrap_or(text).to_string();
+                        last["text"] = json!(trimmed);
+                    }
+                }
+                self.top().content.retain(|n| n.get("text").and_then(Value::as_str) != Some(""));
+                self.close();
+            }
+            TagEnd::TableHead => {
+                self.close_to(&["tableRow"]);
+                self.in_table_head = false;
+            }
+            TagEnd::Paragraph | TagEnd::Heading(_) | TagEnd::BlockQuote(_) | TagEnd::List(_)
+            | TagEnd::Item | TagEnd::Table | TagEnd::TableRow | TagEnd::TableCell => {
+                self.close_implicit_paragraph();
+                self.close();
+            }
+            _ => {}
+        }
+    }
+
+    fn text(&mut self, text: &str) {
+        if let Some((_, mention_text)) = self.mention.as_mut() {
+            mention_text.push_str(text);
+            return;
+        }
+
+        if self.in_code_block {
+            // Blocos de código aceitam apenas texto sem marcas
+            match self.top().content.last_mut() {
+                Some(last) => {
+                    let current = last["text"].as_str().unwrap_or("").to_string();
+                    last["text"] = json!(current + text);
+                }
+                None => self.top().content.push(json!({ "type": "text", "text": text })),
+            }
+            return;
+        }
+
+        let marks = self.marks.clone();
+        self.push_inline(text_node(text, &marks));
+    }
+
+    fn push_inline(&mut self, node: Value) {
+        if node_type(&node) == "text" && node["text"].as_str().map_or(true, str::is_empty) {
+            return;
+        }
+
+        // Listas compactas e células de tabela trazem texto sem parágrafo
+        if !matches!(self.top().kind, "paragraph" | "heading" | "codeBlock" | "taskItem") {
+            let mut paragraph = PendingNode::new("paragraph");
+            paragraph.implicit = true;
+            self.stack.push(paragraph);
+        }
+        self.top().content.push(node);
+    }
+
+    fn push_block(&mut self, node: Value) {
+        self.top().content.push(node);
+    }
+
+    fn open_block(&mut self, node: PendingNode) {
+        self.close_implicit_paragraph();
+        self.stack.push(node);
+    }
+
+    fn close(&mut self) {
+        if self.stack.len() <= 1 {
+            return;
+        }
+        let node = self.stack.pop().expect("nó aberto");
+
+        // Itens de tarefa aceitam apenas conteúdo inline
+        if node.kind == "paragraph" && self.top().kind == "taskItem" {
+            let parent = self.top();
+            if !parent.content.is_empty() && !node.content.is_empty() {
+                parent.content.push(json!({ "type": "hardBreak" }));
+            }
+            parent.content.extend(node.content);
+            return;
+        }
+
+        let value = node.into_value();
+        self.top().content.push(value);
+    }
+
+    fn close_to(&mut self, kinds: &[&str]) {
+        while self.stack.len() > 1 {
+            let done = kinds.contains(&self.top().kind);
+            self.close();
+            if done {
+                break;
+            }
+        }
+    }
+
+    fn close_implicit_paragraph(&mut self) {
+        if self.top().implicit {
+            self.close();
+        }
+    }
+
+    // Converter o item atual em tarefa e a lista pai em lista de tarefas
+    fn mark_task_item(&mut self, done: bool) {
+        self.close_implicit_paragraph();
+
+        // Em listas espaçadas o marcador chega dentro do parágrafo do item
+        let item_index = match self.stack.iter().rposition(|n| n.kind == "listItem") {
+            Some(index) if index + 2 >= self.stack.len() && index > 0 => index,
+            _ => return,
+        };
+
+        let state = if done { "DONE" } else { "TODO" };
+        let item = &mut self.stack[item_index];
+        item.kind = "taskItem";
+        item.attrs.insert("localId".to_string(), json!(local_id()));
+        item.attrs.insert("state".to_string(), json!(state));
+
+        let list = &mut self.stack[item_index - 1];
+        if list.kind != "taskList" {
+            list.kind = "taskList";
+            list.attrs.clear();
+            list.attrs.insert("localId".to_string(), json!(local_id()));
+        }
+    }
+
+    fn top(&mut self) -> &mut PendingNode {
+        self.stack.last_mut().expect("pilha de nós vazia")
+    }
+}
+
+fn text_node(text: &str, marks: &[Value]) -> Value {
+    let mut node = json!({ "type": "text", "text": text });
+    if !marks.is_empty() {
+        node["marks"] = Value::Array(marks.to_vec());
+    }
+    node
+}
+
+fn local_id() -> String {
+    use rand::RngCore;
+    let mut bytes = [0u8; 8];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+// === AUXILIARES ===
+
+fn node_type(node: &Value) -> &str {
+    node.get("type").and_then(Value::as_str).unwrap_or("")
+}
+
+fn children(node: &Value) -> &[Value] {
+    node.get("content").and_then(Value::as_array).map(Vec::as_slice).unwrap_or(&[])
+}
+
+fn marks(node: &Value) -> &[Value] {
+    node.get("marks").and_then(Value::as_array).map(Vec::as_slice).unwrap_or(&[])
+}
+
+fn attr<'a>(node: &'a Value, key: &str) -> Option<&'a Value> {
+    node.get("attrs").and_then(|a| a.get(key))
+}
+
+fn is_inline(node: &Value) -> bool {
+    matches!(
+        node_type(node),
+        "text" | "hardBreak" | "mention" | "emoji" | "inlineCard" | "date" | "status" | "placeholder"
+    )
+}
+
+fn emoji_text(node: &Value) -> String {
+    attr(node, "text")
+        .or_else(|| attr(node, "shortName"))
+        .and_then(Value::as_str)
+        .unwrap_or("")
+        .to_string()
+}
+
+fn date_text(node: &Value) -> String {
+    attr(node, "timestamp")
+        .and_then(|t| t.as_str().and_then(|s| s.parse::<i64>().ok()).or_else(|| t.as_i64()))
+        .and_then(|millis| Utc.timestamp_millis_opt(millis).single())
+        .map(|date| date.format("%Y-%m-%d").to_string())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn doc(content: Value) -> Value {
+        json!({ "type": "doc", "version": 1, "content": content })
+    }
+
+    fn paragraph(content: Value) -> Value {
+        json!({ "type": "paragraph", "content": content })
+    }
+
+    fn round_trip(markdown: &str) -> String {
+        to_markdown(&from_markdown(markdown))
+    }
+
+    #[test]
+    fn markdown_round_trip_keeps_formatting() {
+        for markdown in [
+            "Texto **negrito**, *itálico*, ~~riscado~~ e `código`",
+            "# Título\n\nParágrafo",
+            "- um\n- dois\n- três",
+            "1. primeiro\n2. segundo",
+            "> citação",
+            "```rust\nfn main() {}\n```",
+            "[site](https://example.com)",
+            "Linha com caracteres \\*literais\\* e \\[colchetes\\]",
+        ] {
+            assert_eq!(round_trip(markdown), markdown);
+        }
+    }
+
+    #[test]
+    fn mention_round_trip() {
+        let markdown = "Oi [@Maria](accountid:5b10ac8d82e05b22cc7d4ef5)";
+        let adf = from_markdown(markdown);
+
+        let mention = &adf["content"][0]["content"][1];
+        assert_eq!(mention["type"], "mention");
+        assert_eq!(mention["attrs"]["id"], "5b10ac8d82e05b22cc7d4ef5");
+        assert_eq!(to_markdown(&adf), markdown);
+    }
+
+    #[test]
+    fn plain_text_from_server_is_kept() {
+        // Jira Server/Data Center (API v2) devolve texto simples
+        let text = json!("linha 1\n<b>linha 2</b>");
+
+        assert_eq!(to_markdown(&text), "linha 1\n<b>linha 2</b>");
+        assert_eq!(to_html(&text), "<p>linha 1<br>&lt;b&gt;linha 2&lt;/b&gt;</p>");
+    }
+
+    #[test]
+    fn html_escapes_text_and_attributes() {
+        let adf = doc(json!([
+            paragraph(json!([
+                { "type": "text", "text": "<script>alert('x')</script> & \"aspas\"" },
+                { "type": "mention", "attrs": { "id": "\"><img src=x>", "text": "@<b>" } }
+            ]))
+        ]));
+
+        let html = to_html(&adf);
+
+        assert!(!html.contains("<script>"));
+        assert!(!html.contains("<img"));
+        assert!(!html.contains("<b>"));
+        assert!(html.contains("&lt;script&gt;alert(&#39;x&#39;)&lt;/script&gt; &amp; &quot;aspas&quot;"));
+    }
+
+    #[test]
+    fn html_links_allow_only_safe_schemes() {
+        let link = |href: &str| {
+            let adf = doc(json!([paragraph(json!([
+                { "type": "text", "text": "clique", "marks": [{ "type": "link", "attrs": { "href": href } }] }
+            ]))]));
+            to_html(&adf)
+        };
+
+        assert_eq!(link("https://example.com/?a=1&b=2"), "<p><a href=\"https://example.com/?a=1&amp;b=2\">clique</a></p>");
+        assert_eq!(link("mailto:dev@example.com"), "<p><a href=\"mailto:dev@example.com\">clique</a></p>");
+        assert_eq!(link("/browse/ABC-1"), "<p><a href=\"/browse/ABC-1\">clique</a></p>");
+
+        for href in ["javascript:alert(1)", "JavaScript:alert(1)", " java\tscript:alert(1)", "data:text/html,<b>x</b>", "vbscript:x"] {
+            assert_eq!(link(href), "<p>clique</p>", "{}", href);
+        }
+    }
+
+    #[test]
+    fn cards_and_markdown_links_drop_unsafe_urls() {
+        let adf = doc(json!([paragraph(json!([
+            { "type": "inlineCard", "attrs": { "url": "javascript:alert(1)" } },
+            { "type": "text", "text": "x", "marks": [{ "type": "link", "attrs": { "href": "data:text/html,x" } }] }
+        ]))]));
+
+        assert_eq!(to_html(&adf), "<p>x</p>");
+        assert_eq!(to_markdown(&adf), "x");
+    }
+
+    #[test]
+    fn text_color_accepts_only_hex() {
+        let colored = |color: &str| {
+            let adf = doc(json!([paragraph(json!([
+                { "type": "text", "text": "x", "marks": [{ "type": "textColor", "attrs": { "color": color } }] }
+            ]))]));
+            to_html(&adf)
+        };
+
+        assert_eq!(colored("#ff5630"), "<p><span style=\"color: #ff5630\">x</span></p>");
+        assert_eq!(colored("red; background: url(https://evil)"), "<p>x</p>");
+    }
+
+    #[test]
+    fn markdown_to_wiki_converts_common_syntax() {
+        let wiki = markdown_to_wiki("# Título\n\n**negrito** e [site](https://example.com)\n\n- item");
+
+        assert!(wiki.contains("h1. Título"), "{}", wiki);
+        assert!(wiki.contains("*negrito*"), "{}", wiki);
+        assert!(wiki.contains("[site|https://example.com]"), "{}", wiki);
+        assert!(wiki.contains("* item"), "{}", wiki);
+    }
+}
@@ -1,29 +1,86 @@
-use crate::jira::{config_manager::JiraConfigManager, error::JiraError};
-use base64::{Engine as _, engine::general_purpose};
+use std::sync::{Arc, Mutex};
+use tokio::sync::OnceCell;
+use crate::jira::{config_manager::{JiraConfigManager, JiraAuthMode}, error::JiraError, oauth::{self, OAuthSession, OAuthSessionStore}};
 
 pub struct JiraAuth {
-    config_manager: JiraConfigManager,
+    config_manager: Arc<JiraConfigManager>,
+    // Aberto no primeiro uso em modo OAuth e mantido junto com o cliente
+    session_store: OnceCell<OAuthSessionStore>,
+    // Última sessão lida; o armazenamento só é consultado de novo perto da expiração
+    session: Mutex<Option<OAuthSession>>,
 }
 
 impl JiraAuth {
-    pub fn new() -> Result<Self, JiraError> {
-        Ok(Self {
-            config_manager: JiraConfigManager::new()?,
-        })
+    pub fn for_profile(profile_id: &str) -> Result<Self, JiraError> {
+        Ok(Self::with_manager(Arc::new(JiraConfigManager::for_profile(profile_id)?)))
     }
 
-    pub fn get_auth_header(&self) -> Result<Option<String>, JiraError> {
-        if let Some(config) = self.config_manager.get_config()? {
-            let credentials = format!("{}:{}", config.email, config.token);
-            let encoded = general_purpose::STANDARD.encode(credentials);
-            Ok(Some(format!("Basic {}", encoded)))
-        } else {
-            Ok(None)
+    // Compartilhar o gerenciador (e o cache de configuração) com o JiraClient, para
+    // que um token OAuth renovado aqui seja visto nas requisições seguintes
+    pub fn with_manager(config_manager: Arc<JiraConfigManager>) -> Self {
+        Self {
+            config_manager,
+            session_store: OnceCell::new(),
+            session: Mutex::new(None),
         }
     }
 
+    // No modo OAuth o access token é renovado aqui antes de expirar
+    pub async fn get_auth_header(&self) -> Result<Option<String>, JiraError> {
+        let Some(mut config) = self.config_manager.get_config()? else {
+            return Ok(None);
+        };
 
-    pub fn update_last_used(&self) -> Result<(), JiraError> {
+        if config.auth_mode == JiraAuthMode::OAuth {
+            let profile_id = self.config_manager.profile_id();
+            let store = self.session_store
+                .get_or_try_init(|| async { OAuthSessionStore::for_profile(profile_id) })
+                .await?;
+
+            if self.cached_session(store)?.needs_refresh() {
+                let lock = oauth::refresh_lock(profile_id);
+                let _guard = lock.lock().await;
+
+                // Outra requisição (ou outro cliente do perfil) pode ter renovado o token
+                // enquanto aguardávamos o lock: reler do armazenamento
+                let mut session = load_session(store)?;
+                if session.needs_refresh() {
+                    let token = oauth::refresh_session(&mut session).await?;
+                    // Gravar só o token sobre a versão atual do armazenamento, não sobre a cópia em memória
+                    if let Some(current) = self.config_manager.reload_config()? {
+                        config = current;
+                    }
+                    config.token = token;
+                    self.config_manager.save_config(&config)?;
+                    store.save(&session)?;
+                } else if let Some(current) = self.config_manager.reload_config()? {
+                    config = current;
+                }
+                *self.session.lock().unwrap_or_else(|e| e.into_inner()) = Some(session);
+            }
+        }
+
+        Ok(Some(config.auth_header()))
+    }
+
+    pub fn update_last_used(&self) {
         self.config_manager.update_last_used()
     }
-}
\ No newline at end of file
+
+    // === MÉTODOS PRIVADOS ===
+
+    fn cached_session(&self, store: &OAuthSessionStore) -> Result<OAuthSession, JiraError> {
+        let mut cached = self.session.lock().unwrap_or_else(|e| e.into_inner());
+        match cached.as_ref() {
+            Some(session) => Ok(session.clone()),
+            None => Ok(cached.insert(load_session(store)?).clone()),
+        }
+    }
+}
+
+// === FUNÇÕES PRIVADAS ===
+
+fn load_session(store: &OAuthSessionStore) -> Result<OAuthSession, JiraError> {
+    store.load()?
+        .ok_or_else(|| JiraError::OAuth("Sessão OAuth não encontrada; faça login novamente".to_string()))
+}
@@ -0,0 +1,908 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+#[cfg(unix)]
+use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection, OptionalExtension, Transaction};
+use serde::{Serialize, Deserialize, de::DeserializeOwned};
+use ts_rs::TS;
+use crate::jira::error::JiraError;
+use crate::jira::paths::goji_config_dir;
+use crate::jira::client::{JiraIssue, JiraProject, JiraUser};
+
+// Dados mais novos que isso são servidos sem consultar o JIRA
+const FRESH_FOR_SECS: i64 = 120;
+
+// Incrementar ao adicionar uma etapa em `migrate`
+const SCHEMA_VERSION: i32 = 4;
+
+// Busca local: resultados padrão/máximo e palavras em volta do trecho encontrado
+pub const LOCAL_SEARCH_LIMIT: u32 = 20;
+const LOCAL_SEARCH_MAX_LIMIT: u32 = 100;
+const SNIPPET_TOKENS: i32 = 12;
+
+// Delimitadores dos termos destacados no trecho (caracteres de controle, que não
+// aparecem no texto das issues)
+pub const MATCH_START: &str = "\u{2}";
+pub const MATCH_END: &str = "\u{3}";
+
+// Busca aproximada: candidatas avaliadas e fração mínima dos trigramas de cada
+// termo que precisa aparecer no texto da issue
+const FUZZY_CANDIDATES: u32 = 200;
+const FUZZY_MIN_SIMILARITY: f64 = 0.5;
+const FUZZY_MAX_TRIGRAMS: usize = 48;
+
+const SCHEMA_V1: &str = "
+    CREATE TABLE IF NOT EXISTS issues (
+        profile_id TEXT NOT NULL,
+        item_key TEXT NOT NULL,
+        updated TEXT,
+        fetched_at TEXT NOT NULL,
+        data TEXT NOT NULL,
+        PRIMARY KEY (profile_id, item_key)
+    );
+    CREATE TABLE IF NOT EXISTS projects (
+        profile_id TEXT NOT NULL,
+        item_key TEXT NOT NULL,
+        updated TEXT,
+        fetched_at TEXT NOT NULL,
+        data TEXT NOT NULL,
+        PRIMARY KEY (profile_id, item_key)
+    );
+    CREATE TABLE IF NOT EXISTS users (
+        profile_id TEXT NOT NULL,
+        item_key TEXT NOT NULL,
+        updated TEXT,
+        fetched_at TEXT NOT NULL,
+        data TEXT NOT NULL,
+        PRIMARY KEY (profile_id, item_key)
+    );
+    -- Resultado de cada consulta: chaves dos registros, na ordem da resposta
+    CREATE TABLE IF NOT EXISTS queries (
+        profile_id TEXT NOT NULL,
+        kind TEXT NOT NULL,
+        query TEXT NOT NULL,
+        max_results INTEGER NOT NULL,
+        item_keys TEXT NOT NULL,
+        fetched_at TEXT NOT NULL,
+        PRIMARY KEY (profile_id, kind, query, max_results)
+    );
+";
+
+// Sincronização incremental: horário da última busca por perfil e marcação de
+// consultas que ficaram desatualizadas por mudanças recebidas
+const SCHEMA_V2: &str = "
+    CREATE TABLE IF NOT EXISTS sync_state (
+        profile_id TEXT PRIMARY KEY,
+        last_sync TEXT NOT NULL
+    );
+    ALTER TABLE queries ADD COLUMN expired INTEGER NOT NULL DEFAULT 0;
+";
+
+// Busca local: texto de cada issue e dois índices FTS5 sobre ele, um por palavra
+// (prefixos, sem acentos) e outro por trigramas (tolerante a erros de digitação).
+// Os gatilhos mantêm os índices em dia com `issue_text`.
+const SCHEMA_V3: &str = "
+    CREATE TABLE IF NOT EXISTS issue_text (
+        id INTEGER PRIMARY KEY,
+        profile_id TEXT NOT NULL,
+        item_key TEXT NOT NULL,
+        summary TEXT NOT NULL DEFAULT '',
+        description TEXT NOT NULL DEFAULT '',
+        comments TEXT NOT NULL DEFAULT '',
+        UNIQUE (profile_id, item_key)
+    );
+    CREATE VIRTUAL TABLE IF NOT EXISTS issue_words USING fts5(
+        item_key, summary, description, comments,
+        content = 'issue_text', content_rowid = 'id',
+        tokenize = 'unicode61 remove_diacritics 2', prefix = '2 3'
+    );
+    CREATE VIRTUAL TABLE IF NOT EXISTS issue_trigrams USING fts5(
+        summary, description, comments,
+        content = 'issue_text', content_rowid = 'id',
+        tokenize = 'trigram remove_diacritics 1'
+    );
+    CREATE TRIGGER IF NOT EXISTS issue_text_insert AFTER INSERT ON issue_text BEGIN
+        INSERT INTO issue_words (rowid, item_key, summary, description, comments)
+        VALUES (new.id, new.item_key, new.summary, new.description, new.comments);
+        INSERT INTO issue_trigrams (rowid, summary, description, comments)
+        VALUES (new.id, new.summary, new.description, new.comments);
+    END;
+    CREATE TRIGGER IF NOT EXISTS issue_text_delete AFTER DELETE ON issue_text BEGIN
+        INSERT INTO issue_words (issue_words, rowid, item_key, summary, description, comments)
+        VALUES ('delete', old.id, old.item_key, old.summary, old.description, old.comments);
+        INSERT INTO issue_trigrams (issue_trigrams, rowid, summary, description, comments)
+        VALUES ('delete', old.id, old.summary, old.description, old.comments);
+    END;
+    CREATE TRIGGER IF NOT EXISTS issue_text_update AFTER UPDATE ON issue_text BEGIN
+        INSERT INTO issue_words (issue_words, rowid, item_key, summary, description, comments)
+        VALUES ('delete', old.id, old.item_key, old.summary, old.description, old.comments);
+        INSERT INTO issue_trigrams (issue_trigrams, rowid, summary, description, comments)
+        VALUES ('delete', old.id, old.summary, old.description, old.comments);
+        INSERT INTO issue_words (rowid, item_key, summary, description, comments)
+        VALUES (new.id, new.item_key, new.summary, new.description, new.comments);
+        INSERT INTO issue_trigrams (rowid, summary, description, comments)
+        VALUES (new.id, new.summary, new.description, new.comments);
+    END;
+    -- Issues já em cache entram no índice com o resumo
+    INSERT OR IGNORE INTO issue_text (profile_id, item_key, summary)
+    SELECT profile_id, item_key, COALESCE(json_extract(data, '$.summary'), '') FROM issues;
+";
+
+// Escopo (parte da JQL sem a janela de tempo) da última sincronização: um escopo
+// novo, ex.: outro projeto acompanhado, recomeça pela janela inicial
+const SCHEMA_V4: &str = "
+    ALTER TABLE sync_state ADD COLUMN scope TEXT NOT NULL DEFAULT '';
+";
+
+// Resultado servido do cache local ou recém-buscado no JIRA
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export)]
+pub struct JiraCached<T> {
+    pub data: T,
+    #[serde(rename = "fetchedAt")]
+    #[ts(type = "string")]
+    pub fetched_at: DateTime<Utc>,
+    // Mais antigo que o prazo de validade; pode não refletir o JIRA
+    pub stale: bool,
+    // Atualização em segundo plano em andamento; `jira://cache-refreshed` avisa o fim
+    pub refreshing: bool,
+}
+
+impl<T> JiraCached<T> {
+    pub fn fresh(data: T) -> Self {
+        Self {
+            data,
+            fetched_at: Utc::now(),
+            stale: false,
+            refreshing: false,
+        }
+    }
+
+    fn from_cache(data: T, fetched_at: DateTime<Utc>, expired: bool) -> Self {
+        Self {
+            data,
+            fetched_at,
+            stale: expired || Utc::now() - fetched_at > chrono::Duration::seconds(FRESH_FOR_SECS),
+            refreshing: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub enum CacheKind {
+    Search,
+    Projects,
+    CurrentUser,
+}
+
+impl CacheKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            CacheKind::Search => "search",
+            CacheKind::Projects => "projects",
+            CacheKind::CurrentUser => "current_user",
+        }
+    }
+}
+
+// Payload do evento `jira://cache-refreshed`
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export)]
+pub struct CacheRefreshed {
+    pub kind: CacheKind,
+    // JQL da busca; vazio para projetos e usuário atual
+    pub query: String,
+    // Falha na atualização (ex.: VPN fora do ar); o cache continua obsoleto
+    pub error: Option<String>,
+}
+
+// Descrição e comentários de uma issue, indexados pela busca local.
+// O resumo é indexado junto com a própria issue.
+#[derive(Debug, Clone)]
+pub struct IssueText {
+    pub key: String,
+    pub description: String,
+    pub comments: String,
+}
+
+// Resultado de `search_local_issues`, na ordem de relevância
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export)]
+pub struct LocalIssueMatch {
+    pub issue: JiraIssue,
+    // Trecho onde os termos aparecem, destacados entre MATCH_START e MATCH_END
+    pub snippet: String,
+    // Encontrada por semelhança (ex.: erro de digitação), não pelos termos exatos
+    pub fuzzy: bool,
+}
+
+// Registro armazenado em uma das tabelas do cache
+trait CacheRecord: Serialize + DeserializeOwned {
+    const TABLE: &'static str;
+
+    fn cache_key(&self) -> &str;
+
+    fn updated(&self) -> Option<&str> {
+        None
+    }
+
+    // Atualizar o índice da busca local junto com o registro
+    fn index(&self, _transaction: &Transaction<'_>, _profile_id: &str) -> Result<(), JiraError> {
+        Ok(())
+    }
+}
+
+impl CacheRecord for JiraIssue {
+    const TABLE: &'static str = "issues";
+
+    fn cache_key(&self) -> &str {
+        &self.key
+    }
+
+    fn updated(&self) -> Option<&str> {
+        Some(&self.updated)
+    }
+
+    fn index(&self, transaction: &Transaction<'_>, profile_id: &str) -> Result<(), JiraError> {
+        transaction.prepare_cached(
+            "INSERT INTO issue_text (profile_id, item_key, summary) VALUES (?1, ?2, ?3)
+             ON CONFLICT (profile_id, item_key) DO UPDATE SET summary = excluded.summary
+             WHERE summary <> excluded.summary",
+        )?.execute(params![profile_id, self.key, self.summary])?;
+
+        Ok(())
+    }
+}
+
+impl CacheRecord for JiraProject {
+    const TABLE: &'static str = "projects";
+
+    fn cache_key(&self) -> &str {
+        &self.key
+    }
+}
+
+impl CacheRecord for JiraUser {
+    const TABLE: &'static str = "users";
+
+    fn cache_key(&self) -> &str {
+        &self.account_id
+    }
+}
+
+// Cache offline (SQLite) de issues, projetos e usuários de um perfil.
+// Um único banco guarda todos os perfis; cada linha carrega o `profile_id`.
+pub struct JiraCache {
+    profile_id: String,
+    connection: Mutex<Connection>,
+}
+
+impl JiraCache {
+    pub fn open(profile_id: &str) -> Result<Self, JiraError> {
+        let path = Self::get_cache_file_path()?;
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| JiraError::InvalidConfig(format!("Erro ao criar diretório do cache: {}", e)))?;
+        }
+
+        Self::create_private(&path)?;
+        let mut connection = Connection::open(&path)?;
+        // WAL permite ler o cache enquanto a atualização em segundo plano grava
+        connection.pragma_update(None, "journal_mode", "WAL")?;
+        connection.busy_timeout(std::time::Duration::from_secs(5))?;
+        Self::migrate(&mut connection)?;
+        Self::restrict_permissions(&path)?;
+
+        Ok(Self {
+            profile_id: profile_id.to_string(),
+            connection: Mutex::new(connection),
+        })
+    }
+
+    pub fn search(&self, jql: &str, limit: Option<u32>) -> Result<Option<JiraCached<Vec<JiraIssue>>>, JiraError> {
+        self.load_query(CacheKind::Search, jql, limit)
+    }
+
+    pub fn store_search(&self, jql: &str, limit: Option<u32>, issues: &[JiraIssue]) -> Result<(), JiraError> {
+        self.store_query(CacheKind::Search, jql, limit, issues)
+    }
+
+    pub fn projects(&self, limit: Option<u32>) -> Result<Option<JiraCached<Vec<JiraProject>>>, JiraError> {
+        self.load_query(CacheKind::Projects, "", limit)
+    }
+
+    pub fn store_projects(&self, limit: Option<u32>, projects: &[JiraProject]) -> Result<(), JiraError> {
+        self.store_query(CacheKind::Projects, "", limit, projects)
+    }
+
+    pub fn current_user(&self) -> Result<Option<JiraCached<JiraUser>>, JiraError> {
+        let cached = self.load_query::<JiraUser>(CacheKind::CurrentUser, "", None)?;
+
+        Ok(cached.and_then(|cached| {
+            let JiraCached { data, fetched_at, stale, .. } = cached;
+            data.into_iter().next().map(|user| JiraCached::from_cache(user, fetched_at, stale))
+        }))
+    }
+
+    pub fn store_current_user(&self, user: &JiraUser) -> Result<(), JiraError> {
+        self.store_query(CacheKind::CurrentUser, "", None, std::slice::from_ref(user))
+    }
+
+    // Gravar issues recebidas pela sincronização e devolver as que mudaram
+    // (novas ou com `updated` diferente). Buscas em cache passam a ser obsoletas,
+    // já que a lista de resultados pode ter mudado.
+    pub fn merge_issues(&self, issues: &[JiraIssue]) -> Result<Vec<JiraIssue>, JiraError> {
+        self.merge(issues, true)
+    }
+
+    // Gravar a issue aberta pelo usuário; só as buscas que a contêm ficam obsoletas
+    pub fn merge_opened_issue(&self, issue: &JiraIssue) -> Result<(), JiraError> {
+        self.merge(std::slice::from_ref(issue), false).map(|_| ())
+    }
+
+    // Gravar descrição e comentários no índice da busca local; issues ainda
+    // fora do cache são ignoradas
+    pub fn index_texts(&self, texts: &[IssueText]) -> Result<(), JiraError> {
+        let mut connection = self.lock();
+        let transaction = connection.transaction()?;
+
+        {
+            let mut statement = transaction.prepare_cached(
+                "UPDATE issue_text SET description = ?3, comments = ?4
+                 WHERE profile_id = ?1 AND item_key = ?2 AND (description <> ?3 OR comments <> ?4)",
+            )?;
+
+            for text in texts {
+                statement.execute(params![self.profile_id, text.key, text.description, text.comments])?;
+            }
+        }
+
+        transaction.commit()?;
+        Ok(())
+    }
+
+    // Busca textual nas issues em cache (resumo, descrição e comentários), sem rede.
+    // Primeiro pelos termos (prefixos, sem acentos); se faltarem resultados,
+    // completa com issues parecidas, para tolerar erros de digitação.
+    pub fn search_local(&self, query: &str, limit: u32) -> Result<Vec<LocalIssueMatch>, JiraError> {
+        let terms = search_terms(query);
+        let limit = limit.clamp(1, LOCAL_SEARCH_MAX_LIMIT);
+
+        if terms.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let connection = self.lock();
+        let mut matches = self.search_words(&connection, &terms, limit)?;
+
+        if (matches.len() as u32) < limit {
+            let found: HashSet<String> = matches.iter().map(|m| m.issue.key.clone()).collect();
+            let similar = self.search_trigrams(&connection, &terms)?
+                .into_iter()
+                .filter(|m| !found.contains(&m.issue.key))
+                .take(limit as usize - matches.len());
+
+            matches.extend(similar);
+        }
+
+        Ok(matches)
+    }
+
+    // Última sincronização com o mesmo escopo; `None` se o escopo mudou desde então
+    pub fn last_sync(&self, scope: &str) -> Result<Option<DateTime<Utc>>, JiraError> {
+        let last_sync = self.lock().query_row(
+            "SELECT last_sync FROM sync_state WHERE profile_id = ?1 AND scope = ?2",
+            params![self.profile_id, scope],
+            |row| row.get(0),
+        ).optional()?;
+
+        Ok(last_sync)
+    }
+
+    pub fn set_last_sync(&self, scope: &str, at: DateTime<Utc>) -> Result<(), JiraError> {
+        self.lock().execute(
+            "INSERT OR REPLACE INTO sync_state (profile_id, last_sync, scope) VALUES (?1, ?2, ?3)",
+            params![self.profile_id, at, scope],
+        )?;
+
+        Ok(())
+    }
+
+    // Apagar tudo do perfil, ex.: ao limpar as credenciais ou remover o perfil
+    pub fn clear(&self) -> Result<(), JiraError> {
+        let mut connection = self.lock();
+        let transaction = connection.transaction()?;
+
+        for table in [JiraIssue::TABLE, JiraProject::TABLE, JiraUser::TABLE, "queries", "sync_state", "issue_text"] {
+            transaction.execute(&format!("DELETE FROM {} WHERE profile_id = ?1", table), params![self.profile_id])?;
+        }
+
+        transaction.commit()?;
+        Ok(())
+    }
+
+    // === MÉTODOS PRIVADOS ===
+
+    fn get_cache_file_path() -> Result<PathBuf, JiraError> {
+        Ok(goji_config_dir()?.join("cache.sqlite3"))
+    }
+
+    // O cache guarda o conteúdo das issues: criar o banco já com permissões 600. O
+    // SQLite cria os arquivos -wal e -shm com as mesmas permissões do banco.
+    fn create_private(path: &Path) -> Result<(), JiraError> {
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create(true);
+        #[cfg(unix)]
+        options.mode(0o600);
+
+        options.open(path)
+            .map(|_| ())
+            .map_err(|e| JiraError::InvalidConfig(format!("Erro ao criar cache: {}", e)))
+    }
+
+    // Corrigir caches criados antes com as permissões padrão (644)
+    fn restrict_permissions(path: &Path) -> Result<(), JiraError> {
+        #[cfg(unix)]
+        {
+            let mut files = vec![path.to_path_buf()];
+            for suffix in ["-wal", "-shm"] {
+                let mut file = path.as_os_str().to_owned();
+                file.push(suffix);
+                files.push(PathBuf::from(file));
+            }
+
+            for file in files.iter().filter(|file| file.exists()) {
+                std::fs::set_permissions(file, std::fs::Permissions::from_mode(0o600))
+                    .map_err(|e| JiraError::InvalidConfig(format!("Erro ao definir permissões do cache: {}", e)))?;
+            }
+        }
+        #[cfg(not(unix))]
+        let _ = path;
+
+        Ok(())
+    }
+
+    fn migrate(connection: &mut Connection) -> Result<(), JiraError> {
+        let version: i32 = connection.pragma_query_value(None, "user_version", |row| row.get(0))?;
+
+        if version >= SCHEMA_VERSION {
+            return Ok(());
+        }
+
+        let transaction = connection.transaction()?;
+        if version < 1 {
+            transaction.execute_batch(SCHEMA_V1)?;
+        }
+        if version < 2 {
+            transaction.execute_batch(SCHEMA_V2)?;
+        }
+        if version < 3 {
+            transaction.execute_batch(SCHEMA_V3)?;
+        }
+        if version < 4 {
+            transaction.execute_batch(SCHEMA_V4)?;
+        }
+        transaction.pragma_update(None, "user_version", SCHEMA_VERSION)?;
+        transaction.commit()?;
+
+        Ok(())
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Connection> {
+        self.connection.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    // `all_searches`: a sincronização pode mudar qualquer lista de resultados; uma
+    // issue aberta isoladamente só afeta as buscas em que já aparece
+    fn merge(&self, issues: &[JiraIssue], all_searches: bool) -> Result<Vec<JiraIssue>, JiraError> {
+        let fetched_at = Utc::now();
+
+        let mut changed = Vec::new();
+
+        let mut connection = self.lock();
+        let transaction = connection.transaction()?;
+
+        {
+            let mut current = transaction.prepare_cached(
+                "SELECT updated FROM issues WHERE profile_id = ?1 AND item_key = ?2",
+            )?;
+
+            for issue in issues {
+                let updated: Option<Option<String>> = current
+                    .query_row(params![self.profile_id, issue.key], |row| row.get(0))
+                    .optional()?;
+
+                if updated.flatten().as_deref() != Some(issue.updated.as_str()) {
+                    changed.push(issue.clone());
+                }
+            }
+        }
+
+        Self::upsert(&transaction, &self.profile_id, issues, fetched_at)?;
+
+        if all_searches {
+            if !changed.is_empty() {
+                transaction.execute(
+                    "UPDATE queries SET expired = 1 WHERE profile_id = ?1 AND kind = ?2",
+                    params![self.profile_id, CacheKind::Search.as_str()],
+                )?;
+            }
+        } else {
+            let mut expire = transaction.prepare_cached(
+                "UPDATE queries SET expired = 1
+                 WHERE profile_id = ?1 AND kind = ?2
+                   AND EXISTS (SELECT 1 FROM json_each(queries.item_keys) WHERE value = ?3)",
+            )?;
+            for issue in &changed {
+                expire.execute(params![self.profile_id, CacheKind::Search.as_str(), issue.key])?;
+            }
+        }
+
+        transaction.commit()?;
+        Ok(changed)
+    }
+
+    fn load_query<T: CacheRecord>(
+        &self,
+        kind: CacheKind,
+        query: &str,
+        limit: Option<u32>,
+    ) -> Result<Option<JiraCached<Vec<T>>>, JiraError> {
+        let connection = self.lock();
+
+        let row = connection.query_row(
+            "SELECT item_keys, fetched_at, expired FROM queries
+             WHERE profile_id = ?1 AND kind = ?2 AND query = ?3 AND max_results = ?4",
+            params![self.profile_id, kind.as_str(), query, limit.unwrap_or(0)],
+            |row| Ok((row.get::<_, String>(0)?, row.get::<_, DateTime<Utc>>(1)?, row.get::<_, bool>(2)?)),
+        ).optional()?;
+
+        let Some((item_keys, fetched_at, expired)) = row else {
+            return Ok(None);
+        };
+        let item_keys: Vec<String> = serde_json::from_str(&item_keys)?;
+
+        let mut statement = connection.prepare_cached(&format!(
+            "SELECT data FROM {} WHERE profile_id = ?1 AND item_key = ?2",
+            T::TABLE,
+        ))?;
+
+        let mut items = Vec::with_capacity(item_keys.len());
+        for item_key in &item_keys {
+            let data: Option<String> = statement
+                .query_row(params![self.profile_id, item_key], |row| row.get(0))
+                .optional()?;
+
+            // Registro apagado depois da consulta: tratar como ausência de cache
+            let Some(data) = data else {
+                return Ok(None);
+            };
+            items.push(serde_json::from_str(&data)?);
+        }
+
+        Ok(Some(JiraCached::from_cache(items, fetched_at, expired)))
+    }
+
+    fn store_query<T: CacheRecord>(
+        &self,
+        kind: CacheKind,
+        query: &str,
+        limit: Option<u32>,
+        items: &[T],
+    ) -> Result<(), JiraError> {
+        let fetched_at = Utc::now();
+        let item_keys: Vec<&str> = items.iter().map(|item| item.cache_key()).collect();
+
+        let mut connection = self.lock();
+        let transaction = connection.transaction()?;
+
+        Self::upsert(&transaction, &self.profile_id, items, fetched_at)?;
+
+        transaction.execute(
+            "INSERT OR REPLACE INTO queries (profile_id, kind, query, max_results, item_keys, fetched_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                self.profile_id,
+                kind.as_str(),
+                query,
+                limit.unwrap_or(0),
+                serde_json::to_string(&item_keys)?,
+                fetched_at,
+            ],
+        )?;
+
+        transaction.commit()?;
+        Ok(())
+    }
+
+    fn upsert<T: CacheRecord>(
+        transaction: &Transaction<'_>,
+        profile_id: &str,
+        items: &[T],
+        fetched_at: DateTime<Utc>,
+    ) -> Result<(), JiraError> {
+        let mut statement = transaction.prepare_cached(&format!(
+            "INSERT INTO {} (profile_id, item_key, updated, fetched_at, data)
+             VALUES (?1, ?2, ?3, ?4, ?5)
+             ON CONFLICT (profile_id, item_key) DO UPDATE SET
+                 updated = excluded.updated,
+                 fetched_at = excluded.fetched_at,
+                 data = excluded.data",
+            T::TABLE,
+        ))?;
+
+        for item in items {
+            statement.execute(params![
+                profile_id,
+                item.cache_key(),
+                item.updated(),
+                fetched_at,
+                serde_json::to_string(item)?,
+            ])?;
+            item.index(transaction, profile_id)?;
+        }
+
+        Ok(())
+    }
+
+    fn search_words(&self, connection: &Connection, terms: &[String], limit: u32) -> Result<Vec<LocalIssueMatch>, JiraError> {
+        // Todos os termos, cada um como prefixo: "auten" encontra "autenticação"
+        let expression = terms.iter()
+            .map(|term| format!("\"{}\"*", term))
+            .collect::<Vec<_>>()
+            .join(" AND ");
+
+        // Pesos do bm25: chave e resumo valem mais que descrição e comentários
+        let mut statement = connection.prepare_cached(
+            "SELECT i.data, snippet(issue_words, -1, ?3, ?4, '…', ?5)
+             FROM issue_words
+             JOIN issue_text t ON t.id = issue_words.rowid
+             JOIN issues i ON i.profile_id = t.profile_id AND i.item_key = t.item_key
+             WHERE issue_words MATCH ?1 AND t.profile_id = ?2
+             ORDER BY bm25(issue_words, 10.0, 5.0, 2.0, 1.0)
+             LIMIT ?6",
+        )?;
+
+        let rows = statement.query_map(
+            params![expression, self.profile_id, MATCH_START, MATCH_END, SNIPPET_TOKENS, limit],
+            |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)),
+        )?;
+
+        let mut matches = Vec::new();
+        for row in rows {
+            let (data, snippet) = row?;
+            matches.push(LocalIssueMatch {
+                issue: serde_json::from_str(&data)?,
+                snippet,
+                fuzzy: false,
+            });
+        }
+
+        Ok(matches)
+    }
+
+    fn search_trigrams(&self, connection: &Connection, terms: &[String]) -> Result<Vec<LocalIssueMatch>, JiraError> {
+        // Termos com menos de 3 letras não formam trigramas
+        let term_trigrams: Vec<Vec<String>> = terms.iter()
+            .map(|term| trigrams(term))
+            .filter(|trigrams| !trigrams.is_empty())
+            .collect();
+
+        let mut all: Vec<&String> = term_trigrams.iter().flatten().collect();
+        all.sort();
+        all.dedup();
+        all.truncate(FUZZY_MAX_TRIGRAMS);
+
+        if all.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        // Candidatas com qualquer trigrama; a semelhança é conferida abaixo
+        let expression = all.iter()
+            .map(|trigram| format!("\"{}\"", trigram))
+            .collect::<Vec<_>>()
+            .join(" OR ");
+
+        let mut statement = connection.prepare_cached(
+            "SELECT i.data, t.summary || ' ' || t.description || ' ' || t.comments,
+                    snippet(issue_trigrams, -1, ?3, ?4, '…', ?5)
+             FROM issue_trigrams
+             JOIN issue_text t ON t.id = issue_trigrams.rowid
+             JOIN issues i ON i.profile_id = t.profile_id AND i.item_key = t.item_key
+             WHERE issue_trigrams MATCH ?1 AND t.profile_id = ?2
+             ORDER BY bm25(issue_trigrams, 10.0, 2.0, 1.0)
+             LIMIT ?6",
+        )?;
+
+        let rows = statement.query_map(
+            // No índice de trigramas cada "token" tem 3 caracteres: ampliar o trecho
+            params![expression, self.profile_id, MATCH_START, MATCH_END, SNIPPET_TOKENS * 4, FUZZY_CANDIDATES],
+            |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?, row.get::<_, String>(2)?)),
+        )?;
+
+        let mut scored = Vec::new();
+        for row in rows {
+            let (data, text, snippet) = row?;
+
+            let Some(score) = fuzzy_score(&term_trigrams, &fold(&text)) else {
+                continue;
+            };
+            scored.push((score, LocalIssueMatch {
+                issue: serde_json::from_str(&data)?,
+                snippet,
+                fuzzy: true,
+            }));
+        }
+
+        // Ordenação estável: em caso de empate vale a ordem do bm25
+        scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+        Ok(scored.into_iter().map(|(_, m)| m).collect())
+    }
+}
+
+// === FUNÇÕES PRIVADAS ===
+
+// Palavras da consulta, em minúsculas e sem acentos, como nos índices
+fn search_terms(query: &str) -> Vec<String> {
+    fold(query)
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|term| !term.is_empty())
+        .map(|term| term.to_string())
+        .collect()
+}
+
+fn trigrams(term: &str) -> Vec<String> {
+    let chars: Vec<char> = term.chars().collect();
+    chars.windows(3).map(|window| window.iter().collect()).collect()
+}
+
+// Média da fração de trigramas de cada termo presentes no texto (já sem acentos);
+// None se algum termo não tiver parte suficiente dos seus trigramas
+fn fuzzy_score(term_trigrams: &[Vec<String>], text: &str) -> Option<f64> {
+    let similarities: Vec<f64> = term_trigrams.iter()
+        .map(|trigrams| {
+            let present = trigrams.iter().filter(|trigram| text.contains(trigram.as_str())).count();
+            present as f64 / trigrams.len() as f64
+        })
+        .collect();
+
+    if similarities.iter().any(|&similarity| similarity < FUZZY_MIN_SIMILARITY) {
+        return None;
+    }
+
+    Some(similarities.iter().sum::<f64>() / similarities.len() as f64)
+}
+
+// Letra base (minúscula) de cada caractere latino acentuado, como na tabela do
+// remove_diacritics do SQLite; '.' mantém o caractere. U+00C0 a U+024F:
+const LATIN_BASES: &[u8; 400] = b"aaaaaa.ceeeeiiii.nooooo..uuuuy..aaaaaa.ceeeeiiii.nooooo..uuuuy.yaaaaaaccccccccdd..eeeeeeeeeegggggggghh..iiiiiiiii...jjkk.llllll....nnnnnn...oooooo..rrrrrrsssssssstttt..uuuuuuuuuuuuwwyyyzzzzzz.................................oo.............uu............................aaiioouuuuuuuuuu.aaaa....ggkkoooo..j...gg..nnaa....aaaaeeeeiiiioooorrrruuuusstt..hh......aaeeooooooooyy............................";
+// U+1E00 a U+1EFF (vietnamita e outros)
+const LATIN_ADDITIONAL_BASES: &[u8; 256] = b"aabbbbbbccddddddddddeeeeeeeeeeffgghhhhhhhhhhiiiikkkkkkllllllllmmmmmmnnnnnnnnoooooooopppprrrrrrrrssssssssssttttttttuuuuuuuuuuvvvvwwwwwwwwwwxxxxyyzzzzzzhtwy......aaaaaaaaaaaaaaaaaaaaaaaaeeeeeeeeeeeeeeeeiiiioooooooooooooooooooooooouuuuuuuuuuuuuuyyyyyyyy......";
+
+// Minúsculas sem acentos, equivalente ao remove_diacritics dos índices. Acentos
+// combinantes (texto decomposto) são descartados.
+fn fold(text: &str) -> String {
+    text.chars()
+        .flat_map(char::to_lowercase)
+        .filter_map(|c| {
+            let base = match c as u32 {
+                0x300..=0x36F => return None,
+                code @ 0xC0..=0x24F => LATIN_BASES[(code - 0xC0) as usize],
+                code @ 0x1E00..=0x1EFF => LATIN_ADDITIONAL_BASES[(code - 0x1E00) as usize],
+                _ => b'.',
+            };
+            Some(if base == b'.' { c } else { char::from(base) })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn memory_cache() -> JiraCache {
+        let mut connection = Connection::open_in_memory().unwrap();
+        JiraCache::migrate(&mut connection).unwrap();
+        JiraCache {
+            profile_id: "default".to_string(),
+            connection: Mutex::new(connection),
+        }
+    }
+
+    fn issue(key: &str, updated: &str) -> JiraIssue {
+        JiraIssue {
+            id: key.to_string(),
+            key: key.to_string(),
+            summary: format!("Issue {}", key),
+            status: "To Do".to_string(),
+            assignee: None,
+            reporter: None,
+            created: "2026-01-01T00:00:00.000+0000".to_string(),
+            updated: updated.to_string(),
+        }
+    }
+
+    #[test]
+    fn search_terms_are_folded_like_the_index() {
+        assert_eq!(search_terms("Autenticação do-Usuário"), ["autenticacao", "do", "usuario"]);
+        assert_eq!(search_terms("  "), Vec::<String>::new());
+        // Latin Extended-A/B e Latin Extended Additional, além do Latin-1
+        assert_eq!(fold("Šťastný Nguyễn Ưu Łódź"), "stastny nguyen uu łodz");
+        // Texto decomposto (NFD): o acento combinante é descartado
+        assert_eq!(fold("cafe\u{301}"), "cafe");
+    }
+
+    #[test]
+    fn trigrams_slide_over_characters() {
+        assert_eq!(trigrams("sessao"), ["ses", "ess", "ssa", "sao"]);
+        assert!(trigrams("ab").is_empty());
+    }
+
+    #[test]
+    fn fuzzy_score_requires_every_term() {
+        let terms = vec![trigrams("autenticacao"), trigrams("falha")];
+
+        // Erro de digitação em um termo ainda passa, com nota menor
+        let exact = fuzzy_score(&terms, "falha na autenticacao do usuario").unwrap();
+        let typo = fuzzy_score(&terms, "falha na autenticasao do usuario").unwrap();
+        assert_eq!(exact, 1.0);
+        assert!(typo < exact);
+
+        // Um termo ausente elimina a candidata
+        assert_eq!(fuzzy_score(&terms, "autenticacao do usuario"), None);
+    }
+
+    #[test]
+    fn last_sync_is_tied_to_the_scope() {
+        let cache = memory_cache();
+        let at = Utc::now();
+        cache.set_last_sync("assignee = currentUser()", at).unwrap();
+
+        assert_eq!(cache.last_sync("assignee = currentUser()").unwrap(), Some(at));
+        assert_eq!(cache.last_sync("project IN (\"GOJI\")").unwrap(), None);
+    }
+
+    #[test]
+    fn opened_issue_expires_only_searches_containing_it() {
+        let cache = memory_cache();
+        cache.store_search("project = A", None, &[issue("A-1", "1")]).unwrap();
+        cache.store_search("project = B", None, &[issue("B-1", "1")]).unwrap();
+
+        cache.merge_opened_issue(&issue("A-1", "2")).unwrap();
+        assert!(cache.search("project = A", None).unwrap().unwrap().stale);
+        assert!(!cache.search("project = B", None).unwrap().unwrap().stale);
+
+        // A sincronização continua expirando todas as buscas
+        cache.merge_issues(&[issue("C-1", "1")]).unwrap();
+        assert!(cache.search("project = B", None).unwrap().unwrap().stale);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn cache_files_are_owner_only() {
+        let dir = std::env::temp_dir().join(format!("goji-test-cache-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("cache.sqlite3");
+
+        JiraCache::create_private(&path).unwrap();
+        let connection = Connection::open(&path).unwrap();
+        connection.pragma_update(None, "journal_mode", "WAL").unwrap();
+        connection.execute_batch("CREATE TABLE t (x INTEGER); INSERT INTO t VALUES (1);").unwrap();
+
+        // Banco antigo, criado com as permissões padrão
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o644)).unwrap();
+        JiraCache::restrict_permissions(&path).unwrap();
+
+        for name in ["cache.sqlite3", "cache.sqlite3-wal", "cache.sqlite3-shm"] {
+            let mode = std::fs::metadata(dir.join(name)).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600, "{}", name);
+        }
+
+        drop(connection);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -1,9 +1,16 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
 use serde::{Serialize, Deserialize};
-use crate::jira::{auth::JiraAuth, error::JiraError, config_manager::JiraConfigManager};
+use ts_rs::TS;
+use chrono::{DateTime, Utc};
+use crate::jira::{adf, auth::JiraAuth, error::{JiraError, JiraApiErrorBody}, config_manager::{JiraConfigManager, JiraConfig, JiraApiVersion}, request::RequestExecutor, profiles::ProfileManager, cache::{JiraCache, JiraCached, IssueText}};
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
 pub struct JiraUser {
+    // Server/Data Center não tem accountId; o nome de usuário ocupa o lugar
     #[serde(rename = "accountId")]
+    #[serde(alias = "name")]
     pub account_id: String,
     #[serde(rename = "displayName")]
     pub display_name: String,
@@ -12,7 +19,8 @@ pub struct JiraUser {
     pub active: bool,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
 pub struct JiraProject {
     pub id: String,
     pub key: String,
@@ -22,7 +30,8 @@ pub struct JiraProject {
     pub project_type_key: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
 pub struct JiraIssue {
     pub id: String,
     pub key: String,
@@ -34,171 +43,1923 @@ pub struct JiraIssue {
     pub updated: String,
 }
 
+// Página de resultados entregue durante uma busca paginada
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct JiraIssuePage {
+    pub issues: Vec<JiraIssue>,
+    // Posição da primeira issue da página no resultado completo
+    #[serde(rename = "startAt")]
+    pub start_at: u32,
+    // Disponível apenas no endpoint legado; use count_issues no JIRA Cloud
+    pub total: Option<u32>,
+    #[serde(rename = "isLast")]
+    pub is_last: bool,
+}
+
+// Tamanho máximo de página aceito pelos endpoints de busca
+const PAGE_SIZE: u32 = 100;
+
+const SEARCH_FIELDS: [&str; 6] = ["summary", "status", "assignee", "reporter", "created", "updated"];
+
+// Campos da sincronização: inclui o texto longo indexado pela busca local
+const TEXT_SEARCH_FIELDS: [&str; 8] = ["summary", "status", "assignee", "reporter", "created", "updated", "description", "comment"];
+
+// Posição da próxima página a ser buscada; a primeira página não tem cursor
+enum PageCursor {
+    // Endpoint /search/jql
+    Token(String),
+    // Endpoint legado /search
+    Offset(u32),
+}
+
+struct SearchPage {
+    issues: Vec<RawSearchIssue>,
+    total: Option<u32>,
+    next: Option<PageCursor>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct JiraIssueCount {
+    // u64 vira bigint no ts-rs; a contagem cabe em um number
+    #[ts(type = "number")]
+    pub count: u64,
+    pub approximate: bool,
+}
+
+#[derive(Deserialize)]
+struct RawSearchIssue {
+    id: String,
+    key: String,
+    fields: RawSearchFields,
+}
+
+#[derive(Deserialize)]
+struct RawSearchFields {
+    summary: String,
+    status: RawNamed,
+    assignee: Option<RawDisplayName>,
+    reporter: Option<RawDisplayName>,
+    created: String,
+    updated: String,
+    // Presentes apenas quando pedidos em TEXT_SEARCH_FIELDS
+    #[serde(default)]
+    description: Option<serde_json::Value>,
+    #[serde(default)]
+    comment: Option<RawSearchComments>,
+}
+
+// A busca traz só os comentários mais recentes; os demais entram ao abrir a issue
+#[derive(Deserialize)]
+struct RawSearchComments {
+    #[serde(default)]
+    comments: Vec<RawSearchComment>,
+}
+
+#[derive(Deserialize)]
+struct RawSearchComment {
+    body: serde_json::Value,
+}
+
+#[derive(Deserialize)]
+struct RawNamed {
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct RawDisplayName {
+    #[serde(rename = "displayName")]
+    display_name: String,
+}
+
+impl From<RawSearchIssue> for JiraIssue {
+    fn from(raw_issue: RawSearchIssue) -> Self {
+        JiraIssue {
+            id: raw_issue.id,
+            key: raw_issue.key,
+            summary: raw_issue.fields.summary,
+            status: raw_issue.fields.status.name,
+            assignee: raw_issue.fields.assignee.map(|u| u.display_name),
+            reporter: raw_issue.fields.reporter.map(|u| u.display_name),
+            created: raw_issue.fields.created,
+            updated: raw_issue.fields.updated,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct JiraStatusCategory {
+    pub key: String,
+    pub name: String,
+    #[serde(rename = "colorName")]
+    pub color_name: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct JiraStatus {
+    pub id: String,
+    pub name: String,
+    #[serde(rename = "statusCategory")]
+    pub status_category: Option<JiraStatusCategory>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct JiraIssueType {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub subtask: bool,
+    #[serde(rename = "iconUrl")]
+    pub icon_url: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct JiraPriority {
+    pub id: String,
+    pub name: String,
+    #[serde(rename = "iconUrl")]
+    pub icon_url: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct JiraResolution {
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct JiraComponent {
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct JiraVersion {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub released: bool,
+    #[serde(rename = "releaseDate")]
+    pub release_date: Option<String>,
+}
+
+// Referência resumida a outra issue (pai, subtarefa ou issue vinculada)
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct JiraIssueRef {
+    pub id: String,
+    pub key: String,
+    pub summary: Option<String>,
+    pub status: Option<JiraStatus>,
+    #[serde(rename = "issueType")]
+    pub issue_type: Option<JiraIssueType>,
+    pub priority: Option<JiraPriority>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct JiraIssueLink {
+    pub id: String,
+    #[serde(rename = "linkType")]
+    pub link_type: String,
+    // "inward" ou "outward", relativo à issue consultada
+    #[ts(type = "\"inward\" | \"outward\"")]
+    pub direction: String,
+    // Texto da relação, ex.: "blocks" ou "is blocked by"
+    pub relation: String,
+    pub issue: JiraIssueRef,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct JiraComment {
+    pub id: String,
+    pub author: Option<JiraUser>,
+    // Corpo no formato ADF (Atlassian Document Format) e suas conversões
+    #[ts(type = "unknown")]
+    pub body: serde_json::Value,
+    #[serde(rename = "bodyMarkdown")]
+    pub body_markdown: String,
+    #[serde(rename = "bodyHtml")]
+    pub body_html: String,
+    #[serde(rename = "renderedBody")]
+    pub rendered_body: Option<String>,
+    pub created: String,
+    pub updated: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct JiraTransition {
+    pub id: String,
+    pub name: String,
+    pub to: JiraStatus,
+    #[serde(rename = "hasScreen")]
+    pub has_screen: bool,
+    #[serde(rename = "isConditional")]
+    pub is_conditional: bool,
+    // Campos da tela de transição (ex.: resolução obrigatória ao concluir)
+    pub fields: Vec<JiraFieldMeta>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct JiraCustomField {
+    pub id: String,
+    #[serde(rename = "fieldType")]
+    pub field_type: Option<String>,
+    #[ts(type = "unknown")]
+    pub value: serde_json::Value,
+    #[serde(rename = "renderedValue")]
+    #[ts(type = "unknown")]
+    pub rendered_value: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct JiraIssueDetail {
+    pub id: String,
+    pub key: String,
+    pub summary: String,
+    pub status: JiraStatus,
+    #[serde(rename = "issueType")]
+    pub issue_type: JiraIssueType,
+    pub priority: Option<JiraPriority>,
+    pub resolution: Option<JiraResolution>,
+    pub assignee: Option<JiraUser>,
+    pub reporter: Option<JiraUser>,
+    pub created: String,
+    pub updated: String,
+    #[serde(rename = "dueDate")]
+    pub due_date: Option<String>,
+    // Descrição no formato ADF, suas conversões e a versão HTML renderizada pelo JIRA
+    #[ts(type = "unknown")]
+    pub description: Option<serde_json::Value>,
+    #[serde(rename = "descriptionMarkdown")]
+    pub description_markdown: Option<String>,
+    #[serde(rename = "descriptionHtml")]
+    pub description_html: Option<String>,
+    #[serde(rename = "renderedDescription")]
+    pub rendered_description: Option<String>,
+    pub labels: Vec<String>,
+    pub components: Vec<JiraComponent>,
+    #[serde(rename = "fixVersions")]
+    pub fix_versions: Vec<JiraVersion>,
+    pub parent: Option<JiraIssueRef>,
+    #[serde(rename = "epicKey")]
+    pub epic_key: Option<String>,
+    pub subtasks: Vec<JiraIssueRef>,
+    #[serde(rename = "issueLinks")]
+    pub issue_links: Vec<JiraIssueLink>,
+    pub comments: Vec<JiraComment>,
+    pub transitions: Vec<JiraTransition>,
+    // Campos customizados indexados pelo nome de exibição
+    #[serde(rename = "customFields")]
+    pub custom_fields: BTreeMap<String, JiraCustomField>,
+}
+
+impl From<&JiraIssueDetail> for JiraIssue {
+    fn from(detail: &JiraIssueDetail) -> Self {
+        JiraIssue {
+            id: detail.id.clone(),
+            key: detail.key.clone(),
+            summary: detail.summary.clone(),
+            status: detail.status.name.clone(),
+            assignee: detail.assignee.as_ref().map(|u| u.display_name.clone()),
+            reporter: detail.reporter.as_ref().map(|u| u.display_name.clone()),
+            created: detail.created.clone(),
+            updated: detail.updated.clone(),
+        }
+    }
+}
+
+impl From<&JiraIssueDetail> for IssueText {
+    fn from(detail: &JiraIssueDetail) -> Self {
+        IssueText {
+            key: detail.key.clone(),
+            description: detail.description_markdown.clone().unwrap_or_default(),
+            comments: detail.comments.iter()
+                .map(|comment| comment.body_markdown.as_str())
+                .collect::<Vec<_>>()
+                .join("\n\n"),
+        }
+    }
+}
+
+// Formato bruto de referência a issue retornado pela API
+#[derive(Deserialize)]
+struct RawIssueRef {
+    id: String,
+    key: String,
+    #[serde(default)]
+    fields: RawIssueRefFields,
+}
+
+#[derive(Deserialize, Default)]
+struct RawIssueRefFields {
+    summary: Option<String>,
+    status: Option<JiraStatus>,
+    issuetype: Option<JiraIssueType>,
+    priority: Option<JiraPriority>,
+}
+
+impl From<RawIssueRef> for JiraIssueRef {
+    fn from(raw: RawIssueRef) -> Self {
+        JiraIssueRef {
+            id: raw.id,
+            key: raw.key,
+            summary: raw.fields.summary,
+            status: raw.fields.status,
+            issue_type: raw.fields.issuetype,
+            priority: raw.fields.priority,
+        }
+    }
+}
+
+// Formato bruto de comentário; "renderedBody" só vem com expand=renderedBody
+#[derive(Deserialize)]
+struct RawComment {
+    id: String,
+    author: Option<JiraUser>,
+    body: serde_json::Value,
+    #[serde(rename = "renderedBody")]
+    rendered_body: Option<String>,
+    created: String,
+    updated: String,
+}
+
+impl From<RawComment> for JiraComment {
+    fn from(raw: RawComment) -> Self {
+        JiraComment {
+            id: raw.id,
+            author: raw.author,
+            body_markdown: adf::to_markdown(&raw.body),
+            body_html: adf::to_html(&raw.body),
+            body: raw.body,
+            rendered_body: raw.rendered_body,
+            created: raw.created,
+            updated: raw.updated,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct JiraWorklog {
+    pub id: String,
+    pub author: Option<JiraUser>,
+    #[ts(type = "unknown")]
+    pub comment: Option<serde_json::Value>,
+    #[serde(rename = "commentMarkdown")]
+    pub comment_markdown: Option<String>,
+    pub started: String,
+    #[serde(rename = "timeSpent")]
+    pub time_spent: String,
+    #[serde(rename = "timeSpentSeconds")]
+    #[ts(type = "number")]
+    pub time_spent_seconds: u64,
+    pub created: String,
+    pub updated: String,
+}
+
+// Dados para criar ou atualizar um registro de trabalho
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct JiraWorklogInput {
+    #[serde(rename = "timeSpentSeconds")]
+    #[ts(type = "number")]
+    pub time_spent_seconds: u64,
+    // Início do trabalho; quando ausente, usa o momento atual
+    #[ts(optional, type = "string")]
+    pub started: Option<DateTime<Utc>>,
+    // Comentário em Markdown
+    #[ts(optional)]
+    pub comment: Option<String>,
+}
+
+impl JiraWorklogInput {
+    fn to_request_body(&self, version: JiraApiVersion) -> Result<serde_json::Value, JiraError> {
+        // O JIRA rejeita registros com menos de um minuto
+        if self.time_spent_seconds < 60 {
+            return Err(JiraError::Validation(vec![
+                "timeSpentSeconds: Tempo mínimo de registro é de 1 minuto".to_string(),
+            ]));
+        }
+
+        let started = self.started.unwrap_or_else(Utc::now);
+        let mut body = serde_json::json!({
+            "timeSpentSeconds": self.time_spent_seconds,
+            "started": started.format("%Y-%m-%dT%H:%M:%S%.3f%z").to_string(),
+        });
+        if let Some(comment) = self.comment.as_deref().filter(|c| !c.trim().is_empty()) {
+            body["comment"] = rich_text(version, comment);
+        }
+
+        Ok(body)
+    }
+}
+
+#[derive(Deserialize)]
+struct RawWorklog {
+    id: String,
+    author: Option<JiraUser>,
+    comment: Option<serde_json::Value>,
+    started: String,
+    #[serde(rename = "timeSpent")]
+    time_spent: String,
+    #[serde(rename = "timeSpentSeconds")]
+    time_spent_seconds: u64,
+    created: String,
+    updated: String,
+}
+
+impl From<RawWorklog> for JiraWorklog {
+    fn from(raw: RawWorklog) -> Self {
+        JiraWorklog {
+            id: raw.id,
+            author: raw.author,
+            comment_markdown: raw.comment.as_ref().map(adf::to_markdown),
+            comment: raw.comment,
+            started: raw.started,
+            time_spent: raw.time_spent,
+            time_spent_seconds: raw.time_spent_seconds,
+            created: raw.created,
+            updated: raw.updated,
+        }
+    }
+}
+
+// Metadados de um campo disponível na tela de criação
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct JiraFieldMeta {
+    #[serde(rename = "fieldId")]
+    pub field_id: String,
+    pub name: String,
+    pub required: bool,
+    #[serde(rename = "schemaType")]
+    pub schema_type: Option<String>,
+    #[serde(rename = "schemaItems")]
+    pub schema_items: Option<String>,
+    #[serde(rename = "hasDefaultValue")]
+    pub has_default_value: bool,
+    #[serde(rename = "allowedValues")]
+    #[ts(type = "unknown[]")]
+    pub allowed_values: Vec<serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct JiraCreateMetaIssueType {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub subtask: bool,
+    #[serde(rename = "iconUrl")]
+    pub icon_url: Option<String>,
+}
+
+// Rascunho de issue preenchido pela interface antes do envio
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct JiraIssueDraft {
+    #[serde(rename = "projectKey")]
+    pub project_key: String,
+    #[serde(rename = "issueTypeId")]
+    pub issue_type_id: String,
+    pub summary: String,
+    #[ts(optional)]
+    pub description: Option<String>,
+    // Demais campos no formato esperado pela API (ex.: {"priority": {"id": "2"}})
+    #[serde(default)]
+    #[ts(type = "Record<string, unknown>")]
+    pub fields: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct JiraCreatedIssue {
+    pub id: String,
+    pub key: String,
+    #[serde(rename = "self")]
+    pub self_url: String,
+}
+
+// Formato bruto dos metadados de campo (tela de criação e de transição)
+#[derive(Deserialize)]
+struct RawFieldMeta {
+    #[serde(rename = "fieldId", alias = "key")]
+    field_id: String,
+    name: String,
+    #[serde(default)]
+    required: bool,
+    schema: Option<RawFieldSchema>,
+    #[serde(rename = "hasDefaultValue", default)]
+    has_default_value: bool,
+    #[serde(rename = "allowedValues", default)]
+    allowed_values: Vec<serde_json::Value>,
+}
+
+#[derive(Deserialize)]
+struct RawFieldSchema {
+    #[serde(rename = "type")]
+    field_type: Option<String>,
+    items: Option<String>,
+}
+
+impl From<RawFieldMeta> for JiraFieldMeta {
+    fn from(raw: RawFieldMeta) -> Self {
+        let (schema_type, schema_items) = raw.schema
+            .map(|s| (s.field_type, s.items))
+            .unwrap_or_default();
+
+        JiraFieldMeta {
+            field_id: raw.field_id,
+            name: raw.name,
+            required: raw.required,
+            schema_type,
+            schema_items,
+            has_default_value: raw.has_default_value,
+            allowed_values: raw.allowed_values,
+        }
+    }
+}
+
+// Formato bruto de transição; "fields" só vem com expand=transitions.fields
+#[derive(Deserialize)]
+struct RawTransition {
+    id: String,
+    name: String,
+    to: JiraStatus,
+    #[serde(rename = "hasScreen", default)]
+    has_screen: bool,
+    #[serde(rename = "isConditional", default)]
+    is_conditional: bool,
+    #[serde(default)]
+    fields: BTreeMap<String, serde_json::Value>,
+}
+
+impl From<RawTransition> for JiraTransition {
+    fn from(raw: RawTransition) -> Self {
+        JiraTransition {
+            id: raw.id,
+            name: raw.name,
+            to: raw.to,
+            has_screen: raw.has_screen,
+            is_conditional: raw.is_conditional,
+            fields: field_metas_from_map(raw.fields),
+        }
+    }
+}
+
+impl JiraIssueDraft {
+    // Campos preenchidos a partir das propriedades dedicadas do rascunho
+    const BUILTIN_FIELDS: [&'static str; 4] = ["project", "issuetype", "summary", "description"];
+
+    // Validar o rascunho contra os metadados da tela de criação
+    pub fn validate(&self, fields: &[JiraFieldMeta]) -> Vec<String> {
+        let mut errors = Vec::new();
+
+        if self.project_key.trim().is_empty() {
+            errors.push("project: Projeto é obrigatório".to_string());
+        }
+
+        if self.issue_type_id.trim().is_empty() {
+            errors.push("issuetype: Tipo de issue é obrigatório".to_string());
+        }
+
+        if self.summary.trim().is_empty() {
+            errors.push("summary: Resumo não pode estar vazio".to_string());
+        } else if self.summary.chars().count() > 255 {
+            errors.push("summary: Resumo deve ter no máximo 255 caracteres".to_string());
+        }
+
+        for field_id in self.fields.keys() {
+            if Self::BUILTIN_FIELDS.contains(&field_id.as_str()) {
+                errors.push(format!("{}: Campo deve ser informado pelas propriedades do rascunho", field_id));
+            }
+        }
+
+        errors.extend(validate_field_values(&self.fields, fields, &Self::BUILTIN_FIELDS, "tela de criação"));
+
+        errors
+    }
+}
+
 pub struct JiraClient {
     http_client: reqwest::Client,
+    requests: RequestExecutor,
     auth: JiraAuth,
-    config_manager: JiraConfigManager,
+    config_manager: Arc<JiraConfigManager>,
+    // Ausente se o banco não pôde ser aberto; o cliente segue funcionando sem cache
+    cache: Option<JiraCache>,
 }
 
 impl JiraClient {
+    // Cliente do perfil ativo
     pub fn new() -> Result<Self, JiraError> {
+        let profiles = ProfileManager::load()?;
+        Self::for_profile(profiles.active_id())
+    }
+
+    pub fn for_profile(profile_id: &str) -> Result<Self, JiraError> {
         let http_client = reqwest::Client::builder()
             .timeout(std::time::Duration::from_secs(30))
             .user_agent("Goji/1.0.0")
             .build()?;
 
+        let config_manager = Arc::new(JiraConfigManager::for_profile(profile_id)?);
+
         Ok(Self {
+            requests: RequestExecutor::new(http_client.clone(), profile_id),
             http_client,
-            auth: JiraAuth::new()?,
-            config_manager: JiraConfigManager::new()?,
+            auth: JiraAuth::with_manager(config_manager.clone()),
+            config_manager,
+            cache: JiraCache::open(profile_id).ok(),
         })
     }
 
+    pub fn config_manager(&self) -> &JiraConfigManager {
+        &self.config_manager
+    }
 
-    pub async fn get_current_user(&self) -> Result<JiraUser, JiraError> {
-        let config = self.config_manager.get_config()?
-            .ok_or(JiraError::InvalidConfig("Configuração JIRA não encontrada".to_string()))?;
+    pub fn auth(&self) -> &JiraAuth {
+        &self.auth
+    }
 
-        let auth_header = self.auth.get_auth_header()?
-            .ok_or(JiraError::AuthenticationFailed)?;
+    // === CACHE OFFLINE ===
 
-        let response = self.http_client
-            .get(&format!("{}/rest/api/3/myself", config.url))
-            .header("Authorization", auth_header)
-            .header("Accept", "application/json")
-            .send()
-            .await?;
+    // Última resposta gravada de cada consulta, sem acessar a rede
+    pub fn cached_current_user(&self) -> Option<JiraCached<JiraUser>> {
+        self.cache.as_ref()?.current_user().ok().flatten()
+    }
+
+    pub fn cached_projects(&self, limit: Option<u32>) -> Option<JiraCached<Vec<JiraProject>>> {
+        self.cache.as_ref()?.projects(limit).ok().flatten()
+    }
+
+    pub fn cached_search(&self, jql: &str, limit: Option<u32>) -> Option<JiraCached<Vec<JiraIssue>>> {
+        self.cache.as_ref()?.search(jql, limit).ok().flatten()
+    }
 
-        if !response.status().is_success() {
-            return Err(JiraError::AuthenticationFailed);
+    pub fn cache(&self) -> Option<&JiraCache> {
+        self.cache.as_ref()
+    }
+
+    // Falhas do cache local não devem derrubar uma requisição bem-sucedida
+    fn remember<F>(&self, store: F)
+    where
+        F: FnOnce(&JiraCache) -> Result<(), JiraError>,
+    {
+        if let Some(cache) = &self.cache {
+            let _ = store(cache);
         }
+    }
+
+    pub async fn get_current_user(&self) -> Result<JiraUser, JiraError> {
+        let (config, auth_header) = self.credentials().await?;
+
+        let request = self.http_client
+            .get(config.api_endpoint(&["myself"])?)
+            .header("Authorization", auth_header)
+            .header("Accept", "application/json");
+
+        let response = self.requests.send(request).await?;
 
         let user: JiraUser = response.json().await?;
+        self.remember(|cache| cache.store_current_user(&user));
         
         // Atualizar último uso
-        self.auth.update_last_used()?;
+        self.auth.update_last_used();
         
         Ok(user)
     }
 
-    pub async fn get_projects(&self) -> Result<Vec<JiraProject>, JiraError> {
-        let config = self.config_manager.get_config()?
-            .ok_or(JiraError::InvalidConfig("Configuração JIRA não encontrada".to_string()))?;
+    // Projetos visíveis ao usuário, paginados via /project/search (Cloud)
+    pub async fn get_projects(&self, limit: Option<u32>) -> Result<Vec<JiraProject>, JiraError> {
+        let (config, auth_header) = self.credentials().await?;
+
+        #[derive(Deserialize)]
+        struct ProjectsPage {
+            #[serde(default)]
+            values: Vec<JiraProject>,
+            #[serde(rename = "startAt", default)]
+            start_at: u32,
+            #[serde(rename = "isLast", default)]
+            is_last: bool,
+        }
 
-        let auth_header = self.auth.get_auth_header()?
-            .ok_or(JiraError::AuthenticationFailed)?;
+        let mut projects = Vec::new();
 
-        let response = self.http_client
-            .get(&format!("{}/rest/api/3/project", config.url))
-            .header("Authorization", auth_header)
-            .header("Accept", "application/json")
-            .send()
-            .await?;
+        // Server/Data Center não possui /project/search; /project devolve tudo de uma vez
+        if config.api_version() == JiraApiVersion::V2 {
+            let request = self.http_client
+                .get(config.api_endpoint(&["project"])?)
+                .query(&[("expand", "description")])
+                .header("Authorization", &auth_header)
+                .header("Accept", "application/json");
+
+            let response = self.requests.send(request).await?;
 
-        if !response.status().is_success() {
-            return Err(JiraError::AuthenticationFailed);
+            projects = response.json().await?;
+        } else {
+            let mut start_at = 0u32;
+
+            loop {
+                let page_size = page_size_for(limit, projects.len());
+
+                let request = self.http_client
+                    .get(config.api_endpoint(&["project", "search"])?)
+                    .query(&[
+                        ("startAt", start_at.to_string()),
+                        ("maxResults", page_size.to_string()),
+                        ("expand", "description".to_string()),
+                    ])
+                    .header("Authorization", &auth_header)
+                    .header("Accept", "application/json");
+
+                let response = self.requests.send(request).await?;
+
+                let page: ProjectsPage = response.json().await?;
+                let received = page.values.len() as u32;
+                projects.extend(page.values);
+
+                start_at = page.start_at + received;
+                if page.is_last || received == 0 || limit_reached(limit, projects.len()) {
+                    break;
+                }
+            }
         }
 
-        let projects: Vec<JiraProject> = response.json().await?;
-        
+        if let Some(limit) = limit {
+            projects.truncate(limit as usize);
+        }
+        self.remember(|cache| cache.store_projects(limit, &projects));
+
         // Atualizar último uso
-        self.auth.update_last_used()?;
+        self.auth.update_last_used();
         
         Ok(projects)
     }
 
-    pub async fn search_issues(&self, jql: &str, max_results: u32) -> Result<Vec<JiraIssue>, JiraError> {
-        let config = self.config_manager.get_config()?
-            .ok_or(JiraError::InvalidConfig("Configuração JIRA não encontrada".to_string()))?;
+    pub async fn search_issues(&self, jql: &str, limit: Option<u32>) -> Result<Vec<JiraIssue>, JiraError> {
+        let mut issues = Vec::new();
 
-        let auth_header = self.auth.get_auth_header()?
-            .ok_or(JiraError::AuthenticationFailed)?;
+        self.search_issues_paged(jql, limit, |page| {
+            issues.extend(page.issues);
+            Ok(())
+        }).await?;
 
-        #[derive(Serialize)]
-        struct SearchRequest {
-            jql: String,
-            #[serde(rename = "maxResults")]
-            max_results: u32,
-            fields: Vec<String>,
+        self.remember(|cache| cache.store_search(jql, limit, &issues));
+        Ok(issues)
+    }
+
+    // Busca paginada: cada página é entregue a `on_page` assim que chega.
+    // Usa /search/jql (paginação por token) e recorre ao endpoint legado
+    // /search (paginação por offset) em instâncias que não possuem o novo.
+    // Retorna a quantidade total de issues entregues.
+    pub async fn search_issues_paged<F>(&self, jql: &str, limit: Option<u32>, mut on_page: F) -> Result<u32, JiraError>
+    where
+        F: FnMut(JiraIssuePage) -> Result<(), JiraError>,
+    {
+        self.search_paged(jql, limit, &SEARCH_FIELDS, |page, _| on_page(page)).await
+    }
+
+    // Como search_issues_paged, trazendo também descrição e comentários de cada
+    // página (apenas das issues que os têm) para o índice da busca local
+    pub async fn search_issues_with_text<F>(&self, jql: &str, limit: Option<u32>, on_page: F) -> Result<u32, JiraError>
+    where
+        F: FnMut(JiraIssuePage, Vec<IssueText>) -> Result<(), JiraError>,
+    {
+        self.search_paged(jql, limit, &TEXT_SEARCH_FIELDS, on_page).await
+    }
+
+    async fn search_paged<F>(&self, jql: &str, limit: Option<u32>, fields: &[&str], mut on_page: F) -> Result<u32, JiraError>
+    where
+        F: FnMut(JiraIssuePage, Vec<IssueText>) -> Result<(), JiraError>,
+    {
+        let (config, auth_header) = self.credentials().await?;
+
+        let mut delivered = 0u32;
+        let mut cursor: Option<PageCursor> = None;
+        // Server/Data Center não possui /search/jql
+        let mut use_legacy = config.api_version() == JiraApiVersion::V2;
+
+        loop {
+            let page_size = page_size_for(limit, delivered as usize);
+
+            let page = if use_legacy {
+                let start_at = match &cursor {
+                    Some(PageCursor::Offset(start_at)) => *start_at,
+                    _ => 0,
+                };
+                self.search_page_legacy(&config, &auth_header, jql, fields, start_at, page_size).await?
+            } else {
+                let page_token = match &cursor {
+                    Some(PageCursor::Token(token)) => Some(token.as_str()),
+                    _ => None,
+                };
+                match self.search_page_enhanced(&config, &auth_header, jql, fields, page_token, page_size).await? {
+                    Some(page) => page,
+                    None if cursor.is_none() => {
+                        // Jira Server/Data Center: usar o endpoint legado
+                        use_legacy = true;
+                        continue;
+                    }
+                    None => return Err(JiraError::InvalidConfig("Endpoint de busca indisponível".to_string())),
+                }
+            };
+
+            let mut raw_issues = page.issues;
+            if let Some(limit) = limit {
+                raw_issues.truncate(limit.saturating_sub(delivered) as usize);
+            }
+
+            let texts: Vec<IssueText> = raw_issues.iter().filter_map(search_issue_text).collect();
+            let issues: Vec<JiraIssue> = raw_issues.into_iter().map(Into::into).collect();
+
+            let start_at = delivered;
+            let received = issues.len() as u32;
+            delivered += received;
+
+            let is_last = received == 0
+                || limit_reached(limit, delivered as usize)
+                || page.next.is_none();
+
+            on_page(JiraIssuePage {
+                issues,
+                start_at,
+                total: page.total,
+                is_last,
+            }, texts)?;
+
+            match page.next {
+                Some(next) if !is_last => cursor = Some(next),
+                _ => break,
+            }
         }
 
-        let search_request = SearchRequest {
-            jql: jql.to_string(),
-            max_results,
-            fields: vec![
-                "summary".to_string(),
-                "status".to_string(),
-                "assignee".to_string(),
-                "reporter".to_string(),
-                "created".to_string(),
-                "updated".to_string(),
-            ],
+        // Atualizar último uso
+        self.auth.update_last_used();
+        
+        Ok(delivered)
+    }
+
+    // Total de issues da consulta; aproximado no JIRA Cloud, exato no Server/Data Center
+    pub async fn count_issues(&self, jql: &str) -> Result<JiraIssueCount, JiraError> {
+        let (config, auth_header) = self.credentials().await?;
+
+        let count = match config.api_version() {
+            // Server/Data Center: página vazia do endpoint legado, que ainda informa o total
+            JiraApiVersion::V2 => {
+                let page = self.search_page_legacy(&config, &auth_header, jql, &SEARCH_FIELDS, 0, 0).await?;
+                JiraIssueCount {
+                    count: page.total.unwrap_or(0) as u64,
+                    approximate: false,
+                }
+            }
+            // Cloud: o /search legado foi removido; só resta a contagem aproximada
+            JiraApiVersion::V3 => {
+                #[derive(Deserialize)]
+                struct CountResponse {
+                    count: u64,
+                }
+
+                let request = self.http_client
+                    .post(config.api_endpoint(&["search", "approximate-count"])?)
+                    .header("Authorization", &auth_header)
+                    .header("Accept", "application/json")
+                    .header("Content-Type", "application/json")
+                    .json(&serde_json::json!({ "jql": jql }));
+
+                let count_response: CountResponse = self.requests.send_read_only(request).await?.json().await?;
+                JiraIssueCount {
+                    count: count_response.count,
+                    approximate: true,
+                }
+            }
         };
 
-        let response = self.http_client
-            .post(&format!("{}/rest/api/3/search", config.url))
-            .header("Authorization", auth_header)
-            .header("Accept", "application/json")
-            .header("Content-Type", "application/json")
-            .json(&search_request)
-            .send()
-            .await?;
+        // Atualizar último uso
+        self.auth.update_last_used();
 
-        if !response.status().is_success() {
-            return Err(JiraError::AuthenticationFailed);
-        }
+        Ok(count)
+    }
 
-        #[derive(Deserialize)]
-        struct SearchResponse {
-            issues: Vec<RawIssue>,
-        }
+    pub async fn get_issue(&self, key: &str) -> Result<JiraIssueDetail, JiraError> {
+        let (config, auth_header) = self.credentials().await?;
+
+        let request = self.http_client
+            .get(config.api_endpoint(&["issue", key])?)
+            .query(&[("expand", "renderedFields,names,schema,transitions")])
+            .header("Authorization", auth_header)
+            .header("Accept", "application/json");
+
+        let response = self.requests.send(request).await?;
 
         #[derive(Deserialize)]
-        struct RawIssue {
+        struct RawIssueDetail {
             id: String,
             key: String,
-            fields: IssueFields,
+            fields: DetailFields,
+            #[serde(rename = "renderedFields", default)]
+            rendered_fields: HashMap<String, serde_json::Value>,
+            #[serde(default)]
+            names: HashMap<String, String>,
+            #[serde(default)]
+            schema: HashMap<String, FieldSchema>,
+            #[serde(default)]
+            transitions: Vec<RawTransition>,
         }
 
         #[derive(Deserialize)]
-        struct IssueFields {
+        struct DetailFields {
             summary: String,
-            status: IssueStatus,
-            assignee: Option<IssueUser>,
-            reporter: Option<IssueUser>,
+            status: JiraStatus,
+            issuetype: JiraIssueType,
+            priority: Option<JiraPriority>,
+            resolution: Option<JiraResolution>,
+            assignee: Option<JiraUser>,
+            reporter: Option<JiraUser>,
             created: String,
             updated: String,
+            duedate: Option<String>,
+            description: Option<serde_json::Value>,
+            #[serde(default)]
+            labels: Vec<String>,
+            #[serde(default)]
+            components: Vec<JiraComponent>,
+            #[serde(rename = "fixVersions", default)]
+            fix_versions: Vec<JiraVersion>,
+            parent: Option<RawIssueRef>,
+            #[serde(default)]
+            subtasks: Vec<RawIssueRef>,
+            #[serde(default)]
+            issuelinks: Vec<RawIssueLink>,
+            comment: Option<CommentPage>,
+            // Demais campos, incluindo os customizados (customfield_*)
+            #[serde(flatten)]
+            other: HashMap<String, serde_json::Value>,
         }
 
         #[derive(Deserialize)]
-        struct IssueStatus {
-            name: String,
+        struct FieldSchema {
+            #[serde(rename = "type")]
+            field_type: Option<String>,
         }
 
         #[derive(Deserialize)]
-        struct IssueUser {
-            #[serde(rename = "displayName")]
-            display_name: String,
+        struct RawIssueLink {
+            id: String,
+            #[serde(rename = "type")]
+            link_type: LinkType,
+            #[serde(rename = "inwardIssue")]
+            inward_issue: Option<RawIssueRef>,
+            #[serde(rename = "outwardIssue")]
+            outward_issue: Option<RawIssueRef>,
         }
 
-        let search_response: SearchResponse = response.json().await?;
-        
-        let issues: Vec<JiraIssue> = search_response.issues.into_iter().map(|raw_issue| {
-            JiraIssue {
-                id: raw_issue.id,
-                key: raw_issue.key,
-                summary: raw_issue.fields.summary,
-                status: raw_issue.fields.status.name,
-                assignee: raw_issue.fields.assignee.map(|u| u.display_name),
-                reporter: raw_issue.fields.reporter.map(|u| u.display_name),
-                created: raw_issue.fields.created,
-                updated: raw_issue.fields.updated,
-            }
+        #[derive(Deserialize)]
+        struct LinkType {
+            name: String,
+            inward: String,
+            outward: String,
+        }
+
+        #[derive(Deserialize)]
+        struct CommentPage {
+            #[serde(default)]
+            comments: Vec<RawComment>,
+        }
+
+        let raw: RawIssueDetail = response.json().await?;
+        let fields = raw.fields;
+
+        // Corpos HTML dos comentários vêm em renderedFields.comment.comments
+        let rendered_comments: HashMap<String, String> = raw.rendered_fields
+            .get("comment")
+            .and_then(|c| c.get("comments"))
+            .and_then(|c| c.as_array())
+            .map(|comments| comments.iter().filter_map(|c| {
+                let id = c.get("id")?.as_str()?.to_string();
+                let body = c.get("body")?.as_str()?.to_string();
+                Some((id, body))
+            }).collect())
+            .unwrap_or_default();
+
+        let comments = fields.comment.map(|page| page.comments).unwrap_or_default()
+            .into_iter()
+            .map(|raw_comment| {
+                let mut comment = JiraComment::from(raw_comment);
+                if comment.rendered_body.is_none() {
+                    comment.rendered_body = rendered_comments.get(&comment.id).cloned();
+                }
+                comment
+            })
+            .collect();
+
+        let issue_links = fields.issuelinks.into_iter().filter_map(|link| {
+            let (direction, relation, issue) = match (link.inward_issue, link.outward_issue) {
+                (Some(issue), _) => ("inward", link.link_type.inward, issue),
+                (None, Some(issue)) => ("outward", link.link_type.outward, issue),
+                (None, None) => return None,
+            };
+            Some(JiraIssueLink {
+                id: link.id,
+                link_type: link.link_type.name,
+                direction: direction.to_string(),
+                relation,
+                issue: issue.into(),
+            })
         }).collect();
 
+        let mut custom_fields = BTreeMap::new();
+        let mut epic_link = None;
+        for (field_id, value) in fields.other {
+            if !field_id.starts_with("customfield_") || value.is_null() {
+                continue;
+            }
+
+            let name = raw.names.get(&field_id).cloned().unwrap_or_else(|| field_id.clone());
+            if name == "Epic Link" {
+                epic_link = value.as_str().map(|v| v.to_string());
+            }
+
+            // Nomes de exibição podem se repetir; desambiguar com o ID do campo
+            let display_key = if custom_fields.contains_key(&name) {
+                format!("{} ({})", name, field_id)
+            } else {
+                name
+            };
+
+            custom_fields.insert(display_key, JiraCustomField {
+                field_type: raw.schema.get(&field_id).and_then(|s| s.field_type.clone()),
+                rendered_value: raw.rendered_fields.get(&field_id).filter(|v| !v.is_null()).cloned(),
+                id: field_id,
+                value,
+            });
+        }
+
+        let parent: Option<JiraIssueRef> = fields.parent.map(Into::into);
+        let description = fields.description.filter(|d| !d.is_null());
+
+        // Projetos next-gen usam o pai como épico; projetos clássicos usam o campo "Epic Link"
+        let epic_key = parent.as_ref()
+            .filter(|p| p.issue_type.as_ref().is_some_and(|t| t.name.eq_ignore_ascii_case("epic")))
+            .map(|p| p.key.clone())
+            .or(epic_link);
+
+        let issue = JiraIssueDetail {
+            id: raw.id,
+            key: raw.key,
+            summary: fields.summary,
+            status: fields.status,
+            issue_type: fields.issuetype,
+            priority: fields.priority,
+            resolution: fields.resolution,
+            assignee: fields.assignee,
+            reporter: fields.reporter,
+            created: fields.created,
+            updated: fields.updated,
+            due_date: fields.duedate,
+            description_markdown: description.as_ref().map(adf::to_markdown),
+            description_html: description.as_ref().map(adf::to_html),
+            description,
+            rendered_description: raw.rendered_fields.get("description")
+                .and_then(|d| d.as_str())
+                .map(|d| d.to_string()),
+            labels: fields.labels,
+            components: fields.components,
+            fix_versions: fields.fix_versions,
+            parent,
+            epic_key,
+            subtasks: fields.subtasks.into_iter().map(Into::into).collect(),
+            issue_links,
+            comments,
+            transitions: raw.transitions.into_iter().map(Into::into).collect(),
+            custom_fields,
+        };
+
         // Atualizar último uso
-        self.auth.update_last_used()?;
-        
-        Ok(issues)
+        self.auth.update_last_used();
+
+        // A issue aberta entra no índice da busca local com todos os comentários
+        self.remember(|cache| {
+            cache.merge_opened_issue(&JiraIssue::from(&issue))?;
+            cache.index_texts(&[IssueText::from(&issue)])
+        });
+
+        Ok(issue)
+    }
+
+    // Tipos de issue que o usuário pode criar no projeto, carregados ao selecioná-lo.
+    // O endpoint agregado /issue/createmeta está descontinuado no Cloud e foi
+    // removido no Data Center 9; só é usado quando o endpoint por projeto não existe.
+    pub async fn get_create_issue_types(&self, project_key: &str) -> Result<Vec<JiraCreateMetaIssueType>, JiraError> {
+        let (config, auth_header) = self.credentials().await?;
+
+        #[derive(Deserialize)]
+        struct IssueTypesPage {
+            #[serde(alias = "issueTypes", default)]
+            values: Vec<JiraCreateMetaIssueType>,
+            #[serde(rename = "startAt", default)]
+            start_at: u32,
+            total: Option<u32>,
+            #[serde(rename = "isLast")]
+            is_last: Option<bool>,
+        }
+
+        let mut issue_types = Vec::new();
+        let mut start_at = 0u32;
+
+        loop {
+            let request = self.http_client
+                .get(config.api_endpoint(&["issue", "createmeta", project_key, "issuetypes"])?)
+                .query(&[("startAt", start_at), ("maxResults", PAGE_SIZE)])
+                .header("Authorization", &auth_header)
+                .header("Accept", "application/json");
+
+            // Data Center anterior ao 8.4: só existe o endpoint agregado
+            let response = match self.requests.send(request).await {
+                Err(JiraError::NotFound(_)) if start_at == 0 => {
+                    return self.get_create_issue_types_aggregated(&config, &auth_header, project_key).await;
+                }
+                result => result?,
+            };
+
+            let page: IssueTypesPage = response.json().await?;
+            let received = page.values.len() as u32;
+
+            issue_types.extend(page.values);
+
+            start_at = page.start_at + received;
+            let is_last = page.is_last.unwrap_or(false)
+                || received == 0
+                || page.total.is_some_and(|total| start_at >= total);
+            if is_last {
+                break;
+            }
+        }
+
+        // Atualizar último uso
+        self.auth.update_last_used();
+
+        Ok(issue_types)
+    }
+
+    // Campos da tela de criação para um projeto e tipo de issue
+    pub async fn get_create_fields(&self, project_key: &str, issue_type_id: &str) -> Result<Vec<JiraFieldMeta>, JiraError> {
+        let (config, auth_header) = self.credentials().await?;
+
+        #[derive(Deserialize)]
+        struct FieldsPage {
+            #[serde(alias = "values", default)]
+            fields: Vec<RawFieldMeta>,
+            #[serde(rename = "startAt", default)]
+            start_at: u32,
+            #[serde(default)]
+            total: u32,
+        }
+
+        let mut fields = Vec::new();
+        let mut start_at = 0u32;
+
+        loop {
+            let request = self.http_client
+                .get(config.api_endpoint(&["issue", "createmeta", project_key, "issuetypes", issue_type_id])?)
+                .query(&[("startAt", start_at), ("maxResults", 50)])
+                .header("Authorization", &auth_header)
+                .header("Accept", "application/json");
+
+            // Data Center anterior ao 8.4 não tem o endpoint por tipo de issue
+            let response = match self.requests.send(request).await {
+                Err(JiraError::NotFound(_)) if start_at == 0 => {
+                    return self.get_create_fields_aggregated(&config, &auth_header, project_key, issue_type_id).await;
+                }
+                result => result?,
+            };
+
+            let page: FieldsPage = response.json().await?;
+            let received = page.fields.len() as u32;
+
+            fields.extend(page.fields.into_iter().map(JiraFieldMeta::from));
+
+            start_at = page.start_at + received;
+            if received == 0 || start_at >= page.total {
+                break;
+            }
+        }
+
+        // Atualizar último uso
+        self.auth.update_last_used();
+
+        Ok(fields)
+    }
+
+    // Validar o rascunho localmente e criar a issue
+    pub async fn create_issue(&self, draft: &JiraIssueDraft) -> Result<JiraCreatedIssue, JiraError> {
+        let create_fields = self.get_create_fields(&draft.project_key, &draft.issue_type_id).await?;
+
+        let errors = draft.validate(&create_fields);
+        if !errors.is_empty() {
+            return Err(JiraError::Validation(errors));
+        }
+
+        let (config, auth_header) = self.credentials().await?;
+
+        let mut fields = draft.fields.clone();
+        fields.insert("project".to_string(), serde_json::json!({ "key": draft.project_key }));
+        fields.insert("issuetype".to_string(), serde_json::json!({ "id": draft.issue_type_id }));
+        fields.insert("summary".to_string(), serde_json::Value::String(draft.summary.trim().to_string()));
+        if let Some(description) = draft.description.as_deref().filter(|d| !d.trim().is_empty()) {
+            fields.insert("description".to_string(), rich_text(config.api_version(), description));
+        }
+
+        let request = self.http_client
+            .post(config.api_endpoint(&["issue"])?)
+            .header("Authorization", auth_header)
+            .header("Accept", "application/json")
+            .header("Content-Type", "application/json")
+            .json(&serde_json::json!({ "fields": fields }));
+
+        let response = self.requests.send(request).await?;
+
+        let created: JiraCreatedIssue = response.json().await?;
+
+        // Atualizar último uso
+        self.auth.update_last_used();
+
+        Ok(created)
+    }
+
+    // Transições disponíveis para a issue, incluindo campos das telas de transição
+    pub async fn get_transitions(&self, key: &str) -> Result<Vec<JiraTransition>, JiraError> {
+        let (config, auth_header) = self.credentials().await?;
+
+        let request = self.http_client
+            .get(config.api_endpoint(&["issue", key, "transitions"])?)
+            .query(&[("expand", "transitions.fields")])
+            .header("Authorization", auth_header)
+            .header("Accept", "application/json");
+
+        let response = self.requests.send(request).await?;
+
+        #[derive(Deserialize)]
+        struct TransitionsResponse {
+            transitions: Vec<RawTransition>,
+        }
+
+        let transitions_response: TransitionsResponse = response.json().await?;
+        let transitions = transitions_response.transitions.into_iter().map(Into::into).collect();
+
+        // Atualizar último uso
+        self.auth.update_last_used();
+
+        Ok(transitions)
+    }
+
+    // Executar uma transição, preenchendo os campos da tela e opcionalmente comentando
+    pub async fn transition_issue(
+        &self,
+        key: &str,
+        transition_id: &str,
+        fields: Option<serde_json::Map<String, serde_json::Value>>,
+        comment: Option<&str>,
+    ) -> Result<(), JiraError> {
+        let transitions = self.get_transitions(key).await?;
+        let transition = transitions.iter()
+            .find(|t| t.id == transition_id)
+            .ok_or_else(|| JiraError::Validation(vec![
+                format!("transition: Transição {} não disponível para {}", transition_id, key),
+            ]))?;
+
+        let fields = fields.unwrap_or_default();
+        let errors = validate_field_values(&fields, &transition.fields, &[], "tela de transição");
+        if !errors.is_empty() {
+            return Err(JiraError::Validation(errors));
+        }
+
+        let (config, auth_header) = self.credentials().await?;
+
+        let mut body = serde_json::json!({ "transition": { "id": transition_id } });
+        if !fields.is_empty() {
+            body["fields"] = serde_json::Value::Object(fields);
+        }
+        if let Some(comment) = comment.filter(|c| !c.trim().is_empty()) {
+            body["update"] = serde_json::json!({
+                "comment": [{ "add": { "body": rich_text(config.api_version(), comment) } }]
+            });
+        }
+
+        let request = self.http_client
+            .post(config.api_endpoint(&["issue", key, "transitions"])?)
+            .header("Authorization", auth_header)
+            .header("Accept", "application/json")
+            .header("Content-Type", "application/json")
+            .json(&body);
+
+        self.requests.send(request).await?;
+
+        // Atualizar último uso
+        self.auth.update_last_used();
+
+        Ok(())
+    }
+
+    // Todos os comentários da issue, em ordem de criação
+    pub async fn list_comments(&self, key: &str) -> Result<Vec<JiraComment>, JiraError> {
+        let (config, auth_header) = self.credentials().await?;
+
+        #[derive(Deserialize)]
+        struct CommentsPage {
+            #[serde(default)]
+            comments: Vec<RawComment>,
+            #[serde(rename = "startAt", default)]
+            start_at: u32,
+            #[serde(default)]
+            total: u32,
+        }
+
+        let mut comments = Vec::new();
+        let mut start_at = 0u32;
+
+        loop {
+            let request = self.http_client
+                .get(config.api_endpoint(&["issue", key, "comment"])?)
+                .query(&[
+                    ("startAt", start_at.to_string()),
+                    ("maxResults", "100".to_string()),
+                    ("orderBy", "created".to_string()),
+                    ("expand", "renderedBody".to_string()),
+                ])
+                .header("Authorization", &auth_header)
+                .header("Accept", "application/json");
+
+            let response = self.requests.send(request).await?;
+
+            let page: CommentsPage = response.json().await?;
+            let received = page.comments.len() as u32;
+            comments.extend(page.comments.into_iter().map(JiraComment::from));
+
+            start_at = page.start_at + received;
+            if received == 0 || start_at >= page.total {
+                break;
+            }
+        }
+
+        // Atualizar último uso
+        self.auth.update_last_used();
+
+        Ok(comments)
+    }
+
+    // Adicionar comentário a partir de Markdown
+    pub async fn add_comment(&self, key: &str, body_markdown: &str) -> Result<JiraComment, JiraError> {
+        if body_markdown.trim().is_empty() {
+            return Err(JiraError::Validation(vec!["body: Comentário não pode estar vazio".to_string()]));
+        }
+
+        let (config, auth_header) = self.credentials().await?;
+
+        let request = self.http_client
+            .post(config.api_endpoint(&["issue", key, "comment"])?)
+            .query(&[("expand", "renderedBody")])
+            .header("Authorization", auth_header)
+            .header("Accept", "application/json")
+            .header("Content-Type", "application/json")
+            .json(&serde_json::json!({ "body": rich_text(config.api_version(), body_markdown) }));
+
+        let response = self.requests.send(request).await?;
+
+        let comment: RawComment = response.json().await?;
+
+        // Atualizar último uso
+        self.auth.update_last_used();
+
+        Ok(comment.into())
+    }
+
+    // Editar comentário existente a partir de Markdown
+    pub async fn edit_comment(&self, key: &str, comment_id: &str, body_markdown: &str) -> Result<JiraComment, JiraError> {
+        if body_markdown.trim().is_empty() {
+            return Err(JiraError::Validation(vec!["body: Comentário não pode estar vazio".to_string()]));
+        }
+
+        let (config, auth_header) = self.credentials().await?;
+
+        let request = self.http_client
+            .put(config.api_endpoint(&["issue", key, "comment", comment_id])?)
+            .query(&[("expand", "renderedBody")])
+            .header("Authorization", auth_header)
+            .header("Accept", "application/json")
+            .header("Content-Type", "application/json")
+            .json(&serde_json::json!({ "body": rich_text(config.api_version(), body_markdown) }));
+
+        let response = self.requests.send(request).await?;
+
+        let comment: RawComment = response.json().await?;
+
+        // Atualizar último uso
+        self.auth.update_last_used();
+
+        Ok(comment.into())
+    }
+
+    pub async fn delete_comment(&self, key: &str, comment_id: &str) -> Result<(), JiraError> {
+        let (config, auth_header) = self.credentials().await?;
+
+        let request = self.http_client
+            .delete(config.api_endpoint(&["issue", key, "comment", comment_id])?)
+            .header("Authorization", auth_header)
+            .header("Accept", "application/json");
+
+        self.requests.send(request).await?;
+
+        // Atualizar último uso
+        self.auth.update_last_used();
+
+        Ok(())
+    }
+
+    // Todos os registros de trabalho da issue
+    pub async fn list_worklogs(&self, key: &str) -> Result<Vec<JiraWorklog>, JiraError> {
+        let (config, auth_header) = self.credentials().await?;
+
+        #[derive(Deserialize)]
+        struct WorklogsPage {
+            #[serde(default)]
+            worklogs: Vec<RawWorklog>,
+            #[serde(rename = "startAt", default)]
+            start_at: u32,
+            #[serde(default)]
+            total: u32,
+        }
+
+        let mut worklogs = Vec::new();
+        let mut start_at = 0u32;
+
+        loop {
+            let request = self.http_client
+                .get(config.api_endpoint(&["issue", key, "worklog"])?)
+                .query(&[("startAt", start_at), ("maxResults", 1000)])
+                .header("Authorization", &auth_header)
+                .header("Accept", "application/json");
+
+            let response = self.requests.send(request).await?;
+
+            let page: WorklogsPage = response.json().await?;
+            let received = page.worklogs.len() as u32;
+            worklogs.extend(page.worklogs.into_iter().map(JiraWorklog::from));
+
+            start_at = page.start_at + received;
+            if received == 0 || start_at >= page.total {
+                break;
+            }
+        }
+
+        // Atualizar último uso
+        self.auth.update_last_used();
+
+        Ok(worklogs)
+    }
+
+    pub async fn add_worklog(&self, key: &str, input: &JiraWorklogInput) -> Result<JiraWorklog, JiraError> {
+        let (config, auth_header) = self.credentials().await?;
+        let body = input.to_request_body(config.api_version())?;
+
+        let request = self.http_client
+            .post(config.api_endpoint(&["issue", key, "worklog"])?)
+            .header("Authorization", auth_header)
+            .header("Accept", "application/json")
+            .header("Content-Type", "application/json")
+            .json(&body);
+
+        let response = self.requests.send(request).await?;
+
+        let worklog: RawWorklog = response.json().await?;
+
+        // Atualizar último uso
+        self.auth.update_last_used();
+
+        Ok(worklog.into())
+    }
+
+    pub async fn update_worklog(&self, key: &str, worklog_id: &str, input: &JiraWorklogInput) -> Result<JiraWorklog, JiraError> {
+        let (config, auth_header) = self.credentials().await?;
+        let body = input.to_request_body(config.api_version())?;
+
+        let request = self.http_client
+            .put(config.api_endpoint(&["issue", key, "worklog", worklog_id])?)
+            .header("Authorization", auth_header)
+            .header("Accept", "application/json")
+            .header("Content-Type", "application/json")
+            .json(&body);
+
+        let response = self.requests.send(request).await?;
+
+        let worklog: RawWorklog = response.json().await?;
+
+        // Atualizar último uso
+        self.auth.update_last_used();
+
+        Ok(worklog.into())
+    }
+
+    pub async fn delete_worklog(&self, key: &str, worklog_id: &str) -> Result<(), JiraError> {
+        let (config, auth_header) = self.credentials().await?;
+
+        let request = self.http_client
+            .delete(config.api_endpoint(&["issue", key, "worklog", worklog_id])?)
+            .header("Authorization", auth_header)
+            .header("Accept", "application/json");
+
+        self.requests.send(request).await?;
+
+        // Atualizar último uso
+        self.auth.update_last_used();
+
+        Ok(())
+    }
+
+    // === MÉTODOS PRIVADOS ===
+
+    // Campos da tela de criação pelo endpoint agregado, para Data Center anterior ao 8.4
+    async fn get_create_fields_aggregated(
+        &self,
+        config: &JiraConfig,
+        auth_header: &str,
+        project_key: &str,
+        issue_type_id: &str,
+    ) -> Result<Vec<JiraFieldMeta>, JiraError> {
+        #[derive(Deserialize)]
+        struct CreateMetaResponse {
+            #[serde(default)]
+            projects: Vec<RawProject>,
+        }
+
+        #[derive(Deserialize)]
+        struct RawProject {
+            #[serde(default)]
+            issuetypes: Vec<RawIssueType>,
+        }
+
+        #[derive(Deserialize)]
+        struct RawIssueType {
+            #[serde(default)]
+            fields: BTreeMap<String, serde_json::Value>,
+        }
+
+        let request = self.http_client
+            .get(config.api_endpoint(&["issue", "createmeta"])?)
+            .query(&[
+                ("projectKeys", project_key),
+                ("issuetypeIds", issue_type_id),
+                ("expand", "projects.issuetypes.fields"),
+            ])
+            .header("Authorization", auth_header)
+            .header("Accept", "application/json");
+
+        let response = self.requests.send(request).await?;
+
+        let meta: CreateMetaResponse = response.json().await?;
+
+        let issue_type = meta.projects.into_iter()
+            .flat_map(|project| project.issuetypes)
+            .next()
+            .ok_or_else(|| JiraError::NotFound(JiraApiErrorBody {
+                error_messages: vec![format!("Tipo de issue {} não disponível no projeto {}", issue_type_id, project_key)],
+                ..Default::default()
+            }))?;
+
+        // Atualizar último uso
+        self.auth.update_last_used();
+
+        Ok(field_metas_from_map(issue_type.fields))
+    }
+
+    // Tipos de issue do projeto pelo endpoint agregado, para Data Center anterior ao 8.4
+    async fn get_create_issue_types_aggregated(
+        &self,
+        config: &JiraConfig,
+        auth_header: &str,
+        project_key: &str,
+    ) -> Result<Vec<JiraCreateMetaIssueType>, JiraError> {
+        #[derive(Deserialize)]
+        struct CreateMetaResponse {
+            #[serde(default)]
+            projects: Vec<RawProject>,
+        }
+
+        #[derive(Deserialize)]
+        struct RawProject {
+            #[serde(default)]
+            issuetypes: Vec<JiraCreateMetaIssueType>,
+        }
+
+        let request = self.http_client
+            .get(config.api_endpoint(&["issue", "createmeta"])?)
+            .query(&[("projectKeys", project_key)])
+            .header("Authorization", auth_header)
+            .header("Accept", "application/json");
+
+        let response = self.requests.send(request).await?;
+
+        let meta: CreateMetaResponse = response.json().await?;
+
+        // Atualizar último uso
+        self.auth.update_last_used();
+
+        Ok(meta.projects.into_iter().next().map(|p| p.issuetypes).unwrap_or_default())
     }
-}
\ No newline at end of file
+
+    // Página do endpoint /search/jql; None quando o endpoint não existe na instância
+    async fn search_page_enhanced(
+        &self,
+        config: &JiraConfig,
+        auth_header: &str,
+        jql: &str,
+        fields: &[&str],
+        page_token: Option<&str>,
+        page_size: u32,
+    ) -> Result<Option<SearchPage>, JiraError> {
+        #[derive(Serialize)]
+        struct SearchRequest<'a> {
+            jql: &'a str,
+            #[serde(rename = "nextPageToken", skip_serializing_if = "Option::is_none")]
+            next_page_token: Option<&'a str>,
+            #[serde(rename = "maxResults")]
+            max_results: u32,
+            fields: &'a [&'a str],
+        }
+
+        #[derive(Deserialize)]
+        struct SearchResponse {
+            #[serde(default)]
+            issues: Vec<RawSearchIssue>,
+            #[serde(rename = "nextPageToken")]
+            next_page_token: Option<String>,
+            #[serde(rename = "isLast", default)]
+            is_last: bool,
+        }
+
+        let search_request = SearchRequest {
+            jql,
+            next_page_token: page_token,
+            max_results: page_size,
+            fields,
+        };
+
+        let request = self.http_client
+            .post(config.api_endpoint(&["search", "jql"])?)
+            .header("Authorization", auth_header)
+            .header("Accept", "application/json")
+            .header("Content-Type", "application/json")
+            .json(&search_request);
+
+        let response = match self.requests.send_read_only(request).await {
+            Err(JiraError::NotFound(_)) => return Ok(None),
+            result => result?,
+        };
+
+        let search_response: SearchResponse = response.json().await?;
+
+        let next = match search_response.next_page_token {
+            Some(token) if !search_response.is_last => Some(PageCursor::Token(token)),
+            _ => None,
+        };
+
+        Ok(Some(SearchPage {
+            issues: search_response.issues,
+            total: None,
+            next,
+        }))
+    }
+
+    // Página do endpoint legado /search, paginado por offset
+    async fn search_page_legacy(
+        &self,
+        config: &JiraConfig,
+        auth_header: &str,
+        jql: &str,
+        fields: &[&str],
+        start_at: u32,
+        page_size: u32,
+    ) -> Result<SearchPage, JiraError> {
+        #[derive(Serialize)]
+        struct SearchRequest<'a> {
+            jql: &'a str,
+            #[serde(rename = "startAt")]
+            start_at: u32,
+            #[serde(rename = "maxResults")]
+            max_results: u32,
+            fields: &'a [&'a str],
+        }
+
+        #[derive(Deserialize)]
+        struct SearchResponse {
+            #[serde(rename = "startAt", default)]
+            start_at: u32,
+            total: Option<u32>,
+            #[serde(default)]
+            issues: Vec<RawSearchIssue>,
+        }
+
+        let search_request = SearchRequest {
+            jql,
+            start_at,
+            max_results: page_size,
+            fields,
+        };
+
+        let request = self.http_client
+            .post(config.api_endpoint(&["search"])?)
+            .header("Authorization", auth_header)
+            .header("Accept", "application/json")
+            .header("Content-Type", "application/json")
+            .json(&search_request);
+
+        let response = self.requests.send_read_only(request).await?;
+
+        let search_response: SearchResponse = response.json().await?;
+
+        let next_start = search_response.start_at + search_response.issues.len() as u32;
+        let has_more = !search_response.issues.is_empty()
+            && search_response.total.is_some_and(|total| next_start < total);
+
+        Ok(SearchPage {
+            issues: search_response.issues,
+            total: search_response.total,
+            next: has_more.then_some(PageCursor::Offset(next_start)),
+        })
+    }
+
+    // O cabeçalho vem antes da configuração: no OAuth ele pode renovar o token salvo
+    async fn credentials(&self) -> Result<(JiraConfig, String), JiraError> {
+        let auth_header = self.auth.get_auth_header().await?
+            .ok_or(JiraError::InvalidConfig("Configuração JIRA não encontrada".to_string()))?;
+
+        let config = self.config_manager.get_config()?
+            .ok_or(JiraError::InvalidConfig("Configuração JIRA não encontrada".to_string()))?;
+
+        Ok((config, auth_header))
+    }
+}
+
+// Validar valores de campos contra metadados de tela; campos em `skip` são tratados pelo chamador
+fn validate_field_values(
+    values: &serde_json::Map<String, serde_json::Value>,
+    metas: &[JiraFieldMeta],
+    skip: &[&str],
+    screen: &str,
+) -> Vec<String> {
+    let mut errors = Vec::new();
+
+    for meta in metas {
+        if skip.contains(&meta.field_id.as_str()) {
+            continue;
+        }
+
+        let value = values.get(&meta.field_id);
+        if meta.required && !meta.has_default_value && value.map_or(true, is_empty_field_value) {
+            errors.push(format!("{}: Campo obrigatório \"{}\" não preenchido", meta.field_id, meta.name));
+            continue;
+        }
+
+        if let Some(value) = value {
+            if !meta.allowed_values.is_empty() && !is_allowed_value(value, &meta.allowed_values) {
+                errors.push(format!("{}: Valor não permitido para \"{}\"", meta.field_id, meta.name));
+            }
+        }
+    }
+
+    for field_id in values.keys() {
+        if !skip.contains(&field_id.as_str()) && !metas.iter().any(|meta| &meta.field_id == field_id) {
+            errors.push(format!("{}: Campo não disponível na {}", field_id, screen));
+        }
+    }
+
+    errors
+}
+
+fn is_empty_field_value(value: &serde_json::Value) -> bool {
+    match value {
+        serde_json::Value::Null => true,
+        serde_json::Value::String(s) => s.trim().is_empty(),
+        serde_json::Value::Array(items) => items.is_empty(),
+        serde_json::Value::Object(map) => map.is_empty(),
+        _ => false,
+    }
+}
+
+// Metadados de campo indexados pelo identificador (transições e createmeta agregado)
+fn field_metas_from_map(fields: BTreeMap<String, serde_json::Value>) -> Vec<JiraFieldMeta> {
+    fields.into_iter()
+        .filter_map(|(field_id, mut meta)| {
+            // Alguns campos não trazem o identificador no corpo
+            if let Some(obj) = meta.as_object_mut() {
+                if !obj.contains_key("key") {
+                    obj.entry("fieldId").or_insert(serde_json::Value::String(field_id));
+                }
+            }
+            serde_json::from_value::<RawFieldMeta>(meta).ok().map(JiraFieldMeta::from)
+        })
+        .collect()
+}
+
+// Valores de opção são comparados pelo primeiro identificador presente (id, key, name ou value)
+fn is_allowed_value(value: &serde_json::Value, allowed: &[serde_json::Value]) -> bool {
+    let matches = |v: &serde_json::Value| {
+        let identifier = ["id", "key", "name", "value"].iter()
+            .find_map(|k| v.get(*k).and_then(|i| i.as_str()).map(|i| (*k, i)));
+
+        match identifier {
+            Some((field, id)) => allowed.iter()
+                .any(|a| a.get(field).and_then(|i| i.as_str()) == Some(id)),
+            // Valores sem identificador não podem ser verificados localmente
+            None => true,
+        }
+    };
+
+    match value {
+        serde_json::Value::Array(items) => items.iter().all(matches),
+        other => matches(other),
+    }
+}
+
+fn page_size_for(limit: Option<u32>, fetched: usize) -> u32 {
+    match limit {
+        Some(limit) => limit.saturating_sub(fetched as u32).clamp(1, PAGE_SIZE),
+        None => PAGE_SIZE,
+    }
+}
+
+// Texto rico em Markdown no formato aceito pela versão da API: ADF (v3) ou wiki markup (v2)
+fn rich_text(version: JiraApiVersion, markdown: &str) -> serde_json::Value {
+    match version {
+        JiraApiVersion::V3 => adf::from_markdown(markdown),
+        JiraApiVersion::V2 => serde_json::Value::String(adf::markdown_to_wiki(markdown)),
+    }
+}
+
+fn limit_reached(limit: Option<u32>, fetched: usize) -> bool {
+    limit.is_some_and(|limit| fetched >= limit as usize)
+}
+
+// Texto longo de uma issue da busca; None quando não foi pedido ou está vazio
+fn search_issue_text(raw: &RawSearchIssue) -> Option<IssueText> {
+    let description = raw.fields.description.as_ref()
+        .filter(|d| !d.is_null())
+        .map(adf::to_markdown)
+        .unwrap_or_default();
+
+    let comments = raw.fields.comment.as_ref()
+        .map(|c| c.comments.iter().map(|comment| adf::to_markdown(&comment.body)).collect::<Vec<_>>().join("\n\n"))
+        .unwrap_or_default();
+
+    if description.is_empty() && comments.is_empty() {
+        return None;
+    }
+
+    Some(IssueText {
+        key: raw.key.clone(),
+        description,
+        comments,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn field_metas_from_map_fills_missing_ids() {
+        // Formato do createmeta agregado (Data Center 8.x): identificador só na chave do mapa
+        let fields: BTreeMap<String, serde_json::Value> = serde_json::from_value(serde_json::json!({
+            "summary": { "required": true, "name": "Resumo", "schema": { "type": "string" } },
+            "customfield_10010": {
+                "key": "customfield_10010",
+                "required": false,
+                "name": "Sprint",
+                "schema": { "type": "array", "items": "string" },
+                "allowedValues": [{ "id": "1" }]
+            }
+        })).unwrap();
+
+        let metas = field_metas_from_map(fields);
+
+        assert_eq!(metas.len(), 2);
+        assert_eq!(metas[0].field_id, "customfield_10010");
+        assert_eq!(metas[0].schema_items.as_deref(), Some("string"));
+        assert_eq!(metas[0].allowed_values.len(), 1);
+        assert_eq!(metas[1].field_id, "summary");
+        assert!(metas[1].required);
+    }
+}
@@ -1,41 +1,184 @@
+use std::sync::Mutex;
 use serde::{Serialize, Deserialize};
+use ts_rs::TS;
 use chrono::{DateTime, Utc};
 use base64::{Engine as _, engine::general_purpose};
 use crate::jira::error::JiraError;
 use crate::jira::secure_storage::SecureStorage;
-use crate::jira::environment::EnvironmentInfo;
+use crate::jira::credential_store::CredentialStore;
+use crate::jira::environment::StorageBackend;
+use crate::jira::vault;
+use crate::jira::profiles::{JiraProfile, ProfileManager};
 
-#[derive(Serialize, Deserialize, Clone)]
+// Forma de autenticação da instância
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub enum JiraAuthMode {
+    // JIRA Cloud: email + API token via Basic
+    #[default]
+    CloudApiToken,
+    // Server/Data Center: Personal Access Token via Bearer
+    DataCenterPat,
+    // Server/Data Center: usuário e senha via Basic
+    BasicPassword,
+    // JIRA Cloud: OAuth 2.0 (3LO); token renovado automaticamente pelo JiraAuth
+    OAuth,
+}
+
+impl JiraAuthMode {
+    pub fn default_api_version(&self) -> JiraApiVersion {
+        match self {
+            JiraAuthMode::CloudApiToken | JiraAuthMode::OAuth => JiraApiVersion::V3,
+            JiraAuthMode::DataCenterPat | JiraAuthMode::BasicPassword => JiraApiVersion::V2,
+        }
+    }
+}
+
+// Versão da REST API: v3 (Cloud, textos em ADF) ou v2 (Server/Data Center 8/9, wiki markup)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub enum JiraApiVersion {
+    V2,
+    V3,
+}
+
+impl JiraApiVersion {
+    pub fn path(&self) -> &'static str {
+        match self {
+            JiraApiVersion::V2 => "/rest/api/2",
+            JiraApiVersion::V3 => "/rest/api/3",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, TS)]
+#[ts(export)]
 pub struct JiraConfig {
     pub url: String,
+    // Email no Cloud, nome de usuário no modo BasicPassword; ignorado com PAT
     pub email: String,
+    // API token, Personal Access Token, senha ou access token OAuth, conforme auth_mode
     pub token: String,
+    // Configurações salvas antes do suporte a Data Center são do Cloud
+    #[serde(default)]
+    pub auth_mode: JiraAuthMode,
+    // None usa a versão padrão do modo de autenticação
+    #[serde(default)]
+    pub api_version: Option<JiraApiVersion>,
+    // Base da API quando difere do site, ex.: gateway api.atlassian.com no OAuth
+    #[serde(default)]
+    pub api_url: Option<String>,
+    #[ts(type = "string")]
     pub created_at: DateTime<Utc>,
+    #[ts(type = "string | null")]
     pub last_used: Option<DateTime<Utc>>,
 }
 
+impl JiraConfig {
+    pub fn api_version(&self) -> JiraApiVersion {
+        self.api_version.unwrap_or_else(|| self.auth_mode.default_api_version())
+    }
+
+    // URL base da REST API, ex.: https://empresa.atlassian.net/rest/api/3
+    pub fn api_base(&self) -> String {
+        let base = self.api_url.as_deref().unwrap_or(&self.url);
+        format!("{}{}", base.trim_end_matches('/'), self.api_version().path())
+    }
+
+    // URL de um recurso da API; cada segmento é codificado, já que chaves e ids
+    // vêm de dados externos, ex.: api_endpoint(&["issue", key, "comment"])
+    pub fn api_endpoint(&self, segments: &[&str]) -> Result<reqwest::Url, JiraError> {
+        let mut url = reqwest::Url::parse(&self.api_base())
+            .map_err(|e| JiraError::InvalidConfig(format!("URL do JIRA inválida: {}", e)))?;
+
+        url.path_segments_mut()
+            .map_err(|_| JiraError::InvalidConfig("URL do JIRA inválida".to_string()))?
+            .pop_if_empty()
+            .extend(segments);
+
+        Ok(url)
+    }
+
+    pub fn auth_header(&self) -> String {
+        match self.auth_mode {
+            JiraAuthMode::DataCenterPat | JiraAuthMode::OAuth => format!("Bearer {}", self.token),
+            JiraAuthMode::CloudApiToken | JiraAuthMode::BasicPassword => {
+                let credentials = format!("{}:{}", self.email, self.token);
+                let encoded = general_purpose::STANDARD.encode(credentials);
+                format!("Basic {}", encoded)
+            }
+        }
+    }
+}
+
+// Apenas https, exceto loopback (servidores locais usados em testes)
+pub fn is_allowed_url(url: &str) -> bool {
+    match reqwest::Url::parse(url) {
+        Ok(url) if url.scheme() == "https" => true,
+        Ok(url) if url.scheme() == "http" => matches!(url.host_str(), Some("127.0.0.1" | "localhost" | "[::1]")),
+        _ => false,
+    }
+}
+
 pub struct JiraConfigManager {
-    secure_storage: SecureStorage,
+    profile_id: String,
+    store: Box<dyn CredentialStore>,
+    // Configuração já decifrada; evita ler o keyring/arquivo a cada requisição
+    cache: Mutex<Option<JiraConfig>>,
+    // Último uso nesta execução. Fica só em memória: regravar a configuração a cada
+    // requisição poderia restaurar um token OAuth já renovado por outra instância.
+    last_used: Mutex<Option<DateTime<Utc>>>,
 }
 
 impl JiraConfigManager {
+    // Gerenciador do perfil ativo
     pub fn new() -> Result<Self, JiraError> {
-        let secure_storage = SecureStorage::new("goji", "jira_config")?;
-        
-        Ok(Self {
-            secure_storage,
-        })
+        let profiles = ProfileManager::load()?;
+        Self::for_profile(profiles.active_id())
+    }
+
+    pub fn for_profile(profile_id: &str) -> Result<Self, JiraError> {
+        let secure_storage = SecureStorage::new("goji", &JiraProfile::config_slot(profile_id))?;
+        Ok(Self::with_store(profile_id, Box::new(secure_storage)))
+    }
+
+    // Gerenciador sobre um armazenamento qualquer (ex.: InMemoryStore em testes)
+    pub fn with_store(profile_id: &str, store: Box<dyn CredentialStore>) -> Self {
+        Self {
+            profile_id: profile_id.to_string(),
+            store,
+            cache: Mutex::new(None),
+            last_used: Mutex::new(None),
+        }
+    }
+
+    pub fn profile_id(&self) -> &str {
+        &self.profile_id
     }
 
     // Salvar configuração JIRA
     pub fn save_config(&self, config: &JiraConfig) -> Result<(), JiraError> {
         // Validar configuração
-        if config.url.trim().is_empty() || !config.url.starts_with("https://") {
-            return Err(JiraError::InvalidConfig("URL deve começar com https://".to_string()));
+        if config.url.trim().is_empty() || !is_allowed_url(&config.url) {
+            return Err(JiraError::InvalidConfig("URL deve começar com https:// (http:// apenas em loopback)".to_string()));
+        }
+
+        if config.api_url.as_deref().is_some_and(|url| !is_allowed_url(url)) {
+            return Err(JiraError::InvalidConfig("URL da API deve começar com https:// (http:// apenas em loopback)".to_string()));
         }
         
-        if config.email.trim().is_empty() || !config.email.contains('@') {
-            return Err(JiraError::InvalidConfig("Email deve ser válido".to_string()));
+        match config.auth_mode {
+            JiraAuthMode::CloudApiToken => {
+                if config.email.trim().is_empty() || !config.email.contains('@') {
+                    return Err(JiraError::InvalidConfig("Email deve ser válido".to_string()));
+                }
+            }
+            JiraAuthMode::BasicPassword => {
+                if config.email.trim().is_empty() {
+                    return Err(JiraError::InvalidConfig("Usuário não pode estar vazio".to_string()));
+                }
+            }
+            JiraAuthMode::DataCenterPat | JiraAuthMode::OAuth => {}
         }
         
         if config.token.trim().is_empty() {
@@ -45,22 +188,41 @@ impl JiraConfigManager {
         // Serializar configuração
         let json_data = serde_json::to_string(config)?;
         
-        // Salvar usando SecureStorage
-        self.secure_storage.store(&json_data)?;
+        // Salvar no armazenamento seguro
+        self.store.store(&json_data)?;
+        *self.cache.lock().unwrap_or_else(|e| e.into_inner()) = Some(config.clone());
         
         Ok(())
     }
 
     // Recuperar configuração JIRA
     pub fn get_config(&self) -> Result<Option<JiraConfig>, JiraError> {
-        if let Some(json_data) = self.secure_storage.retrieve()? {
+        let mut cache = self.cache.lock().unwrap_or_else(|e| e.into_inner());
+
+        if let Some(config) = cache.as_ref() {
+            // A cópia em memória não pode sobreviver ao bloqueio do cofre
+            if self.store.backend() == StorageBackend::PassphraseFile {
+                vault::current_key()?;
+            }
+            return Ok(Some(self.with_last_used(config.clone())));
+        }
+
+        if let Some(json_data) = self.store.retrieve()? {
             let config: JiraConfig = serde_json::from_str(&json_data)?;
-            Ok(Some(config))
+            *cache = Some(config.clone());
+            Ok(Some(self.with_last_used(config)))
         } else {
             Ok(None)
         }
     }
 
+    // Descartar a cópia em memória e ler de novo do armazenamento, ex.: token
+    // renovado por outro gerenciador do mesmo perfil
+    pub fn reload_config(&self) -> Result<Option<JiraConfig>, JiraError> {
+        *self.cache.lock().unwrap_or_else(|e| e.into_inner()) = None;
+        self.get_config()
+    }
+
     // Testar configuração
     pub async fn test_config(&self) -> Result<bool, JiraError> {
         if let Some(config) = self.get_config()? {
@@ -69,11 +231,9 @@ impl JiraConfigManager {
                 .timeout(std::time::Duration::from_secs(30))
                 .build()?;
                 
-            let auth_header = Self::create_auth_header(&config);
-            
             let response = client
-                .get(&format!("{}/rest/api/3/myself", config.url))
-                .header("Authorization", auth_header)
+                .get(config.api_endpoint(&["myself"])?)
+                .header("Authorization", config.auth_header())
                 .header("Accept", "application/json")
                 .send()
                 .await?;
@@ -86,28 +246,145 @@ impl JiraConfigManager {
 
     // Limpar configuração
     pub fn clear_config(&self) -> Result<(), JiraError> {
-        self.secure_storage.delete()
+        *self.cache.lock().unwrap_or_else(|e| e.into_inner()) = None;
+        self.store.delete()
+    }
+
+    // Atualizar timestamp de último uso (sem gravar no armazenamento seguro)
+    pub fn update_last_used(&self) {
+        *self.last_used.lock().unwrap_or_else(|e| e.into_inner()) = Some(Utc::now());
     }
 
-    // Atualizar timestamp de último uso
-    pub fn update_last_used(&self) -> Result<(), JiraError> {
-        if let Some(mut config) = self.get_config()? {
-            config.last_used = Some(Utc::now());
-            self.save_config(&config)?;
+    // === MÉTODOS PRIVADOS ===
+
+    fn with_last_used(&self, mut config: JiraConfig) -> JiraConfig {
+        if let Some(last_used) = *self.last_used.lock().unwrap_or_else(|e| e.into_inner()) {
+            config.last_used = Some(last_used);
         }
-        Ok(())
+        config
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jira::credential_store::InMemoryStore;
 
-    // Obter informações sobre o ambiente de segurança
-    pub fn get_environment_info(&self) -> &EnvironmentInfo {
-        self.secure_storage.get_environment_info()
+    fn config(url: &str, auth_mode: JiraAuthMode) -> JiraConfig {
+        JiraConfig {
+            url: url.to_string(),
+            email: "dev@example.com".to_string(),
+            token: "token".to_string(),
+            auth_mode,
+            api_version: None,
+            api_url: None,
+            created_at: Utc::now(),
+            last_used: None,
+        }
     }
 
-    // === MÉTODOS PRIVADOS ===
+    #[test]
+    fn api_endpoint_encodes_each_segment() {
+        let config = config("https://empresa.atlassian.net/", JiraAuthMode::CloudApiToken);
+
+        let url = config.api_endpoint(&["issue", "ABC-1/../../myself", "comment", "10 01?x=1#y"]).unwrap();
+
+        assert_eq!(
+            url.as_str(),
+            "https://empresa.atlassian.net/rest/api/3/issue/ABC-1%2F..%2F..%2Fmyself/comment/10%2001%3Fx=1%23y"
+        );
+    }
+
+    #[test]
+    fn api_endpoint_keeps_context_path() {
+        let config = config("https://jira.empresa.com/jira", JiraAuthMode::DataCenterPat);
+
+        let url = config.api_endpoint(&["issue", "ABC-1"]).unwrap();
+
+        assert_eq!(url.as_str(), "https://jira.empresa.com/jira/rest/api/2/issue/ABC-1");
+    }
+
+    fn manager() -> JiraConfigManager {
+        JiraConfigManager::with_store("teste", Box::new(InMemoryStore::new()))
+    }
+
+    #[test]
+    fn with_store_saves_reads_and_clears() {
+        let manager = manager();
+        assert!(manager.get_config().unwrap().is_none());
+
+        manager.save_config(&config("https://empresa.atlassian.net", JiraAuthMode::CloudApiToken)).unwrap();
+        let saved = manager.get_config().unwrap().unwrap();
+        assert_eq!(saved.url, "https://empresa.atlassian.net");
+        assert_eq!(saved.email, "dev@example.com");
+
+        // Lido do armazenamento, não só da cópia em memória
+        let reloaded = manager.reload_config().unwrap().unwrap();
+        assert_eq!(reloaded.token, "token");
+
+        manager.clear_config().unwrap();
+        assert!(manager.get_config().unwrap().is_none());
+    }
 
-    fn create_auth_header(config: &JiraConfig) -> String {
-        let credentials = format!("{}:{}", config.email, config.token);
-        let encoded = general_purpose::STANDARD.encode(credentials);
-        format!("Basic {}", encoded)
+    // Conta as gravações no armazenamento
+    struct CountingStore {
+        inner: InMemoryStore,
+        writes: std::sync::Arc<std::sync::atomic::AtomicUsize>,
     }
-}
\ No newline at end of file
+
+    impl CredentialStore for CountingStore {
+        fn store(&self, data: &str) -> Result<(), JiraError> {
+            self.writes.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            self.inner.store(data)
+        }
+
+        fn retrieve(&self) -> Result<Option<String>, JiraError> {
+            self.inner.retrieve()
+        }
+
+        fn delete(&self) -> Result<(), JiraError> {
+            self.inner.delete()
+        }
+
+        fn backend(&self) -> StorageBackend {
+            self.inner.backend()
+        }
+    }
+
+    #[test]
+    fn last_used_is_not_written_to_the_store() {
+        let writes = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let store = CountingStore { inner: InMemoryStore::new(), writes: writes.clone() };
+        let manager = JiraConfigManager::with_store("teste", Box::new(store));
+
+        manager.save_config(&config("https://empresa.atlassian.net", JiraAuthMode::CloudApiToken)).unwrap();
+        manager.update_last_used();
+        manager.update_last_used();
+
+        assert_eq!(writes.load(std::sync::atomic::Ordering::SeqCst), 1);
+        assert!(manager.get_config().unwrap().unwrap().last_used.is_some());
+        // Ainda visível depois de reler o armazenamento
+        assert!(manager.reload_config().unwrap().unwrap().last_used.is_some());
+    }
+
+    #[test]
+    fn save_config_rejects_invalid_settings() {
+        let manager = manager();
+
+        let plain_http = config("http://jira.empresa.com", JiraAuthMode::DataCenterPat);
+        assert!(matches!(manager.save_config(&plain_http), Err(JiraError::InvalidConfig(_))));
+
+        let mut no_email = config("https://empresa.atlassian.net", JiraAuthMode::CloudApiToken);
+        no_email.email = "dev".to_string();
+        assert!(matches!(manager.save_config(&no_email), Err(JiraError::InvalidConfig(_))));
+
+        let mut no_token = config("https://empresa.atlassian.net", JiraAuthMode::CloudApiToken);
+        no_token.token = "  ".to_string();
+        assert!(matches!(manager.save_config(&no_token), Err(JiraError::InvalidConfig(_))));
+
+        assert!(manager.get_config().unwrap().is_none());
+
+        // Loopback em http é aceito para servidores locais
+        manager.save_config(&config("http://127.0.0.1:8080", JiraAuthMode::DataCenterPat)).unwrap();
+    }
+}
@@ -0,0 +1,559 @@
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+#[cfg(unix)]
+use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+use keyring::Entry;
+use aes_gcm::{Aes256Gcm, Key, Nonce, KeyInit};
+use aes_gcm::aead::{Aead, OsRng};
+use rand::RngCore;
+use serde::{Serialize, Deserialize};
+use base64::{Engine as _, engine::general_purpose};
+use crate::jira::error::JiraError;
+use crate::jira::environment::StorageBackend;
+use crate::jira::vault;
+
+// Armazenamento de um único segredo (slot). O SecureStorage escolhe a implementação
+// conforme o ambiente; testes e ferramentas podem injetar outra diretamente.
+pub trait CredentialStore: Send + Sync {
+    fn store(&self, data: &str) -> Result<(), JiraError>;
+
+    // `Ok(None)` quando o slot nunca foi gravado
+    fn retrieve(&self) -> Result<Option<String>, JiraError>;
+
+    fn delete(&self) -> Result<(), JiraError>;
+
+    fn backend(&self) -> StorageBackend;
+
+    // Apagar a chave de cifra própria do backend (entrada `_key` ou arquivo `.key`)
+    fn delete_key(&self) -> Result<(), JiraError> {
+        Ok(())
+    }
+
+    // Apagar dados e chave sem deixar cópia recuperável, após migrar para outro backend
+    fn secure_delete(&self) -> Result<(), JiraError> {
+        match self.delete() {
+            Ok(()) | Err(JiraError::Keyring(keyring::Error::NoEntry)) => {}
+            Err(e) => return Err(e),
+        }
+        self.delete_key()
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct EncryptedData {
+    nonce: Vec<u8>,
+    ciphertext: Vec<u8>,
+}
+
+// === KEYRING NATIVO ===
+
+pub struct KeyringStore {
+    entry: Entry,
+    // A chave AES fica em uma entrada própria; a entrada principal guarda só os dados
+    key_entry: Entry,
+    cipher_key: Key<Aes256Gcm>,
+}
+
+impl KeyringStore {
+    pub fn new(service: &str, username: &str) -> Result<Self, JiraError> {
+        let entry = Entry::new(service, username)?;
+        let key_entry = Entry::new(service, &key_entry_name(username))?;
+        let cipher_key = Self::get_or_create_cipher_key(&key_entry)?;
+
+        Ok(Self {
+            entry,
+            key_entry,
+            cipher_key,
+        })
+    }
+
+    fn get_or_create_cipher_key(key_entry: &Entry) -> Result<Key<Aes256Gcm>, JiraError> {
+        match key_entry.get_password() {
+            Ok(key_data) => {
+                if let Some(key) = decode_key(&key_data) {
+                    return Ok(key);
+                }
+            }
+            Err(keyring::Error::NoEntry) => {}
+            // Não gerar outra chave se o keyring apenas falhou: os dados ficariam ilegíveis
+            Err(e) => return Err(JiraError::Keyring(e)),
+        }
+
+        // Salvar nova chave; sem ela os dados gravados não poderiam ser lidos depois
+        let (key, key_b64) = generate_key();
+        key_entry.set_password(&key_b64)?;
+        Ok(key)
+    }
+}
+
+impl CredentialStore for KeyringStore {
+    fn store(&self, data: &str) -> Result<(), JiraError> {
+        // Ainda criptografar mesmo no keyring para dupla proteção
+        let encrypted = encrypt_data(&self.cipher_key, data.as_bytes())?;
+        let encrypted_b64 = general_purpose::STANDARD.encode(serde_json::to_vec(&encrypted)?);
+        self.entry.set_password(&encrypted_b64)?;
+        Ok(())
+    }
+
+    fn retrieve(&self) -> Result<Option<String>, JiraError> {
+        match self.entry.get_password() {
+            Ok(encrypted_b64) => {
+                let encrypted_bytes = general_purpose::STANDARD.decode(encrypted_b64.trim())?;
+                if is_legacy_key(&encrypted_bytes) {
+                    return Err(JiraError::CredentialsUnrecoverable);
+                }
+                open_stored(&self.cipher_key, &encrypted_bytes).map(Some)
+            }
+            Err(keyring::Error::NoEntry) => Ok(None),
+            Err(e) => Err(JiraError::Keyring(e)),
+        }
+    }
+
+    fn delete(&self) -> Result<(), JiraError> {
+        self.entry.delete_password().map_err(JiraError::Keyring)
+    }
+
+    fn backend(&self) -> StorageBackend {
+        StorageBackend::NativeKeyring
+    }
+
+    fn delete_key(&self) -> Result<(), JiraError> {
+        match self.key_entry.delete_password() {
+            Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+            Err(e) => Err(JiraError::Keyring(e)),
+        }
+    }
+}
+
+// === ARQUIVO CRIPTOGRAFADO ===
+
+enum FileKey {
+    // Chave aleatória gravada ao lado dos dados, em `<slot>.key`
+    Stored(Key<Aes256Gcm>),
+    // `.key` ausente ou inválido com um `.enc` já gravado: ler falha com
+    // `CredentialsUnrecoverable`. Nenhuma chave é criada ao abrir o slot.
+    Missing,
+    // Chave derivada da senha mestra, obtida do cofre a cada operação
+    Passphrase,
+    // Chave fornecida pelo chamador (troca de senha mestra)
+    Explicit(Key<Aes256Gcm>),
+}
+
+pub struct EncryptedFileStore {
+    file_path: PathBuf,
+    key: FileKey,
+}
+
+impl EncryptedFileStore {
+    pub fn new(file_path: PathBuf) -> Result<Self, JiraError> {
+        let key_path = file_path.with_extension("key");
+        let key = match Self::load_cipher_key(&key_path) {
+            Ok(Some(key)) => FileKey::Stored(key),
+            Ok(None) if !file_path.exists() => FileKey::Stored(Self::create_cipher_key(&key_path)?),
+            Ok(None) | Err(JiraError::CredentialsUnrecoverable) => FileKey::Missing,
+            Err(e) => return Err(e),
+        };
+
+        Ok(Self { file_path, key })
+    }
+
+    // Arquivo protegido pela senha mestra; operações falham com `VaultLocked` se bloqueado
+    pub fn with_passphrase(file_path: PathBuf) -> Self {
+        Self {
+            file_path,
+            key: FileKey::Passphrase,
+        }
+    }
+
+    pub(crate) fn with_key(file_path: PathBuf, key: Key<Aes256Gcm>) -> Self {
+        Self {
+            file_path,
+            key: FileKey::Explicit(key),
+        }
+    }
+
+    fn cipher_key(&self) -> Result<Key<Aes256Gcm>, JiraError> {
+        match &self.key {
+            FileKey::Stored(key) | FileKey::Explicit(key) => Ok(*key),
+            // Outra instância pode ter gravado credenciais novas desde a abertura
+            FileKey::Missing => Self::load_cipher_key(&self.key_path())?
+                .ok_or(JiraError::CredentialsUnrecoverable),
+            FileKey::Passphrase => vault::current_key(),
+        }
+    }
+
+    fn key_path(&self) -> PathBuf {
+        self.file_path.with_extension("key")
+    }
+
+    // `Ok(None)` se o arquivo não existe. Um arquivo ilegível é um erro: gerar outra
+    // chave deixaria o `.enc` atual sem volta, como no keyring.
+    fn load_cipher_key(key_path: &Path) -> Result<Option<Key<Aes256Gcm>>, JiraError> {
+        match fs::read_to_string(key_path) {
+            Ok(key_data) => decode_key(&key_data)
+                .map(Some)
+                .ok_or(JiraError::CredentialsUnrecoverable),
+            Err(ref e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(JiraError::InvalidConfig(format!("Erro ao ler arquivo de chave: {}", e))),
+        }
+    }
+
+    // Criar `<slot>.key` (permissões 600); falha se já existir, nunca o sobrescreve
+    fn create_cipher_key(key_path: &Path) -> Result<Key<Aes256Gcm>, JiraError> {
+        let (key, key_b64) = generate_key();
+
+        if let Some(parent) = key_path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| JiraError::InvalidConfig(format!("Erro ao criar diretório: {}", e)))?;
+        }
+
+        let mut options = OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        options.mode(0o600);
+
+        let mut file = options.open(key_path)
+            .map_err(|e| JiraError::InvalidConfig(format!("Erro ao criar arquivo de chave: {}", e)))?;
+        file.write_all(key_b64.as_bytes())
+            .map_err(|e| JiraError::InvalidConfig(format!("Erro ao escrever arquivo de chave: {}", e)))?;
+
+        Ok(key)
+    }
+}
+
+impl CredentialStore for EncryptedFileStore {
+    fn store(&self, data: &str) -> Result<(), JiraError> {
+        let key = match self.key {
+            // Credenciais novas substituem o `.enc` ilegível; um `.key` inválido
+            // continua no lugar e a gravação falha
+            FileKey::Missing => match Self::load_cipher_key(&self.key_path())? {
+                Some(key) => key,
+                None => Self::create_cipher_key(&self.key_path())?,
+            },
+            _ => self.cipher_key()?,
+        };
+        let encrypted = encrypt_data(&key, data.as_bytes())?;
+        let encrypted_data = serde_json::to_vec(&encrypted)?;
+
+        // Criar diretório se não existir
+        if let Some(parent) = self.file_path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| JiraError::InvalidConfig(format!("Erro ao criar diretório: {}", e)))?;
+        }
+
+        // Escrever arquivo com permissões restritas
+        let mut file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(&self.file_path)
+            .map_err(|e| JiraError::InvalidConfig(format!("Erro ao criar arquivo: {}", e)))?;
+
+        file.write_all(&encrypted_data)
+            .map_err(|e| JiraError::InvalidConfig(format!("Erro ao escrever arquivo: {}", e)))?;
+
+        // Definir permissões 600 (apenas proprietário pode ler/escrever)
+        #[cfg(unix)]
+        {
+            let metadata = file.metadata()
+                .map_err(|e| JiraError::InvalidConfig(format!("Erro ao obter metadados: {}", e)))?;
+            let mut permissions = metadata.permissions();
+            permissions.set_mode(0o600);
+            file.set_permissions(permissions)
+                .map_err(|e| JiraError::InvalidConfig(format!("Erro ao definir permissões: {}", e)))?;
+        }
+
+        Ok(())
+    }
+
+    fn retrieve(&self) -> Result<Option<String>, JiraError> {
+        match fs::read(&self.file_path) {
+            Ok(encrypted_data) => open_stored(&self.cipher_key()?, &encrypted_data).map(Some),
+            Err(ref e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(JiraError::InvalidConfig(format!("Erro ao ler arquivo: {}", e))),
+        }
+    }
+
+    fn delete(&self) -> Result<(), JiraError> {
+        if self.file_path.exists() {
+            fs::remove_file(&self.file_path)
+                .map_err(|e| JiraError::InvalidConfig(format!("Erro ao deletar arquivo: {}", e)))?;
+        }
+        // Sem os dados, um `.key` inválido só impediria gravar credenciais novas
+        if matches!(self.key, FileKey::Missing)
+            && matches!(Self::load_cipher_key(&self.key_path()), Err(JiraError::CredentialsUnrecoverable))
+        {
+            self.delete_key()?;
+        }
+        Ok(())
+    }
+
+    fn backend(&self) -> StorageBackend {
+        match self.key {
+            FileKey::Stored(_) | FileKey::Missing => StorageBackend::EncryptedFile,
+            FileKey::Passphrase | FileKey::Explicit(_) => StorageBackend::PassphraseFile,
+        }
+    }
+
+    fn delete_key(&self) -> Result<(), JiraError> {
+        let key_path = self.file_path.with_extension("key");
+        if key_path.exists() {
+            fs::remove_file(&key_path)
+                .map_err(|e| JiraError::InvalidConfig(format!("Erro ao deletar arquivo de chave: {}", e)))?;
+        }
+        Ok(())
+    }
+
+    fn secure_delete(&self) -> Result<(), JiraError> {
+        let key_path = self.file_path.with_extension("key");
+        if matches!(self.key, FileKey::Stored(_) | FileKey::Missing) && key_path.exists() {
+            overwrite_and_remove(&key_path)?;
+        }
+        if self.file_path.exists() {
+            overwrite_and_remove(&self.file_path)?;
+        }
+        Ok(())
+    }
+}
+
+// === MEMÓRIA ===
+
+// Nada é persistido: os dados somem com o processo. Útil em testes e em ambientes
+// sem keyring nem diretório de configuração gravável.
+#[derive(Default)]
+pub struct InMemoryStore {
+    data: Mutex<Option<String>>,
+}
+
+impl InMemoryStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl CredentialStore for InMemoryStore {
+    fn store(&self, data: &str) -> Result<(), JiraError> {
+        *self.data.lock().unwrap_or_else(|e| e.into_inner()) = Some(data.to_string());
+        Ok(())
+    }
+
+    fn retrieve(&self) -> Result<Option<String>, JiraError> {
+        Ok(self.data.lock().unwrap_or_else(|e| e.into_inner()).clone())
+    }
+
+    fn delete(&self) -> Result<(), JiraError> {
+        *self.data.lock().unwrap_or_else(|e| e.into_inner()) = None;
+        Ok(())
+    }
+
+    fn backend(&self) -> StorageBackend {
+        StorageBackend::InMemory
+    }
+}
+
+// Entrada do keyring com a chave AES de um slot. O ':' não aparece nos slots dos
+// perfis (slugs só têm letras, números e '-'), então a chave de um slot nunca coincide
+// com o slot de outro perfil, ex.: o perfil "Key" (jira_config_key)
+pub(crate) fn key_entry_name(username: &str) -> String {
+    format!("{}:key", username)
+}
+
+// === FUNÇÕES PRIVADAS ===
+
+// Sobrescrever o conteúdo com bytes aleatórios antes de remover, para que o texto
+// cifrado e a chave não fiquem nos blocos liberados do disco
+fn overwrite_and_remove(path: &PathBuf) -> Result<(), JiraError> {
+    let len = fs::metadata(path)
+        .map_err(|e| JiraError::InvalidConfig(format!("Erro ao obter metadados: {}", e)))?
+        .len() as usize;
+
+    let mut noise = vec![0u8; len];
+    OsRng.fill_bytes(&mut noise);
+
+    let mut file = OpenOptions::new().write(true).open(path)
+        .map_err(|e| JiraError::InvalidConfig(format!("Erro ao abrir arquivo: {}", e)))?;
+    file.write_all(&noise)
+        .and_then(|_| file.sync_all())
+        .map_err(|e| JiraError::InvalidConfig(format!("Erro ao sobrescrever arquivo: {}", e)))?;
+    drop(file);
+
+    fs::remove_file(path)
+        .map_err(|e| JiraError::InvalidConfig(format!("Erro ao deletar arquivo: {}", e)))
+}
+
+fn generate_key() -> (Key<Aes256Gcm>, String) {
+    let mut key_bytes = [0u8; 32];
+    OsRng.fill_bytes(&mut key_bytes);
+    (*Key::<Aes256Gcm>::from_slice(&key_bytes), general_purpose::STANDARD.encode(key_bytes))
+}
+
+fn decode_key(key_data: &str) -> Option<Key<Aes256Gcm>> {
+    let key_bytes = general_purpose::STANDARD.decode(key_data.trim()).ok()?;
+    (key_bytes.len() == 32).then(|| *Key::<Aes256Gcm>::from_slice(&key_bytes))
+}
+
+// Instalações antigas gravavam a chave AES na mesma entrada do keyring, por cima dos
+// dados: a entrada contém só os 32 bytes da chave e não há como recuperar as credenciais
+fn is_legacy_key(stored: &[u8]) -> bool {
+    stored.len() == 32
+}
+
+fn open_stored(key: &Key<Aes256Gcm>, stored: &[u8]) -> Result<String, JiraError> {
+    let encrypted: EncryptedData = serde_json::from_slice(stored)?;
+    if !is_well_formed(&encrypted) {
+        return Err(JiraError::Decryption);
+    }
+
+    // Dados íntegros que não autenticam foram cifrados com outra chave: a chave do
+    // slot se perdeu ou foi recriada, e as credenciais não podem ser recuperadas
+    let decrypted = decrypt_data(key, &encrypted)
+        .map_err(|_| JiraError::CredentialsUnrecoverable)?;
+    Ok(String::from_utf8_lossy(&decrypted).to_string())
+}
+
+// Nonce de 12 bytes e texto cifrado com pelo menos a tag de autenticação (16 bytes)
+fn is_well_formed(encrypted: &EncryptedData) -> bool {
+    encrypted.nonce.len() == 12 && encrypted.ciphertext.len() >= 16
+}
+
+fn encrypt_data(key: &Key<Aes256Gcm>, plaintext: &[u8]) -> Result<EncryptedData, JiraError> {
+    let cipher = Aes256Gcm::new(key);
+
+    let mut nonce_bytes = [0u8; 12];
+    OsRng.fill_bytes(&mut nonce_bytes);
+    let nonce = Nonce::from_slice(&nonce_bytes);
+
+    let ciphertext = cipher.encrypt(nonce, plaintext)
+        .map_err(|_| JiraError::Encryption)?;
+
+    Ok(EncryptedData {
+        nonce: nonce_bytes.to_vec(),
+        ciphertext,
+    })
+}
+
+fn decrypt_data(key: &Key<Aes256Gcm>, encrypted: &EncryptedData) -> Result<Vec<u8>, JiraError> {
+    // from_slice entra em pânico com tamanhos diferentes de 12 bytes
+    if encrypted.nonce.len() != 12 {
+        return Err(JiraError::Decryption);
+    }
+
+    let cipher = Aes256Gcm::new(key);
+    let nonce = Nonce::from_slice(&encrypted.nonce);
+
+    cipher.decrypt(nonce, encrypted.ciphertext.as_slice())
+        .map_err(|_| JiraError::Decryption)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn in_memory_store_round_trip() {
+        let store = InMemoryStore::new();
+        assert_eq!(store.retrieve().unwrap(), None);
+
+        store.store("primeiro").unwrap();
+        store.store("segundo").unwrap();
+        assert_eq!(store.retrieve().unwrap().as_deref(), Some("segundo"));
+        assert_eq!(store.backend(), StorageBackend::InMemory);
+
+        store.secure_delete().unwrap();
+        assert_eq!(store.retrieve().unwrap(), None);
+    }
+
+    #[test]
+    fn decode_key_accepts_only_32_byte_keys() {
+        let (key, key_b64) = generate_key();
+        assert_eq!(decode_key(&key_b64), Some(key));
+        // Arquivos .key editados à mão podem terminar com quebra de linha
+        assert_eq!(decode_key(&format!("{}\n", key_b64)), Some(key));
+
+        assert_eq!(decode_key(&general_purpose::STANDARD.encode([7u8; 16])), None);
+        assert_eq!(decode_key("não é base64"), None);
+        assert_eq!(decode_key(""), None);
+    }
+
+    #[test]
+    fn open_stored_reports_each_failure() {
+        let (key, _) = generate_key();
+        let (other_key, _) = generate_key();
+
+        let sealed = serde_json::to_vec(&encrypt_data(&key, b"segredo").unwrap()).unwrap();
+        assert_eq!(open_stored(&key, &sealed).unwrap(), "segredo");
+
+        // Chave perdida ou recriada: dados íntegros que não abrem
+        assert!(matches!(open_stored(&other_key, &sealed), Err(JiraError::CredentialsUnrecoverable)));
+        assert!(matches!(open_stored(&key, b"{corrompido"), Err(JiraError::Serialization(_))));
+
+        let bad_nonce = serde_json::to_vec(&EncryptedData { nonce: vec![0; 3], ciphertext: vec![1; 32] }).unwrap();
+        assert!(matches!(open_stored(&key, &bad_nonce), Err(JiraError::Decryption)));
+
+        let truncated = serde_json::to_vec(&EncryptedData { nonce: vec![0; 12], ciphertext: vec![1, 2, 3] }).unwrap();
+        assert!(matches!(open_stored(&key, &truncated), Err(JiraError::Decryption)));
+    }
+
+    #[test]
+    fn only_a_bare_key_is_legacy() {
+        let (_, key_b64) = generate_key();
+        let legacy = general_purpose::STANDARD.decode(key_b64).unwrap();
+        assert!(is_legacy_key(&legacy));
+
+        let (key, _) = generate_key();
+        let sealed = serde_json::to_vec(&encrypt_data(&key, b"segredo").unwrap()).unwrap();
+        assert!(!is_legacy_key(&sealed));
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("goji-test-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn file_store_creates_a_key_only_for_a_new_slot() {
+        let dir = temp_dir("file-store-new");
+        let path = dir.join("slot.enc");
+
+        let store = EncryptedFileStore::new(path.clone()).unwrap();
+        assert!(dir.join("slot.key").exists());
+        store.store("segredo").unwrap();
+
+        let reopened = EncryptedFileStore::new(path).unwrap();
+        assert_eq!(reopened.retrieve().unwrap().as_deref(), Some("segredo"));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn file_store_keeps_data_without_a_usable_key() {
+        let dir = temp_dir("file-store-lost");
+        let path = dir.join("slot.enc");
+        let key_path = dir.join("slot.key");
+        EncryptedFileStore::new(path.clone()).unwrap().store("segredo").unwrap();
+
+        // Chave ilegível: o arquivo não é truncado nem substituído
+        fs::write(&key_path, "corrompida").unwrap();
+        let store = EncryptedFileStore::new(path.clone()).unwrap();
+        assert!(matches!(store.retrieve(), Err(JiraError::CredentialsUnrecoverable)));
+        assert!(matches!(store.store("outro"), Err(JiraError::CredentialsUnrecoverable)));
+        assert_eq!(fs::read_to_string(&key_path).unwrap(), "corrompida");
+
+        // Chave removida: nenhuma outra é criada ao abrir
+        fs::remove_file(&key_path).unwrap();
+        let store = EncryptedFileStore::new(path.clone()).unwrap();
+        assert!(!key_path.exists());
+        assert!(matches!(store.retrieve(), Err(JiraError::CredentialsUnrecoverable)));
+
+        // Informar as credenciais de novo substitui os dados ilegíveis
+        store.store("novo").unwrap();
+        assert_eq!(store.retrieve().unwrap().as_deref(), Some("novo"));
+        assert_eq!(EncryptedFileStore::new(path).unwrap().retrieve().unwrap().as_deref(), Some("novo"));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
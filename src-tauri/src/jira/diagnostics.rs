@@ -0,0 +1,597 @@
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+#[cfg(unix)]
+use std::os::unix::fs::PermissionsExt;
+use serde::{Serialize, Deserialize};
+use ts_rs::TS;
+use chrono::{DateTime, Utc};
+use crate::jira::error::JiraError;
+use crate::jira::paths::goji_config_dir;
+use crate::jira::environment::{EnvironmentInfo, StorageBackend};
+use crate::jira::config_manager::{JiraConfig, JiraConfigManager};
+use crate::jira::auth::JiraAuth;
+use crate::jira::profiles::{ProfileManager, JiraProfile, DEFAULT_PROFILE_ID};
+use crate::jira::secure_storage::SecureStorage;
+use crate::jira::credential_store::CredentialStore;
+use crate::jira::vault;
+
+const CHECK_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub enum DiagnosticsFormat {
+    Json,
+    Markdown,
+}
+
+// Relatório para anexar a chamados; nunca contém tokens. Perfis aparecem como
+// "perfil-N", o host do Jira é mascarado e caminhos do usuário começam em "~".
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export)]
+pub struct DiagnosticsReport {
+    #[serde(rename = "generatedAt")]
+    #[ts(type = "string")]
+    pub generated_at: DateTime<Utc>,
+    #[serde(rename = "appVersion")]
+    pub app_version: String,
+    pub platform: String,
+    pub environment: EnvironmentInfo,
+    pub security: SecuritySummary,
+    pub paths: Vec<PathCheck>,
+    pub storage: Vec<SlotHealth>,
+    pub connectivity: Option<ConnectivityReport>,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export)]
+pub struct SecuritySummary {
+    pub description: String,
+    pub suggestions: Vec<String>,
+    #[serde(rename = "showWarning")]
+    pub show_warning: bool,
+    #[serde(rename = "vaultEnabled")]
+    pub vault_enabled: bool,
+    #[serde(rename = "vaultUnlocked")]
+    pub vault_unlocked: bool,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export)]
+pub struct PathCheck {
+    pub label: String,
+    pub path: String,
+    pub exists: bool,
+    // Permissões em octal (ex.: "600"), apenas em Unix
+    pub mode: Option<String>,
+    #[serde(rename = "ownerOnly")]
+    pub owner_only: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export)]
+pub struct SlotHealth {
+    pub profile: String,
+    pub slot: String,
+    pub backend: String,
+    // "ok", "empty" ou o código do erro (ex.: "vault_locked")
+    pub status: String,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export)]
+pub struct CheckResult {
+    pub ok: bool,
+    pub detail: String,
+    #[serde(rename = "elapsedMs")]
+    #[ts(type = "number")]
+    pub elapsed_ms: u64,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export)]
+pub struct ConnectivityReport {
+    pub host: String,
+    #[serde(rename = "authMode")]
+    pub auth_mode: String,
+    #[serde(rename = "apiVersion")]
+    pub api_version: String,
+    pub dns: CheckResult,
+    pub tls: CheckResult,
+    pub auth: CheckResult,
+    // Versão e tipo de implantação informados por /serverInfo
+    #[serde(rename = "serverInfo")]
+    pub server_info: Option<String>,
+}
+
+impl DiagnosticsReport {
+    pub async fn collect() -> Result<Self, JiraError> {
+        let mut environment = EnvironmentInfo::detect();
+        // A mensagem crua do keyring pode trazer caminhos e nomes da sessão; o código basta
+        environment.keyring_probe.message = None;
+        let vault_status = vault::status();
+        let profiles = ProfileManager::load()?;
+
+        let security = SecuritySummary {
+            description: environment.get_security_description(),
+            suggestions: environment.get_improvement_suggestions(),
+            show_warning: environment.should_show_security_warning(),
+            vault_enabled: vault_status.enabled,
+            vault_unlocked: vault_status.unlocked,
+        };
+
+        let profile_list = profiles.list().profiles;
+        let paths = Self::check_paths(&profile_list)?;
+        let storage = Self::check_storage(environment.storage_backend, &profile_list);
+        let connectivity = Self::check_connectivity(profiles.active_id()).await;
+
+        Ok(Self {
+            generated_at: Utc::now(),
+            app_version: env!("CARGO_PKG_VERSION").to_string(),
+            platform: format!("{} {}", std::env::consts::OS, std::env::consts::ARCH),
+            environment,
+            security,
+            paths,
+            storage,
+            connectivity,
+        })
+    }
+
+    pub fn render(&self, format: DiagnosticsFormat) -> Result<String, JiraError> {
+        match format {
+            DiagnosticsFormat::Json => Ok(serde_json::to_string_pretty(self)?),
+            DiagnosticsFormat::Markdown => Ok(self.to_markdown()),
+        }
+    }
+
+    // === MÉTODOS PRIVADOS ===
+
+    fn check_paths(profiles: &[JiraProfile]) -> Result<Vec<PathCheck>, JiraError> {
+        let config_dir = goji_config_dir()?;
+
+        let mut checks = vec![
+            path_check("Diretório de configuração", &config_dir),
+            path_check("Perfis", &config_dir.join("profiles.json")),
+            path_check("Preferência de armazenamento", &config_dir.join("storage.json")),
+            path_check("Cofre (senha mestra)", &config_dir.join("vault.json")),
+            path_check("Cache local", &config_dir.join("cache.sqlite3")),
+        ];
+
+        let mut profile_files = Vec::new();
+        for (index, profile) in profiles.iter().enumerate() {
+            let alias = profile_alias(index, profile);
+            for (slot, shown_slot) in profile_slots(&profile.id, &alias) {
+                let file = SecureStorage::get_secure_file_path(&slot)?;
+                let shown_file = SecureStorage::get_secure_file_path(&shown_slot)?;
+                profile_files.push(path_check_as(&shown_slot, &file, &shown_file));
+                profile_files.push(path_check_as(
+                    &format!("{} chave", shown_slot),
+                    &file.with_extension("key"),
+                    &shown_file.with_extension("key"),
+                ));
+            }
+
+            let per_profile = [
+                ("Timers", JiraProfile::timers_file(&profile.id), JiraProfile::timers_file(&alias)),
+                ("Sincronização", JiraProfile::sync_settings_file(&profile.id), JiraProfile::sync_settings_file(&alias)),
+            ];
+            for (label, file, shown_file) in per_profile {
+                profile_files.push(path_check_as(
+                    &format!("{} ({})", label, alias),
+                    &config_dir.join(file),
+                    &config_dir.join(shown_file),
+                ));
+            }
+        }
+
+        // Arquivos ausentes de slots e perfis não usados são esperados; só poluiriam o relatório
+        checks.extend(profile_files.into_iter().filter(|check| check.exists));
+        Ok(checks)
+    }
+
+    // Somente leitura: slots vazios não são abertos, para não criar chaves novas
+    fn check_storage(backend: StorageBackend, profiles: &[JiraProfile]) -> Vec<SlotHealth> {
+        let mut health = Vec::new();
+
+        for (index, profile) in profiles.iter().enumerate() {
+            let alias = profile_alias(index, profile);
+            for (slot, shown_slot) in profile_slots(&profile.id, &alias) {
+                let status = match SecureStorage::open_existing(backend, "goji", &slot) {
+                    Ok(Some(storage)) => match storage.retrieve() {
+                        Ok(Some(_)) => "ok".to_string(),
+                        Ok(None) => "empty".to_string(),
+                        Err(e) => e.code().to_string(),
+                    },
+                    Ok(None) => "empty".to_string(),
+                    Err(e) => e.code().to_string(),
+                };
+
+                health.push(SlotHealth {
+                    profile: alias.clone(),
+                    slot: shown_slot,
+                    backend: format!("{:?}", backend),
+                    status,
+                });
+            }
+        }
+
+        health
+    }
+
+    async fn check_connectivity(profile_id: &str) -> Option<ConnectivityReport> {
+        let config = JiraConfigManager::for_profile(profile_id).ok()?.get_config().ok()??;
+        let url = reqwest::Url::parse(&config.api_base()).ok()?;
+        let host = url.host_str()?.to_string();
+        let port = url.port_or_known_default().unwrap_or(443);
+
+        let dns = timed(async {
+            let addrs: Vec<_> = tokio::net::lookup_host((host.as_str(), port)).await
+                .map_err(|e| e.to_string())?
+                .collect();
+            Ok(format!("{} endereço(s)", addrs.len()))
+        }).await;
+
+        let http_client = reqwest::Client::builder()
+            .timeout(CHECK_TIMEOUT)
+            .build()
+            .ok()?;
+
+        // /serverInfo não exige autenticação: valida TCP + TLS + HTTP de uma vez
+        let mut server_info = None;
+        let tls = timed(async {
+            let response = http_client.get(config.api_endpoint(&["serverInfo"]).map_err(|e| e.to_string())?)
+                .header("Accept", "application/json")
+                .send()
+                .await
+                .map_err(describe_http_error)?;
+
+            let status = response.status();
+            if let Ok(info) = response.json::<serde_json::Value>().await {
+                server_info = Some(format!(
+                    "{} {}",
+                    info["deploymentType"].as_str().unwrap_or("?"),
+                    info["version"].as_str().unwrap_or("?"),
+                ));
+            }
+            Ok(format!("HTTP {}", status.as_u16()))
+        }).await;
+
+        let auth = timed(Self::check_auth(&http_client, profile_id, &config)).await;
+
+        Some(ConnectivityReport {
+            host: redact_host(&host),
+            auth_mode: format!("{:?}", config.auth_mode),
+            api_version: format!("{:?}", config.api_version()),
+            dns,
+            tls,
+            auth,
+            server_info,
+        })
+    }
+
+    async fn check_auth(http_client: &reqwest::Client, profile_id: &str, config: &JiraConfig) -> Result<String, String> {
+        // Renova o token OAuth se necessário, como nos comandos normais
+        let auth_header = JiraAuth::for_profile(profile_id)
+            .map_err(|e| e.code().to_string())?
+            .get_auth_header()
+            .await
+            .map_err(|e| e.code().to_string())?
+            .ok_or_else(|| "sem credenciais".to_string())?;
+
+        let response = http_client.get(config.api_endpoint(&["myself"]).map_err(|e| e.to_string())?)
+            .header("Authorization", auth_header)
+            .header("Accept", "application/json")
+            .send()
+            .await
+            .map_err(describe_http_error)?;
+
+        match response.status().as_u16() {
+            200 => Ok("autenticado".to_string()),
+            status => Err(format!("HTTP {}", status)),
+        }
+    }
+
+    fn to_markdown(&self) -> String {
+        let env = &self.environment;
+        let mut md = String::new();
+
+        let _ = writeln!(md, "# Diagnóstico do Goji\n");
+        let _ = writeln!(md, "- Gerado em: {}", self.generated_at.to_rfc3339());
+        let _ = writeln!(md, "- Versão: {}", self.app_version);
+        let _ = writeln!(md, "- Plataforma: {}\n", self.platform);
+
+        let _ = writeln!(md, "## Ambiente\n");
+        let _ = writeln!(md, "| Item | Valor |\n|---|---|");
+        let _ = writeln!(md, "| WSL | {} |", if env.is_wsl2 { "WSL2" } else if env.is_wsl { "WSL1" } else { "não" });
+        let _ = writeln!(md, "| Container | {} |", env.container.map(|c| format!("{:?}", c)).unwrap_or_else(|| "não".to_string()));
+        let _ = writeln!(md, "| Sandbox | {} |", env.sandbox.map(|s| format!("{:?}", s)).unwrap_or_else(|| "não".to_string()));
+        let _ = writeln!(md, "| Acesso a segredos no sandbox | {} |", yes_no(env.has_secret_portal));
+        let _ = writeln!(md, "| Sessão SSH | {} |", yes_no(env.is_ssh_session));
+        let _ = writeln!(md, "| Headless | {} |", yes_no(env.is_headless));
+        let _ = writeln!(md, "| Desktop | {} |", yes_no(env.has_desktop_environment));
+        let _ = writeln!(
+            md,
+            "| Keyring | {} ({} ms){} |",
+            if env.has_keyring { "disponível" } else { "indisponível" },
+            env.keyring_probe.elapsed_ms,
+            env.keyring_probe.failure.map(|f| format!(" — {:?}", f)).unwrap_or_default(),
+        );
+        let _ = writeln!(md, "| Preferência | {:?} |", env.storage_preference);
+        let _ = writeln!(md, "| Backend | {:?} |", env.storage_backend);
+        let _ = writeln!(md, "| Nível de segurança | {:?} |\n", env.security_level);
+
+        let _ = writeln!(md, "## Segurança\n");
+        let _ = writeln!(md, "{}\n", self.security.description);
+        let _ = writeln!(
+            md,
+            "- Senha mestra: {}",
+            match (self.security.vault_enabled, self.security.vault_unlocked) {
+                (false, _) => "desativada",
+                (true, true) => "ativada (desbloqueada)",
+                (true, false) => "ativada (bloqueada)",
+            },
+        );
+        for suggestion in &self.security.suggestions {
+            let _ = writeln!(md, "- {}", suggestion);
+        }
+
+        let _ = writeln!(md, "\n## Arquivos\n");
+        let _ = writeln!(md, "| Item | Caminho | Existe | Permissões |\n|---|---|---|---|");
+        for check in &self.paths {
+            let _ = writeln!(
+                md,
+                "| {} | `{}` | {} | {} |",
+                check.label,
+                check.path,
+                yes_no(check.exists),
+                check.mode.as_deref().unwrap_or("-"),
+            );
+        }
+
+        let _ = writeln!(md, "\n## Armazenamento\n");
+        let _ = writeln!(md, "| Perfil | Slot | Backend | Estado |\n|---|---|---|---|");
+        for slot in &self.storage {
+            let _ = writeln!(md, "| {} | {} | {} | {} |", slot.profile, slot.slot, slot.backend, slot.status);
+        }
+
+        let _ = writeln!(md, "\n## Conectividade\n");
+        match &self.connectivity {
+            Some(conn) => {
+                let _ = writeln!(md, "- Host: {}", conn.host);
+                let _ = writeln!(md, "- Autenticação: {} (API {})", conn.auth_mode, conn.api_version);
+                if let Some(info) = &conn.server_info {
+                    let _ = writeln!(md, "- Servidor: {}", info);
+                }
+                for (name, check) in [("DNS", &conn.dns), ("TLS/HTTP", &conn.tls), ("Autenticação", &conn.auth)] {
+                    let _ = writeln!(
+                        md,
+                        "- {}: {} — {} ({} ms)",
+                        name,
+                        if check.ok { "ok" } else { "falhou" },
+                        check.detail,
+                        check.elapsed_ms,
+                    );
+                }
+            }
+            None => {
+                let _ = writeln!(md, "Nenhuma configuração do JIRA acessível no perfil ativo.");
+            }
+        }
+
+        md
+    }
+}
+
+// === FUNÇÕES PRIVADAS ===
+
+async fn timed<F>(check: F) -> CheckResult
+where
+    F: std::future::Future<Output = Result<String, String>>,
+{
+    let started = Instant::now();
+    let result = tokio::time::timeout(CHECK_TIMEOUT, check).await
+        .unwrap_or_else(|_| Err(format!("sem resposta em {}s", CHECK_TIMEOUT.as_secs())));
+
+    let (ok, detail) = match result {
+        Ok(detail) => (true, detail),
+        Err(detail) => (false, detail),
+    };
+
+    CheckResult {
+        ok,
+        detail,
+        elapsed_ms: started.elapsed().as_millis() as u64,
+    }
+}
+
+fn describe_http_error(error: reqwest::Error) -> String {
+    // O Display inclui "for url (https://<host>/...)", que revelaria o host mascarado
+    let error = error.without_url();
+    if error.is_timeout() {
+        "tempo esgotado".to_string()
+    } else if error.is_connect() {
+        // Inclui falhas de handshake TLS e certificados inválidos
+        format!("falha de conexão/TLS: {}", error)
+    } else {
+        error.to_string()
+    }
+}
+
+fn path_check(label: &str, path: &Path) -> PathCheck {
+    path_check_as(label, path, path)
+}
+
+// Verifica `path`, mas exibe `shown_path` (caminho com o nome do perfil mascarado)
+fn path_check_as(label: &str, path: &Path, shown_path: &Path) -> PathCheck {
+    let metadata = fs::metadata(path).ok();
+
+    #[cfg(unix)]
+    let mode = metadata.as_ref().map(|m| m.permissions().mode() & 0o777);
+    #[cfg(not(unix))]
+    let mode: Option<u32> = None;
+
+    PathCheck {
+        label: label.to_string(),
+        path: redact_home(shown_path),
+        exists: metadata.is_some(),
+        mode: mode.map(|m| format!("{:o}", m)),
+        // Diretórios precisam de x para o dono; basta não haver bits para grupo/outros
+        owner_only: mode.map(|m| m & 0o077 == 0),
+    }
+}
+
+// Substituir o diretório do usuário por "~" para não expor o nome de login
+fn redact_home(path: &Path) -> String {
+    let home: Option<PathBuf> = dirs::home_dir();
+    match home.as_deref().and_then(|home| path.strip_prefix(home).ok()) {
+        Some(relative) => format!("~/{}", relative.display()),
+        None => path.display().to_string(),
+    }
+}
+
+// Nomes e ids de perfil costumam ser nomes de clientes; o perfil padrão não diz nada
+fn profile_alias(index: usize, profile: &JiraProfile) -> String {
+    if profile.id == DEFAULT_PROFILE_ID {
+        DEFAULT_PROFILE_ID.to_string()
+    } else {
+        format!("perfil-{}", index + 1)
+    }
+}
+
+// Slots reais do perfil, acompanhados dos nomes exibidos com o alias
+fn profile_slots(id: &str, alias: &str) -> [(String, String); 2] {
+    [
+        (JiraProfile::config_slot(id), JiraProfile::config_slot(alias)),
+        (JiraProfile::oauth_slot(id), JiraProfile::oauth_slot(alias)),
+    ]
+}
+
+// O Jira Cloud só revela o provedor; hosts próprios (Server/Data Center) e IPs
+// identificam a empresa e somem por inteiro
+fn redact_host(host: &str) -> String {
+    match host.strip_suffix(".atlassian.net") {
+        Some(_) => "***.atlassian.net".to_string(),
+        None => "***".to_string(),
+    }
+}
+
+fn yes_no(value: bool) -> &'static str {
+    if value { "sim" } else { "não" }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile(id: &str) -> JiraProfile {
+        JiraProfile {
+            id: id.to_string(),
+            name: id.to_string(),
+            created_at: Utc::now(),
+        }
+    }
+
+    fn report(connectivity: ConnectivityReport) -> DiagnosticsReport {
+        let environment = EnvironmentInfo {
+            is_wsl: false,
+            is_wsl2: false,
+            has_keyring: false,
+            keyring_probe: crate::jira::keyring_probe::KeyringProbe {
+                available: false,
+                locked: false,
+                failure: None,
+                message: None,
+                elapsed_ms: 0,
+            },
+            has_desktop_environment: false,
+            container: None,
+            sandbox: None,
+            has_secret_portal: false,
+            is_ssh_session: false,
+            is_headless: true,
+            storage_preference: crate::jira::environment::StoragePreference::Auto,
+            storage_backend: StorageBackend::EncryptedFile,
+            security_level: crate::jira::environment::SecurityLevel::Medium,
+        };
+
+        DiagnosticsReport {
+            generated_at: Utc::now(),
+            app_version: "0.0.0".to_string(),
+            platform: "linux x86_64".to_string(),
+            security: SecuritySummary {
+                description: environment.get_security_description(),
+                suggestions: Vec::new(),
+                show_warning: false,
+                vault_enabled: false,
+                vault_unlocked: false,
+            },
+            environment,
+            paths: Vec::new(),
+            storage: Vec::new(),
+            connectivity: Some(connectivity),
+        }
+    }
+
+    #[test]
+    fn report_never_contains_the_jira_host() {
+        let host = "jira.acme-interno.com.br";
+        let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+
+        // Conexão recusada: o erro do reqwest traz a URL completa
+        let error = runtime.block_on(async {
+            reqwest::Client::builder()
+                .resolve(host, "127.0.0.1:1".parse().unwrap())
+                .build()
+                .unwrap()
+                .get(format!("https://{}:1/rest/api/2/serverInfo", host))
+                .send()
+                .await
+                .unwrap_err()
+        });
+        assert!(error.to_string().contains(host));
+
+        let failed = |detail: String| CheckResult { ok: false, detail, elapsed_ms: 1 };
+        let report = report(ConnectivityReport {
+            host: redact_host(host),
+            auth_mode: "DataCenterPat".to_string(),
+            api_version: "V2".to_string(),
+            dns: failed("1 endereço(s)".to_string()),
+            tls: failed(describe_http_error(error)),
+            auth: failed("HTTP 401".to_string()),
+            server_info: None,
+        });
+
+        for format in [DiagnosticsFormat::Json, DiagnosticsFormat::Markdown] {
+            let rendered = report.render(format).unwrap();
+            assert!(!rendered.contains(host), "{}", rendered);
+            assert!(!rendered.contains("acme"), "{}", rendered);
+        }
+    }
+
+    #[test]
+    fn redact_host_keeps_only_the_cloud_provider() {
+        assert_eq!(redact_host("acme.atlassian.net"), "***.atlassian.net");
+        assert_eq!(redact_host("jira.acme.com.br"), "***");
+        assert_eq!(redact_host("10.0.0.5"), "***");
+    }
+
+    #[test]
+    fn profile_slots_hide_the_profile_id() {
+        let alias = profile_alias(1, &profile("cliente-acme"));
+        assert_eq!(alias, "perfil-2");
+
+        for (slot, shown_slot) in profile_slots("cliente-acme", &alias) {
+            assert!(slot.contains("cliente-acme"));
+            assert!(!shown_slot.contains("cliente-acme"));
+        }
+    }
+
+    #[test]
+    fn default_profile_keeps_its_slot_names() {
+        let alias = profile_alias(0, &profile(DEFAULT_PROFILE_ID));
+        for (slot, shown_slot) in profile_slots(DEFAULT_PROFILE_ID, &alias) {
+            assert_eq!(slot, shown_slot);
+        }
+    }
+}
@@ -1,28 +1,139 @@
 use std::fs;
 use std::env;
+use std::path::PathBuf;
 use std::process::Command;
 use serde::{Serialize, Deserialize};
+use ts_rs::TS;
+use crate::jira::error::JiraError;
+use crate::jira::paths::goji_config_dir;
+use crate::jira::vault;
+use crate::jira::keyring_probe::{self, KeyringProbe};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
 pub enum StorageBackend {
     NativeKeyring,
     EncryptedFile,
+    PassphraseFile, // Arquivo criptografado com chave derivada da senha mestra
     InMemory, // Para testes
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+// Backend escolhido pelo usuário; `Auto` segue a detecção de ambiente
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub enum StoragePreference {
+    #[default]
+    Auto,
+    NativeKeyring,
+    EncryptedFile,
+    PassphraseFile,
+}
+
+#[derive(Serialize, Deserialize)]
+struct StorageSettings {
+    preference: StoragePreference,
+}
+
+impl StoragePreference {
+    // Preferência persistida; arquivo ausente ou inválido equivale a `Auto`
+    pub fn load() -> Self {
+        Self::get_settings_file_path()
+            .ok()
+            .and_then(|path| fs::read(path).ok())
+            .and_then(|data| serde_json::from_slice::<StorageSettings>(&data).ok())
+            .map(|settings| settings.preference)
+            .unwrap_or_default()
+    }
+
+    // Validar que o backend pode ser usado neste ambiente antes de persistir
+    pub fn save(self) -> Result<(), JiraError> {
+        self.validate()?;
+
+        let path = Self::get_settings_file_path()?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| JiraError::InvalidConfig(format!("Erro ao criar diretório: {}", e)))?;
+        }
+
+        let data = serde_json::to_vec_pretty(&StorageSettings { preference: self })?;
+        fs::write(&path, data)
+            .map_err(|e| JiraError::InvalidConfig(format!("Erro ao salvar preferência de armazenamento: {}", e)))
+    }
+
+    // === MÉTODOS PRIVADOS ===
+
+    fn validate(self) -> Result<(), JiraError> {
+        match self {
+            StoragePreference::Auto => Ok(()),
+            StoragePreference::NativeKeyring => {
+                let probe = keyring_probe::refresh_keyring_probe();
+                if probe.available {
+                    Ok(())
+                } else {
+                    Err(JiraError::InvalidConfig(format!(
+                        "Keyring do sistema indisponível: {}",
+                        probe.message.unwrap_or_default(),
+                    )))
+                }
+            }
+            // Com a senha mestra ativa os arquivos .enc são cifrados com a chave do cofre
+            StoragePreference::EncryptedFile if vault::is_enabled() => Err(JiraError::InvalidConfig(
+                "Arquivo criptografado sem senha mestra não pode ser usado enquanto a senha mestra está ativa".to_string(),
+            )),
+            StoragePreference::EncryptedFile => Ok(()),
+            StoragePreference::PassphraseFile if !vault::is_enabled() => Err(JiraError::InvalidConfig(
+                "Ative a senha mestra antes de escolher este armazenamento".to_string(),
+            )),
+            StoragePreference::PassphraseFile => Ok(()),
+        }
+    }
+
+    fn get_settings_file_path() -> Result<PathBuf, JiraError> {
+        Ok(goji_config_dir()?.join("storage.json"))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub enum ContainerRuntime {
+    Docker,
+    Podman,
+    Kubernetes,
+    Other, // LXC, systemd-nspawn e afins (variável `container`)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub enum Sandbox {
+    Flatpak,
+    Snap,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
 pub struct EnvironmentInfo {
     pub is_wsl: bool,
     pub is_wsl2: bool,
     pub has_keyring: bool,
+    // Resultado da sondagem que definiu `has_keyring`, com o motivo da falha
+    pub keyring_probe: KeyringProbe,
     pub has_desktop_environment: bool,
+    pub container: Option<ContainerRuntime>,
+    pub sandbox: Option<Sandbox>,
+    // Flatpak/Snap: permissão de acesso ao Secret Service (talk-name ou interface conectada)
+    pub has_secret_portal: bool,
+    pub is_ssh_session: bool,
+    // Linux sem desktop nem WSL: servidor ou máquina de CI
+    pub is_headless: bool,
+    pub storage_preference: StoragePreference,
     pub storage_backend: StorageBackend,
     pub security_level: SecurityLevel,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
 pub enum SecurityLevel {
-    High,    // Keyring nativo disponível
+    High,    // Keyring nativo ou senha mestra
     Medium,  // Arquivo criptografado
     Low,     // Fallback básico
 }
@@ -31,17 +142,38 @@ impl EnvironmentInfo {
     pub fn detect() -> Self {
         let is_wsl = Self::detect_wsl();
         let is_wsl2 = Self::detect_wsl2();
-        let has_keyring = Self::detect_keyring();
+        let keyring_probe = keyring_probe::keyring_probe();
+        let has_keyring = keyring_probe.available;
         let has_desktop_environment = Self::detect_desktop_environment();
+        let container = Self::detect_container();
+        let sandbox = Self::detect_sandbox();
+        let has_secret_portal = sandbox.is_some_and(Self::detect_secret_portal);
+        let is_ssh_session = Self::detect_ssh_session();
+        // macOS e Windows não definem as variáveis XDG/DISPLAY, mas sempre têm keyring
+        let is_headless = cfg!(target_os = "linux") && !has_desktop_environment && !is_wsl;
+        let keyring_usable = Self::keyring_usable(
+            &keyring_probe,
+            sandbox,
+            has_secret_portal,
+            is_ssh_session && !has_desktop_environment,
+        );
 
-        let storage_backend = Self::determine_storage_backend(has_keyring, is_wsl);
-        let security_level = Self::determine_security_level(&storage_backend);
+        let storage_preference = StoragePreference::load();
+        let storage_backend = Self::resolve_storage_backend(storage_preference, has_keyring, keyring_usable, is_wsl);
+        let security_level = Self::determine_security_level(&storage_backend, container.is_some());
 
         Self {
             is_wsl,
             is_wsl2,
             has_keyring,
+            keyring_probe,
             has_desktop_environment,
+            container,
+            sandbox,
+            has_secret_portal,
+            is_ssh_session,
+            is_headless,
+            storage_preference,
             storage_backend,
             security_level,
         }
@@ -72,43 +204,6 @@ impl EnvironmentInfo {
         false
     }
 
-    fn detect_keyring() -> bool {
-        // Verificar se gnome-keyring está disponível
-        if Self::command_exists("gnome-keyring-daemon") {
-            return true;
-        }
-
-        // Verificar se KDE Wallet está disponível
-        if Self::command_exists("kwalletd5") || Self::command_exists("kwalletd") {
-            return true;
-        }
-
-        // Verificar se secret-tool está disponível
-        if Self::command_exists("secret-tool") {
-            return true;
-        }
-
-        // Verificar se dbus-daemon está rodando (necessário para keyring)
-        if let Ok(output) = Command::new("pgrep").args(["-x", "dbus-daemon"]).output() {
-            if !output.stdout.is_empty() {
-                // Verificar se há um serviço de secrets ativo
-                if let Ok(_) = Command::new("dbus-send")
-                    .args([
-                        "--session",
-                        "--print-reply",
-                        "--dest=org.freedesktop.secrets",
-                        "/org/freedesktop/secrets",
-                        "org.freedesktop.DBus.Introspectable.Introspect"
-                    ])
-                    .output() {
-                    return true;
-                }
-            }
-        }
-
-        false
-    }
-
     fn detect_desktop_environment() -> bool {
         // Verificar variáveis de ambiente de desktop
         let desktop_vars = [
@@ -128,12 +223,98 @@ impl EnvironmentInfo {
         env::var("DISPLAY").is_ok() || env::var("WAYLAND_DISPLAY").is_ok()
     }
 
-    fn command_exists(cmd: &str) -> bool {
-        Command::new("which")
-            .arg(cmd)
-            .output()
-            .map(|output| output.status.success())
-            .unwrap_or(false)
+    fn detect_container() -> Option<ContainerRuntime> {
+        if fs::metadata("/run/.containerenv").is_ok() {
+            return Some(ContainerRuntime::Podman);
+        }
+        if fs::metadata("/.dockerenv").is_ok() {
+            return Some(ContainerRuntime::Docker);
+        }
+        if env::var("KUBERNETES_SERVICE_HOST").is_ok() {
+            return Some(ContainerRuntime::Kubernetes);
+        }
+
+        // Runtimes que não criam arquivo marcador aparecem no cgroup do PID 1
+        if let Ok(cgroup) = fs::read_to_string("/proc/1/cgroup") {
+            if cgroup.contains("kubepods") {
+                return Some(ContainerRuntime::Kubernetes);
+            }
+            if cgroup.contains("docker") || cgroup.contains("containerd") {
+                return Some(ContainerRuntime::Docker);
+            }
+            if cgroup.contains("libpod") {
+                return Some(ContainerRuntime::Podman);
+            }
+        }
+
+        // Flatpak também define `container`, mas é tratado como sandbox
+        match env::var("container") {
+            Ok(value) if value == "podman" => Some(ContainerRuntime::Podman),
+            Ok(value) if value == "docker" => Some(ContainerRuntime::Docker),
+            Ok(value) if value != "flatpak" && !value.is_empty() => Some(ContainerRuntime::Other),
+            _ => None,
+        }
+    }
+
+    fn detect_sandbox() -> Option<Sandbox> {
+        if fs::metadata("/.flatpak-info").is_ok() || env::var("FLATPAK_ID").is_ok() {
+            return Some(Sandbox::Flatpak);
+        }
+        if env::var("SNAP").is_ok() && env::var("SNAP_NAME").is_ok() {
+            return Some(Sandbox::Snap);
+        }
+        None
+    }
+
+    fn detect_secret_portal(sandbox: Sandbox) -> bool {
+        match sandbox {
+            // Permissões de D-Bus concedidas ao app ficam em /.flatpak-info
+            Sandbox::Flatpak => fs::read_to_string("/.flatpak-info")
+                .map(|info| info.contains("org.freedesktop.secrets"))
+                .unwrap_or(false),
+            Sandbox::Snap => Command::new("snapctl")
+                .args(["is-connected", "password-manager-service"])
+                .status()
+                .map(|status| status.success())
+                .unwrap_or(false),
+        }
+    }
+
+    fn detect_ssh_session() -> bool {
+        ["SSH_CONNECTION", "SSH_CLIENT", "SSH_TTY"]
+            .iter()
+            .any(|var| env::var(var).is_ok())
+    }
+
+    // Se o keyring serve para a escolha automática. Em sandbox sem permissão o D-Bus do
+    // host pode responder e ainda assim negar os segredos; numa sessão SSH sem desktop o
+    // pedido de desbloqueio apareceria numa tela que o usuário não vê. Em container o
+    // fallback continua sendo o arquivo, com o nível de segurança rebaixado.
+    fn keyring_usable(probe: &KeyringProbe, sandbox: Option<Sandbox>, has_secret_portal: bool, remote_only: bool) -> bool {
+        if !probe.available {
+            return false;
+        }
+        if sandbox.is_some() && !has_secret_portal {
+            return false;
+        }
+        !(remote_only && probe.locked)
+    }
+
+    fn resolve_storage_backend(
+        preference: StoragePreference,
+        has_keyring: bool,
+        keyring_usable: bool,
+        is_wsl: bool,
+    ) -> StorageBackend {
+        match preference {
+            // Escolha explícita do usuário: basta o keyring responder
+            StoragePreference::NativeKeyring if has_keyring => StorageBackend::NativeKeyring,
+            StoragePreference::EncryptedFile if !vault::is_enabled() => StorageBackend::EncryptedFile,
+            StoragePreference::PassphraseFile if vault::is_enabled() => StorageBackend::PassphraseFile,
+            // Preferência que deixou de ser utilizável neste ambiente: seguir a detecção
+            _ if vault::is_enabled() => StorageBackend::PassphraseFile,
+            _ => Self::determine_storage_backend(keyring_usable, is_wsl),
+        }
     }
 
     fn determine_storage_backend(has_keyring: bool, is_wsl: bool) -> StorageBackend {
@@ -146,20 +327,26 @@ impl EnvironmentInfo {
         }
     }
 
-    fn determine_security_level(backend: &StorageBackend) -> SecurityLevel {
+    fn determine_security_level(backend: &StorageBackend, in_container: bool) -> SecurityLevel {
         match backend {
             StorageBackend::NativeKeyring => SecurityLevel::High,
+            StorageBackend::PassphraseFile => SecurityLevel::High,
+            // Em container a chave fica na mesma camada gravável que os dados, que pode
+            // acabar em uma imagem commitada ou em um volume compartilhado
+            StorageBackend::EncryptedFile if in_container => SecurityLevel::Low,
             StorageBackend::EncryptedFile => SecurityLevel::Medium,
             StorageBackend::InMemory => SecurityLevel::Low,
         }
     }
 
-    #[allow(dead_code)]
     pub fn get_security_description(&self) -> String {
         match self.security_level {
-            SecurityLevel::High => {
-                "Máxima segurança: credenciais armazenadas no keyring nativo do sistema".to_string()
-            }
+            SecurityLevel::High => match self.storage_backend {
+                StorageBackend::PassphraseFile => {
+                    "Máxima segurança: credenciais criptografadas com chave derivada da senha mestra".to_string()
+                }
+                _ => "Máxima segurança: credenciais armazenadas no keyring nativo do sistema".to_string(),
+            },
             SecurityLevel::Medium => {
                 if self.is_wsl {
                     "Boa segurança: credenciais criptografadas em arquivo local (ambiente WSL detectado)".to_string()
@@ -167,13 +354,15 @@ impl EnvironmentInfo {
                     "Boa segurança: credenciais criptografadas em arquivo local".to_string()
                 }
             }
-            SecurityLevel::Low => {
-                "Segurança básica: armazenamento temporário".to_string()
-            }
+            SecurityLevel::Low => match self.storage_backend {
+                StorageBackend::EncryptedFile => {
+                    "Segurança básica: arquivo criptografado com a chave no mesmo container".to_string()
+                }
+                _ => "Segurança básica: armazenamento temporário".to_string(),
+            },
         }
     }
 
-    #[allow(dead_code)]
     pub fn get_improvement_suggestions(&self) -> Vec<String> {
         let mut suggestions = Vec::new();
 
@@ -183,17 +372,86 @@ impl EnvironmentInfo {
             suggestions.push("Depois execute: gnome-keyring-daemon --start --components=secrets".to_string());
         }
 
-        if !self.has_desktop_environment && !self.is_wsl {
+        if self.container.is_some() {
+            suggestions.push("Container detectado: monte ~/.config/goji como volume para manter as credenciais fora da imagem".to_string());
+        } else if self.is_headless && !self.is_ssh_session {
             suggestions.push("Sistema sem ambiente desktop detectado. Instale um gerenciador de keyring:".to_string());
             suggestions.push("Ubuntu/Debian: sudo apt install gnome-keyring".to_string());
             suggestions.push("Fedora: sudo dnf install gnome-keyring".to_string());
         }
 
+        if self.is_ssh_session && (!self.has_keyring || self.keyring_probe.locked) {
+            suggestions.push("Sessão SSH: o keyring do desktop remoto costuma estar bloqueado ou inacessível; prefira a senha mestra".to_string());
+        }
+
+        match self.sandbox {
+            Some(Sandbox::Flatpak) if !self.has_secret_portal => {
+                suggestions.push("Flatpak sem acesso ao Secret Service. Libere com:".to_string());
+                suggestions.push(format!(
+                    "flatpak override --user --talk-name=org.freedesktop.secrets {}",
+                    env::var("FLATPAK_ID").unwrap_or_else(|_| "<app-id>".to_string()),
+                ));
+            }
+            Some(Sandbox::Snap) if !self.has_secret_portal => {
+                suggestions.push("Snap sem acesso ao keyring. Conecte a interface:".to_string());
+                suggestions.push(format!(
+                    "sudo snap connect {}:password-manager-service",
+                    env::var("SNAP_NAME").unwrap_or_else(|_| "goji".to_string()),
+                ));
+            }
+            _ => {}
+        }
+
+        if matches!(self.storage_backend, StorageBackend::EncryptedFile) {
+            suggestions.push("Ative uma senha mestra para que a chave de criptografia não fique gravada em disco".to_string());
+        }
+
         suggestions
     }
 
-    #[allow(dead_code)]
     pub fn should_show_security_warning(&self) -> bool {
         matches!(self.security_level, SecurityLevel::Medium | SecurityLevel::Low)
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jira::keyring_probe::KeyringFailure;
+
+    fn probe(available: bool, locked: bool) -> KeyringProbe {
+        KeyringProbe {
+            available,
+            locked,
+            failure: (!available).then_some(KeyringFailure::NoProvider),
+            message: None,
+            elapsed_ms: 0,
+        }
+    }
+
+    #[test]
+    fn keyring_usable_requires_an_available_probe() {
+        assert!(EnvironmentInfo::keyring_usable(&probe(true, false), None, false, false));
+        assert!(!EnvironmentInfo::keyring_usable(&probe(false, false), None, false, false));
+    }
+
+    #[test]
+    fn keyring_usable_requires_secret_access_inside_sandbox() {
+        assert!(!EnvironmentInfo::keyring_usable(&probe(true, false), Some(Sandbox::Flatpak), false, false));
+        assert!(EnvironmentInfo::keyring_usable(&probe(true, false), Some(Sandbox::Snap), true, false));
+    }
+
+    #[test]
+    fn locked_keyring_is_skipped_only_for_remote_sessions() {
+        assert!(EnvironmentInfo::keyring_usable(&probe(true, true), None, false, false));
+        assert!(!EnvironmentInfo::keyring_usable(&probe(true, true), None, false, true));
+        assert!(EnvironmentInfo::keyring_usable(&probe(true, false), None, false, true));
+    }
+
+    #[test]
+    fn automatic_backend_avoids_keyring_on_wsl() {
+        assert_eq!(EnvironmentInfo::determine_storage_backend(true, false), StorageBackend::NativeKeyring);
+        assert_eq!(EnvironmentInfo::determine_storage_backend(true, true), StorageBackend::EncryptedFile);
+        assert_eq!(EnvironmentInfo::determine_storage_backend(false, false), StorageBackend::EncryptedFile);
+    }
+}
@@ -1,28 +1,293 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use serde::{Serialize, Deserialize};
 use thiserror::Error;
+use ts_rs::TS;
+
+// Corpo de erro padrão da API do JIRA
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct JiraApiErrorBody {
+    #[serde(rename = "errorMessages", default)]
+    pub error_messages: Vec<String>,
+    // Erros por campo, ex.: {"summary": "You must specify a summary of the issue."}
+    #[serde(default)]
+    pub errors: BTreeMap<String, String>,
+}
+
+impl JiraApiErrorBody {
+    pub fn summary(&self) -> String {
+        let mut parts = self.error_messages.clone();
+        parts.extend(self.errors.iter().map(|(field, message)| format!("{}: {}", field, message)));
+
+        if parts.is_empty() {
+            "sem detalhes".to_string()
+        } else {
+            parts.join("; ")
+        }
+    }
+}
+
+// Código estável usado pelo frontend para identificar o tipo de erro
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "snake_case")]
+pub enum JiraErrorCode {
+    Keyring,
+    Encryption,
+    Decryption,
+    CredentialsUnrecoverable,
+    VaultLocked,
+    InvalidPassphrase,
+    Serialization,
+    Base64,
+    Http,
+    InvalidConfig,
+    AuthenticationFailed,
+    Validation,
+    PermissionDenied,
+    NotFound,
+    RateLimited,
+    ApiError,
+    Timeout,
+    Network,
+    #[serde(rename = "oauth")]
+    OAuth,
+    Cache,
+}
+
+impl JiraErrorCode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            JiraErrorCode::Keyring => "keyring",
+            JiraErrorCode::Encryption => "encryption",
+            JiraErrorCode::Decryption => "decryption",
+            JiraErrorCode::CredentialsUnrecoverable => "credentials_unrecoverable",
+            JiraErrorCode::VaultLocked => "vault_locked",
+            JiraErrorCode::InvalidPassphrase => "invalid_passphrase",
+            JiraErrorCode::Serialization => "serialization",
+            JiraErrorCode::Base64 => "base64",
+            JiraErrorCode::Http => "http",
+            JiraErrorCode::InvalidConfig => "invalid_config",
+            JiraErrorCode::AuthenticationFailed => "authentication_failed",
+            JiraErrorCode::Validation => "validation",
+            JiraErrorCode::PermissionDenied => "permission_denied",
+            JiraErrorCode::NotFound => "not_found",
+            JiraErrorCode::RateLimited => "rate_limited",
+            JiraErrorCode::ApiError => "api_error",
+            JiraErrorCode::Timeout => "timeout",
+            JiraErrorCode::Network => "network",
+            JiraErrorCode::OAuth => "oauth",
+            JiraErrorCode::Cache => "cache",
+        }
+    }
+}
+
+impl fmt::Display for JiraErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
 
 #[derive(Error, Debug)]
 pub enum JiraError {
     #[error("Keyring error: {0}")]
     Keyring(#[from] keyring::Error),
-    
+
     #[error("Encryption error")]
     Encryption,
-    
+
     #[error("Decryption error")]
     Decryption,
-    
+
+    #[error("Stored credentials could not be recovered; please re-enter your token")]
+    CredentialsUnrecoverable,
+
+    #[error("Credential vault is locked; unlock it with your passphrase")]
+    VaultLocked,
+
+    #[error("Invalid passphrase")]
+    InvalidPassphrase,
+
     #[error("Serialization error: {0}")]
     Serialization(#[from] serde_json::Error),
-    
+
     #[error("Base64 error: {0}")]
     Base64(#[from] base64::DecodeError),
-    
+
     #[error("HTTP error: {0}")]
-    Http(#[from] reqwest::Error),
-    
+    Http(reqwest::Error),
+
     #[error("Invalid configuration: {0}")]
     InvalidConfig(String),
-    
+
     #[error("Authentication failed")]
     AuthenticationFailed,
-}
\ No newline at end of file
+
+    #[error("Validation failed: {}", .0.join("; "))]
+    Validation(Vec<String>),
+
+    #[error("Permission denied: {}", .0.summary())]
+    PermissionDenied(JiraApiErrorBody),
+
+    #[error("Not found: {}", .0.summary())]
+    NotFound(JiraApiErrorBody),
+
+    #[error("Rate limited by Jira{}", .retry_after.map(|s| format!(" (retry after {}s)", s)).unwrap_or_default())]
+    RateLimited { retry_after: Option<u64> },
+
+    #[error("Jira API error {status}: {}", .body.summary())]
+    Api { status: u16, body: JiraApiErrorBody },
+
+    #[error("Request timed out")]
+    Timeout,
+
+    #[error("Network error: {0}")]
+    Network(String),
+
+    #[error("OAuth error: {0}")]
+    OAuth(String),
+
+    #[error("Local cache error: {0}")]
+    Cache(#[from] rusqlite::Error),
+}
+
+impl From<reqwest::Error> for JiraError {
+    fn from(error: reqwest::Error) -> Self {
+        if error.is_timeout() {
+            JiraError::Timeout
+        } else if error.is_connect() || error.is_request() {
+            JiraError::Network(error.to_string())
+        } else {
+            JiraError::Http(error)
+        }
+    }
+}
+
+impl JiraError {
+    pub fn code(&self) -> JiraErrorCode {
+        match self {
+            JiraError::Keyring(_) => JiraErrorCode::Keyring,
+            JiraError::Encryption => JiraErrorCode::Encryption,
+            JiraError::Decryption => JiraErrorCode::Decryption,
+            JiraError::CredentialsUnrecoverable => JiraErrorCode::CredentialsUnrecoverable,
+            JiraError::VaultLocked => JiraErrorCode::VaultLocked,
+            JiraError::InvalidPassphrase => JiraErrorCode::InvalidPassphrase,
+            JiraError::Serialization(_) => JiraErrorCode::Serialization,
+            JiraError::Base64(_) => JiraErrorCode::Base64,
+            JiraError::Http(_) => JiraErrorCode::Http,
+            JiraError::InvalidConfig(_) => JiraErrorCode::InvalidConfig,
+            JiraError::AuthenticationFailed => JiraErrorCode::AuthenticationFailed,
+            JiraError::Validation(_) => JiraErrorCode::Validation,
+            JiraError::PermissionDenied(_) => JiraErrorCode::PermissionDenied,
+            JiraError::NotFound(_) => JiraErrorCode::NotFound,
+            JiraError::RateLimited { .. } => JiraErrorCode::RateLimited,
+            JiraError::Api { .. } => JiraErrorCode::ApiError,
+            JiraError::Timeout => JiraErrorCode::Timeout,
+            JiraError::Network(_) => JiraErrorCode::Network,
+            JiraError::OAuth(_) => JiraErrorCode::OAuth,
+            JiraError::Cache(_) => JiraErrorCode::Cache,
+        }
+    }
+
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            JiraError::AuthenticationFailed => Some(401),
+            JiraError::PermissionDenied(_) => Some(403),
+            JiraError::NotFound(_) => Some(404),
+            JiraError::RateLimited { .. } => Some(429),
+            JiraError::Api { status, .. } => Some(*status),
+            JiraError::Http(e) => e.status().map(|s| s.as_u16()),
+            _ => None,
+        }
+    }
+
+    fn details(&self) -> Option<serde_json::Value> {
+        match self {
+            JiraError::Validation(errors) => Some(serde_json::json!({ "errors": errors })),
+            JiraError::PermissionDenied(body) | JiraError::NotFound(body) | JiraError::Api { body, .. } => {
+                serde_json::to_value(body).ok()
+            }
+            JiraError::RateLimited { retry_after } => Some(serde_json::json!({ "retryAfter": retry_after })),
+            _ => None,
+        }
+    }
+}
+
+// Erro serializável retornado pelos comandos Tauri
+#[derive(Debug, Serialize, TS)]
+#[ts(export, rename = "JiraCommandError")]
+pub struct CommandError {
+    pub code: JiraErrorCode,
+    pub message: String,
+    pub status: Option<u16>,
+    #[ts(type = "Record<string, unknown> | null")]
+    pub details: Option<serde_json::Value>,
+}
+
+impl CommandError {
+    // Prefixar a mensagem com o contexto da operação, ex.: "Erro ao buscar issues"
+    pub fn with_context(context: &str, error: JiraError) -> Self {
+        Self {
+            code: error.code(),
+            message: format!("{}: {}", context, error),
+            status: error.status(),
+            details: error.details(),
+        }
+    }
+}
+
+impl From<JiraError> for CommandError {
+    fn from(error: JiraError) -> Self {
+        Self {
+            code: error.code(),
+            message: error.to_string(),
+            status: error.status(),
+            details: error.details(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn error_code_serializes_as_its_string() {
+        let codes = [
+            JiraErrorCode::Keyring,
+            JiraErrorCode::Encryption,
+            JiraErrorCode::Decryption,
+            JiraErrorCode::CredentialsUnrecoverable,
+            JiraErrorCode::VaultLocked,
+            JiraErrorCode::InvalidPassphrase,
+            JiraErrorCode::Serialization,
+            JiraErrorCode::Base64,
+            JiraErrorCode::Http,
+            JiraErrorCode::InvalidConfig,
+            JiraErrorCode::AuthenticationFailed,
+            JiraErrorCode::Validation,
+            JiraErrorCode::PermissionDenied,
+            JiraErrorCode::NotFound,
+            JiraErrorCode::RateLimited,
+            JiraErrorCode::ApiError,
+            JiraErrorCode::Timeout,
+            JiraErrorCode::Network,
+            JiraErrorCode::OAuth,
+            JiraErrorCode::Cache,
+        ];
+
+        for code in codes {
+            assert_eq!(serde_json::to_value(code).unwrap(), code.as_str());
+        }
+    }
+
+    #[test]
+    fn command_error_carries_code_and_details() {
+        let error = CommandError::with_context("Erro ao buscar issue", JiraError::RateLimited { retry_after: Some(5) });
+        let value = serde_json::to_value(&error).unwrap();
+
+        assert_eq!(value["code"], "rate_limited");
+        assert_eq!(value["status"], 429);
+        assert_eq!(value["details"]["retryAfter"], 5);
+    }
+}
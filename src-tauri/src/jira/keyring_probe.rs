@@ -0,0 +1,350 @@
+use std::sync::{mpsc, Mutex, OnceLock};
+use std::thread;
+use std::time::{Duration, Instant};
+use serde::{Serialize, Deserialize};
+use ts_rs::TS;
+use keyring::Entry;
+use rand::RngCore;
+use base64::{Engine as _, engine::general_purpose};
+
+// Sem resposta nesse prazo o keyring é tratado como indisponível; chamadas D-Bus
+// para um Secret Service travado podem bloquear indefinidamente
+const PROBE_TIMEOUT: Duration = Duration::from_secs(3);
+// Um timeout pode ser só o D-Bus ainda subindo: não vale para o processo inteiro,
+// senão o backend trocaria para arquivo e as credenciais novas iriam para outro lugar
+const TIMEOUT_CACHE: Duration = Duration::from_secs(30);
+const PROBE_SERVICE: &str = "goji";
+const PROBE_USERNAME: &str = "goji_keyring_probe";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub enum KeyringFailure {
+    NoDbusSession,    // Sem barramento de sessão (containers, SSH, CI)
+    NoProvider,       // D-Bus ok, mas nenhum serviço org.freedesktop.secrets
+    CollectionLocked, // Coleção bloqueada ou acesso negado pelo usuário
+    Timeout,
+    Mismatch,         // Valor lido difere do gravado
+    Other,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct KeyringProbe {
+    pub available: bool,
+    // Serviço respondeu, mas a gravação ficou aguardando desbloqueio (ou foi negada);
+    // o keyring continua em uso e pede a senha da coleção no primeiro acesso
+    pub locked: bool,
+    pub failure: Option<KeyringFailure>,
+    // Mensagem da plataforma, para diagnóstico
+    pub message: Option<String>,
+    #[ts(type = "number")]
+    pub elapsed_ms: u64,
+}
+
+struct CachedProbe {
+    probe: KeyringProbe,
+    at: Instant,
+}
+
+static PROBE_RESULT: OnceLock<Mutex<Option<CachedProbe>>> = OnceLock::new();
+// Thread de sondagem que não terminou no prazo. Há no máximo uma: a próxima sondagem
+// continua aguardando esta em vez de criar outra (ou de supor um novo timeout).
+static PENDING_PROBE: Mutex<Option<PendingProbe>> = Mutex::new(None);
+
+// Etapas informadas pela thread de sondagem
+enum ProbeStep {
+    // A leitura respondeu: o serviço existe, mesmo que a gravação trave depois
+    Reachable,
+    Finished(Result<(), (KeyringFailure, String)>),
+}
+
+struct PendingProbe {
+    receiver: mpsc::Receiver<ProbeStep>,
+    // `ProbeStep::Reachable` já recebido
+    reachable: bool,
+}
+
+// Resultado em cache da sondagem; a primeira chamada grava, lê e apaga um segredo de
+// teste. Um timeout só fica em cache por `TIMEOUT_CACHE`.
+pub fn keyring_probe() -> KeyringProbe {
+    let mut cached = probe_cache().lock().unwrap_or_else(|e| e.into_inner());
+
+    match cached.as_ref() {
+        Some(cached) if !is_expired(cached) => cached.probe.clone(),
+        _ => cache_probe(&mut cached, run_probe()),
+    }
+}
+
+// Sondar novamente, ex.: depois que o usuário inicia o gnome-keyring. Uma sondagem
+// anterior ainda em andamento é aguardada, não repetida.
+pub fn refresh_keyring_probe() -> KeyringProbe {
+    let mut cached = probe_cache().lock().unwrap_or_else(|e| e.into_inner());
+    cache_probe(&mut cached, run_probe())
+}
+
+// === FUNÇÕES PRIVADAS ===
+
+fn probe_cache() -> &'static Mutex<Option<CachedProbe>> {
+    PROBE_RESULT.get_or_init(|| Mutex::new(None))
+}
+
+fn cache_probe(cached: &mut Option<CachedProbe>, probe: KeyringProbe) -> KeyringProbe {
+    *cached = Some(CachedProbe { probe: probe.clone(), at: Instant::now() });
+    probe
+}
+
+fn is_expired(cached: &CachedProbe) -> bool {
+    cached.probe.failure == Some(KeyringFailure::Timeout) && cached.at.elapsed() >= TIMEOUT_CACHE
+}
+
+fn run_probe() -> KeyringProbe {
+    let started = Instant::now();
+
+    if let Some(failure) = missing_session_bus() {
+        return failed(failure, "Nenhum barramento D-Bus de sessão encontrado".to_string(), started);
+    }
+
+    let mut pending = PENDING_PROBE.lock().unwrap_or_else(|e| e.into_inner());
+    let probe = pending.take().unwrap_or_else(|| {
+        // Thread separada para aplicar o timeout; se travar, ela é abandonada
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || probe_steps(&sender));
+        PendingProbe { receiver, reachable: false }
+    });
+
+    let (result, still_pending) = wait_probe(probe, PROBE_TIMEOUT, started);
+    *pending = still_pending;
+    result
+}
+
+// Aguardar as etapas da thread de sondagem por até `timeout` cada; sem resposta,
+// a sondagem volta como pendente para a próxima chamada
+fn wait_probe(mut probe: PendingProbe, timeout: Duration, started: Instant) -> (KeyringProbe, Option<PendingProbe>) {
+    // Primeiro a leitura, que não pede desbloqueio: sem resposta, não há serviço utilizável
+    if !probe.reachable {
+        match probe.receiver.recv_timeout(timeout) {
+            Ok(ProbeStep::Reachable) => probe.reachable = true,
+            Ok(ProbeStep::Finished(Err((failure, message)))) => return (failed(failure, message, started), None),
+            Ok(ProbeStep::Finished(Ok(()))) => return (available(false, None, started), None),
+            Err(mpsc::RecvTimeoutError::Timeout) => {
+                let message = format!("Keyring não respondeu em {}s", timeout.as_secs());
+                return (failed(KeyringFailure::Timeout, message, started), Some(probe));
+            }
+            Err(mpsc::RecvTimeoutError::Disconnected) => {
+                return (failed(KeyringFailure::Other, "Sondagem do keyring interrompida".to_string(), started), None);
+            }
+        }
+    }
+
+    // A gravação pode aguardar o usuário desbloquear a coleção; isso não torna o keyring
+    // indisponível, e trocar para arquivo deixaria as credenciais já salvas para trás
+    match probe.receiver.recv_timeout(timeout) {
+        Ok(ProbeStep::Finished(Ok(()))) | Ok(ProbeStep::Reachable) => (available(false, None, started), None),
+        Ok(ProbeStep::Finished(Err((KeyringFailure::CollectionLocked, message)))) => {
+            (available(true, Some(message), started), None)
+        }
+        Ok(ProbeStep::Finished(Err((failure, message)))) => (failed(failure, message, started), None),
+        Err(mpsc::RecvTimeoutError::Timeout) => (
+            available(
+                true,
+                Some(format!("Keyring não concluiu a gravação em {}s; provavelmente aguarda desbloqueio", timeout.as_secs())),
+                started,
+            ),
+            Some(probe),
+        ),
+        Err(mpsc::RecvTimeoutError::Disconnected) => {
+            (failed(KeyringFailure::Other, "Sondagem do keyring interrompida".to_string(), started), None)
+        }
+    }
+}
+
+fn probe_steps(sender: &mpsc::Sender<ProbeStep>) {
+    let entry = match Entry::new(PROBE_SERVICE, PROBE_USERNAME) {
+        Ok(entry) => entry,
+        Err(e) => {
+            let _ = sender.send(ProbeStep::Finished(Err(classify(e))));
+            return;
+        }
+    };
+
+    match entry.get_password() {
+        Ok(_) | Err(keyring::Error::NoEntry) => {
+            let _ = sender.send(ProbeStep::Reachable);
+        }
+        // Bloqueio na leitura também indica um serviço presente
+        Err(keyring::Error::NoStorageAccess(_)) => {
+            let _ = sender.send(ProbeStep::Reachable);
+        }
+        Err(e) => {
+            let _ = sender.send(ProbeStep::Finished(Err(classify(e))));
+            return;
+        }
+    }
+
+    let _ = sender.send(ProbeStep::Finished(round_trip(&entry)));
+}
+
+fn round_trip(entry: &Entry) -> Result<(), (KeyringFailure, String)> {
+    let mut nonce = [0u8; 16];
+    rand::thread_rng().fill_bytes(&mut nonce);
+    let secret = general_purpose::STANDARD.encode(nonce);
+
+    entry.set_password(&secret).map_err(classify)?;
+    let read_back = entry.get_password().map_err(classify);
+    // Apagar mesmo se a leitura falhou, para não deixar o segredo de teste para trás
+    let deleted = entry.delete_password().map_err(classify);
+
+    if read_back? != secret {
+        return Err((KeyringFailure::Mismatch, "Valor lido do keyring difere do gravado".to_string()));
+    }
+    deleted
+}
+
+fn classify(error: keyring::Error) -> (KeyringFailure, String) {
+    let message = error.to_string();
+    let lower = message.to_lowercase();
+
+    let failure = match error {
+        keyring::Error::NoStorageAccess(_) => KeyringFailure::CollectionLocked,
+        keyring::Error::PlatformFailure(_)
+            if lower.contains("serviceunknown") || lower.contains("org.freedesktop.secrets") || lower.contains("unavailable") =>
+        {
+            KeyringFailure::NoProvider
+        }
+        keyring::Error::PlatformFailure(_)
+            if lower.contains("dbus") || lower.contains("d-bus") || lower.contains("address") =>
+        {
+            KeyringFailure::NoDbusSession
+        }
+        _ => KeyringFailure::Other,
+    };
+
+    (failure, message)
+}
+
+// No Linux o Secret Service só é alcançável pelo barramento de sessão
+#[cfg(target_os = "linux")]
+fn missing_session_bus() -> Option<KeyringFailure> {
+    let has_bus = std::env::var_os("DBUS_SESSION_BUS_ADDRESS").is_some()
+        || std::env::var_os("XDG_RUNTIME_DIR")
+            .map(|dir| std::path::Path::new(&dir).join("bus").exists())
+            .unwrap_or(false);
+    (!has_bus).then_some(KeyringFailure::NoDbusSession)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn missing_session_bus() -> Option<KeyringFailure> {
+    None
+}
+
+fn available(locked: bool, message: Option<String>, started: Instant) -> KeyringProbe {
+    KeyringProbe {
+        available: true,
+        locked,
+        failure: None,
+        message,
+        elapsed_ms: started.elapsed().as_millis() as u64,
+    }
+}
+
+fn failed(failure: KeyringFailure, message: String, started: Instant) -> KeyringProbe {
+    KeyringProbe {
+        available: false,
+        locked: false,
+        failure: Some(failure),
+        message: Some(message),
+        elapsed_ms: started.elapsed().as_millis() as u64,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SHORT: Duration = Duration::from_millis(20);
+
+    fn pending() -> (mpsc::Sender<ProbeStep>, PendingProbe) {
+        let (sender, receiver) = mpsc::channel();
+        (sender, PendingProbe { receiver, reachable: false })
+    }
+
+    #[test]
+    fn classify_maps_platform_errors() {
+        let (failure, _) = classify(keyring::Error::NoStorageAccess("locked".into()));
+        assert_eq!(failure, KeyringFailure::CollectionLocked);
+
+        let (failure, _) = classify(keyring::Error::PlatformFailure(
+            "org.freedesktop.DBus.Error.ServiceUnknown: The name org.freedesktop.secrets was not provided".into(),
+        ));
+        assert_eq!(failure, KeyringFailure::NoProvider);
+
+        let (failure, message) = classify(keyring::Error::PlatformFailure("D-Bus error: no address".into()));
+        assert_eq!(failure, KeyringFailure::NoDbusSession);
+        assert!(message.contains("no address"));
+
+        let (failure, _) = classify(keyring::Error::BadEncoding(vec![0xff]));
+        assert_eq!(failure, KeyringFailure::Other);
+    }
+
+    #[test]
+    fn slow_probe_is_awaited_by_the_next_call() {
+        let (sender, probe) = pending();
+
+        // D-Bus ainda subindo: timeout, mas a sondagem continua pendente
+        let (result, probe) = wait_probe(probe, SHORT, Instant::now());
+        assert_eq!(result.failure, Some(KeyringFailure::Timeout));
+        let probe = probe.expect("sondagem pendente");
+
+        // A mesma thread responde depois; a próxima chamada usa o resultado dela
+        sender.send(ProbeStep::Reachable).unwrap();
+        sender.send(ProbeStep::Finished(Ok(()))).unwrap();
+        let (result, probe) = wait_probe(probe, SHORT, Instant::now());
+        assert!(result.available && !result.locked);
+        assert!(probe.is_none());
+    }
+
+    #[test]
+    fn write_waiting_for_unlock_keeps_the_keyring() {
+        let (sender, probe) = pending();
+        sender.send(ProbeStep::Reachable).unwrap();
+
+        let (result, probe) = wait_probe(probe, SHORT, Instant::now());
+        assert!(result.available && result.locked);
+        let probe = probe.expect("gravação pendente");
+        assert!(probe.reachable);
+
+        sender.send(ProbeStep::Finished(Err((KeyringFailure::CollectionLocked, "negado".to_string())))).unwrap();
+        let (result, probe) = wait_probe(probe, SHORT, Instant::now());
+        assert!(result.available && result.locked);
+        assert!(probe.is_none());
+    }
+
+    #[test]
+    fn finished_failure_is_not_pending() {
+        let (sender, probe) = pending();
+        sender.send(ProbeStep::Finished(Err((KeyringFailure::NoProvider, "sem serviço".to_string())))).unwrap();
+
+        let (result, probe) = wait_probe(probe, SHORT, Instant::now());
+        assert_eq!(result.failure, Some(KeyringFailure::NoProvider));
+        assert!(probe.is_none());
+
+        // Thread encerrada sem resposta
+        let (sender, probe) = pending();
+        drop(sender);
+        let (result, probe) = wait_probe(probe, SHORT, Instant::now());
+        assert_eq!(result.failure, Some(KeyringFailure::Other));
+        assert!(probe.is_none());
+    }
+
+    #[test]
+    fn only_timeouts_expire_from_the_cache() {
+        let old = Instant::now() - TIMEOUT_CACHE - Duration::from_secs(1);
+        let timeout = failed(KeyringFailure::Timeout, String::new(), Instant::now());
+        let no_provider = failed(KeyringFailure::NoProvider, String::new(), Instant::now());
+
+        assert!(is_expired(&CachedProbe { probe: timeout.clone(), at: old }));
+        assert!(!is_expired(&CachedProbe { probe: timeout, at: Instant::now() }));
+        assert!(!is_expired(&CachedProbe { probe: no_provider, at: old }));
+        assert!(!is_expired(&CachedProbe { probe: available(false, None, Instant::now()), at: old }));
+    }
+}
@@ -1,6 +1,18 @@
 pub mod config_manager;
 pub mod client;
+pub mod adf;
 pub mod auth;
 pub mod error;
 pub mod environment;
-pub mod secure_storage;
\ No newline at end of file
+pub mod keyring_probe;
+pub mod credential_store;
+pub mod secure_storage;
+pub mod timer;
+pub mod request;
+pub mod oauth;
+pub mod profiles;
+pub mod vault;
+pub mod diagnostics;
+pub mod cache;
+pub mod sync;
+pub mod paths;
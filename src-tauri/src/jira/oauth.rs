@@ -0,0 +1,650 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+use serde::{Serialize, Deserialize};
+use ts_rs::TS;
+use chrono::{DateTime, Utc};
+use rand::{Rng, distributions::Alphanumeric};
+use sha2::{Digest, Sha256};
+use base64::{Engine as _, engine::general_purpose};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use crate::jira::error::JiraError;
+use crate::jira::secure_storage::SecureStorage;
+use crate::jira::credential_store::CredentialStore;
+use crate::jira::profiles::JiraProfile;
+use crate::jira::config_manager::{self, JiraConfig, JiraAuthMode};
+
+// OAuth 2.0 (3LO) com PKCE. Os endpoints são configuráveis para permitir
+// testes contra um servidor de autorização local no lugar do Atlassian.
+pub const DEFAULT_REDIRECT_PORT: u16 = 8765;
+const CALLBACK_PATH: &str = "/callback";
+const LOGIN_TIMEOUT_SECS: u64 = 300;
+// Conexões abertas sem enviar a requisição (ex.: preconnect do navegador) são descartadas
+const CALLBACK_READ_TIMEOUT_SECS: u64 = 5;
+// Renovar o access token um pouco antes de expirar
+const REFRESH_MARGIN_SECS: i64 = 60;
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct OAuthEndpoints {
+    #[serde(rename = "authorizeUrl")]
+    pub authorize_url: String,
+    #[serde(rename = "tokenUrl")]
+    pub token_url: String,
+    // Lista os sites JIRA liberados para o token (cloud id + URL)
+    #[serde(rename = "resourcesUrl")]
+    pub resources_url: String,
+    // Chamadas à API com OAuth passam pelo gateway: {gateway}/ex/jira/{cloudId}
+    #[serde(rename = "apiGatewayUrl")]
+    pub api_gateway_url: String,
+}
+
+impl Default for OAuthEndpoints {
+    fn default() -> Self {
+        Self {
+            authorize_url: "https://auth.atlassian.com/authorize".to_string(),
+            token_url: "https://auth.atlassian.com/oauth/token".to_string(),
+            resources_url: "https://api.atlassian.com/oauth/token/accessible-resources".to_string(),
+            api_gateway_url: "https://api.atlassian.com".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct OAuthClientConfig {
+    #[serde(rename = "clientId")]
+    pub client_id: String,
+    // Opcional: apps com PKCE podem ser públicos
+    #[serde(rename = "clientSecret", default)]
+    #[ts(optional)]
+    pub client_secret: Option<String>,
+    #[serde(default = "default_scopes")]
+    pub scopes: Vec<String>,
+    #[serde(default)]
+    pub endpoints: OAuthEndpoints,
+    // Precisa coincidir com a callback cadastrada no app OAuth
+    #[serde(rename = "redirectPort", default = "default_redirect_port")]
+    pub redirect_port: u16,
+}
+
+fn default_scopes() -> Vec<String> {
+    ["read:jira-work", "write:jira-work", "read:jira-user", "offline_access"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect()
+}
+
+fn default_redirect_port() -> u16 {
+    DEFAULT_REDIRECT_PORT
+}
+
+impl OAuthClientConfig {
+    pub fn redirect_uri(&self) -> String {
+        format!("http://127.0.0.1:{}{}", self.redirect_port, CALLBACK_PATH)
+    }
+
+    fn validate(&self) -> Result<(), JiraError> {
+        if self.client_id.trim().is_empty() {
+            return Err(JiraError::InvalidConfig("Client ID não pode estar vazio".to_string()));
+        }
+
+        let endpoints = [
+            &self.endpoints.authorize_url,
+            &self.endpoints.token_url,
+            &self.endpoints.resources_url,
+            &self.endpoints.api_gateway_url,
+        ];
+        if let Some(url) = endpoints.iter().find(|url| !config_manager::is_allowed_url(url)) {
+            return Err(JiraError::InvalidConfig(format!("Endpoint OAuth deve usar https:// (http:// apenas em loopback): {}", url)));
+        }
+
+        Ok(())
+    }
+}
+
+// Site JIRA liberado para o token
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JiraCloudResource {
+    pub id: String,
+    pub url: String,
+    pub name: String,
+    #[serde(default)]
+    pub scopes: Vec<String>,
+}
+
+// Dados persistidos para renovar o access token; o token atual fica no JiraConfig
+#[derive(Clone, Serialize, Deserialize)]
+pub struct OAuthSession {
+    pub client: OAuthClientConfig,
+    pub refresh_token: Option<String>,
+    pub expires_at: DateTime<Utc>,
+    pub cloud_id: String,
+}
+
+impl OAuthSession {
+    pub fn needs_refresh(&self) -> bool {
+        self.expires_at - chrono::Duration::seconds(REFRESH_MARGIN_SECS) <= Utc::now()
+    }
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    #[serde(default)]
+    refresh_token: Option<String>,
+    expires_in: i64,
+}
+
+pub struct OAuthSessionStore {
+    secure_storage: SecureStorage,
+}
+
+impl OAuthSessionStore {
+    pub fn for_profile(profile_id: &str) -> Result<Self, JiraError> {
+        Ok(Self {
+            secure_storage: SecureStorage::new("goji", &JiraProfile::oauth_slot(profile_id))?,
+        })
+    }
+
+    pub fn load(&self) -> Result<Option<OAuthSession>, JiraError> {
+        match self.secure_storage.retrieve()? {
+            Some(json_data) => Ok(Some(serde_json::from_str(&json_data)?)),
+            None => Ok(None),
+        }
+    }
+
+    pub fn save(&self, session: &OAuthSession) -> Result<(), JiraError> {
+        self.secure_storage.store(&serde_json::to_string(session)?)
+    }
+
+    pub fn clear(&self) -> Result<(), JiraError> {
+        match self.secure_storage.delete() {
+            Err(JiraError::Keyring(keyring::Error::NoEntry)) => Ok(()),
+            result => result,
+        }
+    }
+}
+
+// Autorização iniciada: o listener de loopback já está aguardando a callback
+pub struct PendingAuthorization {
+    listener: TcpListener,
+    client: OAuthClientConfig,
+    verifier: String,
+    state: String,
+    authorize_url: String,
+}
+
+pub async fn begin_authorization(mut client: OAuthClientConfig) -> Result<PendingAuthorization, JiraError> {
+    client.validate()?;
+
+    let listener = TcpListener::bind(("127.0.0.1", client.redirect_port))
+        .await
+        .map_err(|e| JiraError::OAuth(format!("Não foi possível escutar na porta {}: {}", client.redirect_port, e)))?;
+
+    // Com a porta 0 o sistema escolhe uma livre; a redirect_uri precisa refletir a porta real
+    client.redirect_port = listener.local_addr()
+        .map_err(|e| JiraError::OAuth(format!("Erro no listener de callback: {}", e)))?
+        .port();
+
+    let verifier = random_string(64);
+    let state = random_string(32);
+    let challenge = general_purpose::URL_SAFE_NO_PAD.encode(Sha256::digest(verifier.as_bytes()));
+
+    let authorize_url = reqwest::Url::parse_with_params(&client.endpoints.authorize_url, &[
+        ("audience", "api.atlassian.com"),
+        ("client_id", client.client_id.as_str()),
+        ("scope", client.scopes.join(" ").as_str()),
+        ("redirect_uri", client.redirect_uri().as_str()),
+        ("state", state.as_str()),
+        ("response_type", "code"),
+        ("prompt", "consent"),
+        ("code_challenge", challenge.as_str()),
+        ("code_challenge_method", "S256"),
+    ])
+    .map_err(|e| JiraError::InvalidConfig(format!("URL de autorização inválida: {}", e)))?;
+
+    Ok(PendingAuthorization {
+        listener,
+        client,
+        verifier,
+        state,
+        authorize_url: authorize_url.to_string(),
+    })
+}
+
+impl PendingAuthorization {
+    // URL a ser aberta no navegador do usuário
+    pub fn authorize_url(&self) -> &str {
+        &self.authorize_url
+    }
+
+    // Aguardar a callback, trocar o código por tokens e salvar a sessão no perfil.
+    // `site_url` escolhe o site quando o token libera mais de um.
+    pub async fn complete(self, profile_id: &str, site_url: Option<&str>) -> Result<JiraConfig, JiraError> {
+        let (config, session) = self.authorize(site_url).await?;
+
+        config_manager::JiraConfigManager::for_profile(profile_id)?.save_config(&config)?;
+        OAuthSessionStore::for_profile(profile_id)?.save(&session)?;
+
+        Ok(config)
+    }
+
+    // === MÉTODOS PRIVADOS ===
+
+    async fn authorize(self, site_url: Option<&str>) -> Result<(JiraConfig, OAuthSession), JiraError> {
+        let code = tokio::time::timeout(
+            Duration::from_secs(LOGIN_TIMEOUT_SECS),
+            wait_for_callback(&self.listener, &self.state),
+        )
+        .await
+        .map_err(|_| JiraError::OAuth("Tempo esgotado aguardando a autorização".to_string()))??;
+
+        let http_client = http_client()?;
+
+        let tokens = request_tokens(&http_client, &self.client, &[
+            ("grant_type", "authorization_code"),
+            ("code", code.as_str()),
+            ("redirect_uri", self.client.redirect_uri().as_str()),
+            ("code_verifier", self.verifier.as_str()),
+        ]).await?;
+
+        let resource = select_resource(&http_client, &self.client, &tokens.access_token, site_url).await?;
+
+        let config = JiraConfig {
+            url: resource.url.trim_end_matches('/').to_string(),
+            email: String::new(),
+            token: tokens.access_token,
+            auth_mode: JiraAuthMode::OAuth,
+            api_version: None,
+            api_url: Some(format!(
+                "{}/ex/jira/{}",
+                self.client.endpoints.api_gateway_url.trim_end_matches('/'),
+                resource.id,
+            )),
+            created_at: Utc::now(),
+            last_used: None,
+        };
+
+        let session = OAuthSession {
+            client: self.client,
+            refresh_token: tokens.refresh_token,
+            expires_at: Utc::now() + chrono::Duration::seconds(tokens.expires_in),
+            cloud_id: resource.id,
+        };
+
+        Ok((config, session))
+    }
+}
+
+static REFRESH_LOCKS: OnceLock<Mutex<HashMap<String, Arc<tokio::sync::Mutex<()>>>>> = OnceLock::new();
+
+// Lock da renovação de um perfil: o refresh token pode ser rotacionado a cada uso,
+// então duas trocas simultâneas com o mesmo token derrubariam a sessão
+pub fn refresh_lock(profile_id: &str) -> Arc<tokio::sync::Mutex<()>> {
+    let mut locks = REFRESH_LOCKS.get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+
+    locks.entry(profile_id.to_string()).or_default().clone()
+}
+
+// Renovar o access token, atualizando a sessão (o refresh token pode ser rotacionado)
+pub async fn refresh_session(session: &mut OAuthSession) -> Result<String, JiraError> {
+    let refresh_token = session.refresh_token.clone()
+        .ok_or_else(|| JiraError::OAuth("Sessão OAuth sem refresh token; faça login novamente".to_string()))?;
+
+    let tokens = request_tokens(&http_client()?, &session.client, &[
+        ("grant_type", "refresh_token"),
+        ("refresh_token", refresh_token.as_str()),
+    ]).await?;
+
+    session.expires_at = Utc::now() + chrono::Duration::seconds(tokens.expires_in);
+    if tokens.refresh_token.is_some() {
+        session.refresh_token = tokens.refresh_token;
+    }
+
+    Ok(tokens.access_token)
+}
+
+fn http_client() -> Result<reqwest::Client, JiraError> {
+    Ok(reqwest::Client::builder()
+        .timeout(Duration::from_secs(30))
+        .user_agent("Goji/1.0.0")
+        .build()?)
+}
+
+async fn request_tokens(
+    http_client: &reqwest::Client,
+    client: &OAuthClientConfig,
+    grant: &[(&str, &str)],
+) -> Result<TokenResponse, JiraError> {
+    let mut form: Vec<(&str, &str)> = vec![("client_id", client.client_id.as_str())];
+    if let Some(secret) = client.client_secret.as_deref().filter(|s| !s.is_empty()) {
+        form.push(("client_secret", secret));
+    }
+    form.extend_from_slice(grant);
+
+    let response = http_client
+        .post(&client.endpoints.token_url)
+        .header("Accept", "application/json")
+        .form(&form)
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(JiraError::OAuth(format!("Troca de token recusada ({}): {}", status, text.trim())));
+    }
+
+    Ok(response.json().await?)
+}
+
+async fn select_resource(
+    http_client: &reqwest::Client,
+    client: &OAuthClientConfig,
+    access_token: &str,
+    site_url: Option<&str>,
+) -> Result<JiraCloudResource, JiraError> {
+    let response = http_client
+        .get(&client.endpoints.resources_url)
+        .header("Authorization", format!("Bearer {}", access_token))
+        .header("Accept", "application/json")
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        return Err(JiraError::OAuth(format!("Falha ao listar sites autorizados ({})", response.status())));
+    }
+
+    let resources: Vec<JiraCloudResource> = response.json().await?;
+
+    let wanted = site_url.map(|url| url.trim_end_matches('/').to_lowercase());
+    resources.into_iter()
+        .find(|r| wanted.as_ref().map_or(true, |url| r.url.trim_end_matches('/').to_lowercase() == *url))
+        .ok_or_else(|| JiraError::OAuth(match site_url {
+            Some(url) => format!("O site {} não foi autorizado para este app", url),
+            None => "Nenhum site JIRA foi autorizado para este app".to_string(),
+        }))
+}
+
+// Atender requisições no loopback até chegar a callback com o código
+async fn wait_for_callback(listener: &TcpListener, expected_state: &str) -> Result<String, JiraError> {
+    loop {
+        let (mut stream, _) = listener.accept()
+            .await
+            .map_err(|e| JiraError::OAuth(format!("Erro na conexão de callback: {}", e)))?;
+
+        let read = tokio::time::timeout(
+            Duration::from_secs(CALLBACK_READ_TIMEOUT_SECS),
+            read_request_head(&mut stream),
+        );
+        let Ok(request) = read.await else {
+            continue;
+        };
+
+        let target = request.lines().next()
+            .and_then(|line| line.split_whitespace().nth(1))
+            .unwrap_or("/");
+
+        let url = match reqwest::Url::parse(&format!("http://127.0.0.1{}", target)) {
+            Ok(url) if url.path() == CALLBACK_PATH => url,
+            // Navegadores pedem /favicon.ico e afins; ignorar
+            _ => {
+                let _ = respond(&mut stream, "404 Not Found", "").await;
+                continue;
+            }
+        };
+
+        let param = |name: &str| url.query_pairs()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.into_owned());
+
+        // Qualquer página ou processo local pode chamar a callback: sem o state
+        // desta autorização a requisição é ignorada, inclusive com `error`
+        if param("state").as_deref() != Some(expected_state) {
+            let _ = respond(&mut stream, "400 Bad Request", "<h1>Requisição inválida</h1>").await;
+            continue;
+        }
+
+        let result = match param("error") {
+            Some(error) => Err(JiraError::OAuth(param("error_description").unwrap_or(error))),
+            None => param("code").ok_or_else(|| JiraError::OAuth("Callback sem código de autorização".to_string())),
+        };
+
+        let page = match &result {
+            Ok(_) => "<h1>Login concluído</h1><p>Você já pode fechar esta aba e voltar ao Goji.</p>",
+            Err(_) => "<h1>Falha no login</h1><p>Volte ao Goji para ver os detalhes.</p>",
+        };
+        let _ = respond(&mut stream, "200 OK", page).await;
+
+        return result;
+    }
+}
+
+// Linha de requisição e cabeçalhos, até a linha em branco
+async fn read_request_head(stream: &mut tokio::net::TcpStream) -> String {
+    let mut buffer = vec![0u8; 8192];
+    let mut read = 0;
+    while read < buffer.len() {
+        match stream.read(&mut buffer[read..]).await {
+            Ok(0) => break,
+            Ok(n) => {
+                read += n;
+                if buffer[..read].windows(4).any(|w| w == b"\r\n\r\n") {
+                    break;
+                }
+            }
+            Err(_) => break,
+        }
+    }
+
+    String::from_utf8_lossy(&buffer[..read]).into_owned()
+}
+
+async fn respond(stream: &mut tokio::net::TcpStream, status: &str, body: &str) -> std::io::Result<()> {
+    let html = format!("<!doctype html><html><head><meta charset=\"utf-8\"><title>Goji</title></head><body>{}</body></html>", body);
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        html.len(),
+        html,
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await
+}
+
+fn random_string(len: usize) -> String {
+    rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(len)
+        .map(char::from)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::TcpStream;
+
+    fn runtime() -> tokio::runtime::Runtime {
+        tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap()
+    }
+
+    fn client(base: &str) -> OAuthClientConfig {
+        OAuthClientConfig {
+            client_id: "goji-test".to_string(),
+            client_secret: None,
+            scopes: default_scopes(),
+            endpoints: OAuthEndpoints {
+                authorize_url: format!("{}/authorize", base),
+                token_url: format!("{}/oauth/token", base),
+                resources_url: format!("{}/resources", base),
+                api_gateway_url: base.to_string(),
+            },
+            redirect_port: 0,
+        }
+    }
+
+    // Servidor de autorização local no lugar do Atlassian; guarda os formulários recebidos em /oauth/token
+    async fn start_authorization_server() -> (String, Arc<Mutex<Vec<String>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        let forms = Arc::new(Mutex::new(Vec::new()));
+
+        let received = forms.clone();
+        let resources = serde_json::json!([
+            { "id": "cloud-1", "url": "https://empresa.atlassian.net", "name": "Empresa", "scopes": [] },
+        ]);
+        tokio::spawn(async move {
+            loop {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let head = read_request_head(&mut stream).await;
+                let (head, already_read) = head.split_once("\r\n\r\n").unwrap_or((&head, ""));
+
+                let length = head.lines()
+                    .find_map(|line| line.to_lowercase().strip_prefix("content-length:").map(|v| v.trim().parse::<usize>().unwrap()))
+                    .unwrap_or(0);
+                let mut body = already_read.as_bytes().to_vec();
+                while body.len() < length {
+                    let mut chunk = vec![0u8; length - body.len()];
+                    let n = stream.read(&mut chunk).await.unwrap();
+                    body.extend_from_slice(&chunk[..n]);
+                }
+
+                let path = head.split_whitespace().nth(1).unwrap_or("/").to_string();
+                let response = match path.as_str() {
+                    "/oauth/token" => {
+                        let mut forms = received.lock().unwrap();
+                        forms.push(String::from_utf8_lossy(&body).into_owned());
+                        let n = forms.len();
+                        serde_json::json!({
+                            "access_token": format!("access-{}", n),
+                            "refresh_token": format!("refresh-{}", n),
+                            "expires_in": 3600,
+                        })
+                    }
+                    "/resources" => resources.clone(),
+                    _ => serde_json::json!({}),
+                }
+                .to_string();
+
+                let reply = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    response.len(),
+                    response,
+                );
+                let _ = stream.write_all(reply.as_bytes()).await;
+                let _ = stream.shutdown().await;
+            }
+        });
+
+        (base, forms)
+    }
+
+    fn query_param(url: &str, name: &str) -> String {
+        reqwest::Url::parse(url).unwrap()
+            .query_pairs()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.into_owned())
+            .unwrap()
+    }
+
+    // Simula o navegador voltando para a redirect_uri
+    async fn open_callback(port: u16, query: String) -> String {
+        let mut stream = TcpStream::connect(("127.0.0.1", port)).await.unwrap();
+        let request = format!("GET {}?{} HTTP/1.1\r\nHost: 127.0.0.1\r\n\r\n", CALLBACK_PATH, query);
+        stream.write_all(request.as_bytes()).await.unwrap();
+
+        let mut page = String::new();
+        stream.read_to_string(&mut page).await.unwrap();
+        page
+    }
+
+    #[test]
+    fn authorization_code_flow_against_local_server() {
+        runtime().block_on(async {
+            let (base, forms) = start_authorization_server().await;
+
+            let pending = begin_authorization(client(&base)).await.unwrap();
+            let state = query_param(pending.authorize_url(), "state");
+            let redirect_uri = query_param(pending.authorize_url(), "redirect_uri");
+            let port = reqwest::Url::parse(&redirect_uri).unwrap().port().unwrap();
+            assert_ne!(port, 0);
+
+            // Conexão que nunca envia a requisição não pode impedir a callback
+            let _preconnect = TcpStream::connect(("127.0.0.1", port)).await.unwrap();
+            let browser = tokio::spawn(open_callback(port, format!("code=abc&state={}", state)));
+
+            let (config, session) = pending.authorize(None).await.unwrap();
+            assert!(browser.await.unwrap().contains("Login concluído"));
+
+            assert_eq!(config.url, "https://empresa.atlassian.net");
+            assert_eq!(config.api_url.as_deref(), Some(format!("{}/ex/jira/cloud-1", base).as_str()));
+            assert_eq!(config.token, "access-1");
+            assert_eq!(config.auth_mode, JiraAuthMode::OAuth);
+            assert_eq!(session.refresh_token.as_deref(), Some("refresh-1"));
+            assert_eq!(session.cloud_id, "cloud-1");
+
+            let forms = forms.lock().unwrap();
+            assert!(forms[0].contains("grant_type=authorization_code"));
+            assert!(forms[0].contains("code=abc"));
+            assert!(forms[0].contains("code_verifier="));
+        });
+    }
+
+    #[test]
+    fn callback_without_matching_state_is_ignored() {
+        runtime().block_on(async {
+            let (base, forms) = start_authorization_server().await;
+
+            let pending = begin_authorization(client(&base)).await.unwrap();
+            let state = query_param(pending.authorize_url(), "state");
+            let redirect_uri = query_param(pending.authorize_url(), "redirect_uri");
+            let port = reqwest::Url::parse(&redirect_uri).unwrap().port().unwrap();
+
+            let login = tokio::spawn(pending.authorize(None));
+
+            // Requisições forjadas não interrompem o login em andamento
+            assert!(open_callback(port, "code=abc&state=forjado".to_string()).await.starts_with("HTTP/1.1 400"));
+            assert!(open_callback(port, "error=access_denied".to_string()).await.starts_with("HTTP/1.1 400"));
+            assert!(forms.lock().unwrap().is_empty());
+
+            // Com o state correto, `error` encerra o login
+            let page = open_callback(port, format!("error=access_denied&error_description=Negado&state={}", state)).await;
+            assert!(page.contains("Falha no login"));
+            assert!(matches!(login.await.unwrap(), Err(JiraError::OAuth(message)) if message == "Negado"));
+            assert!(forms.lock().unwrap().is_empty());
+        });
+    }
+
+    #[test]
+    fn refresh_keeps_rotated_refresh_token() {
+        runtime().block_on(async {
+            let (base, forms) = start_authorization_server().await;
+
+            let mut session = OAuthSession {
+                client: client(&base),
+                refresh_token: Some("refresh-0".to_string()),
+                expires_at: Utc::now(),
+                cloud_id: "cloud-1".to_string(),
+            };
+            assert!(session.needs_refresh());
+
+            let token = refresh_session(&mut session).await.unwrap();
+            assert_eq!(token, "access-1");
+            assert_eq!(session.refresh_token.as_deref(), Some("refresh-1"));
+            assert!(!session.needs_refresh());
+            assert!(forms.lock().unwrap()[0].contains("refresh_token=refresh-0"));
+        });
+    }
+
+    #[test]
+    fn endpoints_must_be_https_outside_loopback() {
+        assert!(client("http://127.0.0.1:9000").validate().is_ok());
+        assert!(client("https://auth.example.com").validate().is_ok());
+        assert!(client("http://auth.example.com").validate().is_err());
+    }
+}
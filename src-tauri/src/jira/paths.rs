@@ -0,0 +1,12 @@
+use std::path::PathBuf;
+use crate::jira::error::JiraError;
+
+// Diretório com os arquivos do Goji (perfis, cofre, cache, cronômetros...),
+// ex.: ~/.config/goji no Linux
+pub fn goji_config_dir() -> Result<PathBuf, JiraError> {
+    let path = dirs::config_dir()
+        .or_else(|| dirs::home_dir().map(|p| p.join(".config")))
+        .ok_or_else(|| JiraError::InvalidConfig("Não foi possível encontrar diretório de configuração".to_string()))?;
+
+    Ok(path.join("goji"))
+}
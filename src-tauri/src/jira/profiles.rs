@@ -0,0 +1,255 @@
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use serde::{Serialize, Deserialize};
+use ts_rs::TS;
+use chrono::{DateTime, Utc};
+use crate::jira::error::JiraError;
+use crate::jira::paths::goji_config_dir;
+
+// Perfil criado na migração; usa os slots de armazenamento anteriores aos perfis
+pub const DEFAULT_PROFILE_ID: &str = "default";
+
+// Serializa o ciclo carregar-alterar-salvar do profiles.json entre comandos simultâneos
+static PROFILES_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct JiraProfile {
+    // Identificador estável (slug); o nome pode ser alterado livremente
+    pub id: String,
+    pub name: String,
+    #[ts(type = "string")]
+    pub created_at: DateTime<Utc>,
+}
+
+impl JiraProfile {
+    // Nome da entrada no SecureStorage para a configuração do perfil
+    pub fn config_slot(id: &str) -> String {
+        Self::slot("jira_config", id)
+    }
+
+    // Nome da entrada no SecureStorage para a sessão OAuth do perfil
+    pub fn oauth_slot(id: &str) -> String {
+        Self::slot("jira_oauth", id)
+    }
+
+    // Arquivo de cronômetros do perfil, no diretório de configuração
+    pub fn timers_file(id: &str) -> String {
+        format!("{}.json", Self::slot("work_timers", id))
+    }
+
+    // Configuração de sincronização do perfil; os projetos acompanhados só existem
+    // na instância do próprio perfil
+    pub fn sync_settings_file(id: &str) -> String {
+        format!("{}.json", Self::slot("sync", id))
+    }
+
+    fn slot(prefix: &str, id: &str) -> String {
+        if id == DEFAULT_PROFILE_ID {
+            prefix.to_string()
+        } else {
+            format!("{}_{}", prefix, id)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct JiraProfileList {
+    pub active: String,
+    pub profiles: Vec<JiraProfile>,
+}
+
+// Índice de perfis (sem credenciais), persistido no diretório de configuração.
+// As credenciais de cada perfil ficam em slots próprios do SecureStorage.
+pub struct ProfileManager {
+    file_path: PathBuf,
+    list: JiraProfileList,
+}
+
+impl ProfileManager {
+    pub fn load() -> Result<Self, JiraError> {
+        let file_path = Self::get_profiles_file_path()?;
+
+        let list = match fs::read(&file_path) {
+            Ok(data) => serde_json::from_slice(&data)?,
+            // Primeira execução com perfis: a configuração existente vira o perfil padrão
+            Err(ref e) if e.kind() == std::io::ErrorKind::NotFound => JiraProfileList {
+                active: DEFAULT_PROFILE_ID.to_string(),
+                profiles: vec![JiraProfile {
+                    id: DEFAULT_PROFILE_ID.to_string(),
+                    name: "Padrão".to_string(),
+                    created_at: Utc::now(),
+                }],
+            },
+            Err(e) => return Err(JiraError::InvalidConfig(format!("Erro ao ler perfis: {}", e))),
+        };
+
+        Ok(Self { file_path, list })
+    }
+
+    // Alterar o índice com acesso exclusivo; leituras podem usar `load` diretamente
+    pub fn update<T, F>(operation: F) -> Result<T, JiraError>
+    where
+        F: FnOnce(&mut Self) -> Result<T, JiraError>,
+    {
+        let _guard = PROFILES_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let mut profiles = Self::load()?;
+        operation(&mut profiles)
+    }
+
+    pub fn list(&self) -> JiraProfileList {
+        self.list.clone()
+    }
+
+    pub fn active_id(&self) -> &str {
+        &self.list.active
+    }
+
+    pub fn get(&self, id: &str) -> Result<&JiraProfile, JiraError> {
+        self.list.profiles.iter()
+            .find(|p| p.id == id)
+            .ok_or_else(|| Self::not_found(id))
+    }
+
+    pub fn create(&mut self, name: &str) -> Result<JiraProfile, JiraError> {
+        let name = Self::validate_name(name)?;
+        self.ensure_unique_name(&name, None)?;
+
+        let base = slugify(&name);
+        let mut id = base.clone();
+        let mut suffix = 2;
+        while self.list.profiles.iter().any(|p| p.id == id) {
+            id = format!("{}-{}", base, suffix);
+            suffix += 1;
+        }
+
+        let profile = JiraProfile {
+            id,
+            name,
+            created_at: Utc::now(),
+        };
+        self.list.profiles.push(profile.clone());
+        self.save()?;
+        Ok(profile)
+    }
+
+    pub fn rename(&mut self, id: &str, name: &str) -> Result<JiraProfile, JiraError> {
+        let name = Self::validate_name(name)?;
+        self.ensure_unique_name(&name, Some(id))?;
+
+        let profile = self.list.profiles.iter_mut()
+            .find(|p| p.id == id)
+            .ok_or_else(|| Self::not_found(id))?;
+        profile.name = name;
+
+        let profile = profile.clone();
+        self.save()?;
+        Ok(profile)
+    }
+
+    // Remove apenas do índice; o chamador apaga as credenciais do perfil
+    pub fn delete(&mut self, id: &str) -> Result<(), JiraError> {
+        self.get(id)?;
+        if self.list.profiles.len() == 1 {
+            return Err(JiraError::InvalidConfig("Não é possível remover o único perfil".to_string()));
+        }
+
+        self.list.profiles.retain(|p| p.id != id);
+        if self.list.active == id {
+            self.list.active = self.list.profiles[0].id.clone();
+        }
+        self.save()
+    }
+
+    pub fn activate(&mut self, id: &str) -> Result<JiraProfile, JiraError> {
+        let profile = self.get(id)?.clone();
+        self.list.active = profile.id.clone();
+        self.save()?;
+        Ok(profile)
+    }
+
+    // Todos os slots do SecureStorage usados pelos perfis (configuração e sessão OAuth)
+    pub fn storage_slots(&self) -> Vec<String> {
+        self.list.profiles.iter()
+            .flat_map(|p| [JiraProfile::config_slot(&p.id), JiraProfile::oauth_slot(&p.id)])
+            .collect()
+    }
+
+    // === MÉTODOS PRIVADOS ===
+
+    fn validate_name(name: &str) -> Result<String, JiraError> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(JiraError::InvalidConfig("Nome do perfil não pode estar vazio".to_string()));
+        }
+        if slugify(name).is_empty() {
+            return Err(JiraError::InvalidConfig("Nome do perfil deve conter letras ou números".to_string()));
+        }
+        Ok(name.to_string())
+    }
+
+    fn ensure_unique_name(&self, name: &str, except_id: Option<&str>) -> Result<(), JiraError> {
+        let taken = self.list.profiles.iter()
+            .any(|p| Some(p.id.as_str()) != except_id && p.name.eq_ignore_ascii_case(name));
+        if taken {
+            return Err(JiraError::InvalidConfig(format!("Já existe um perfil chamado {}", name)));
+        }
+        Ok(())
+    }
+
+    fn save(&self) -> Result<(), JiraError> {
+        if let Some(parent) = self.file_path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| JiraError::InvalidConfig(format!("Erro ao criar diretório: {}", e)))?;
+        }
+
+        let data = serde_json::to_vec_pretty(&self.list)?;
+        fs::write(&self.file_path, data)
+            .map_err(|e| JiraError::InvalidConfig(format!("Erro ao salvar perfis: {}", e)))
+    }
+
+    fn get_profiles_file_path() -> Result<PathBuf, JiraError> {
+        Ok(goji_config_dir()?.join("profiles.json"))
+    }
+
+    fn not_found(id: &str) -> JiraError {
+        JiraError::InvalidConfig(format!("Perfil não encontrado: {}", id))
+    }
+}
+
+// "Empresa Cloud" -> "empresa-cloud"; usado em nomes de slots do keyring e de arquivos
+fn slugify(name: &str) -> String {
+    let mut slug = String::new();
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.ends_with('-') && !slug.is_empty() {
+            slug.push('-');
+        }
+    }
+    slug.trim_end_matches('-').to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use crate::jira::credential_store::key_entry_name;
+
+    #[test]
+    fn profile_named_key_does_not_share_the_default_keys() {
+        let key_profile = slugify("Key");
+        assert_eq!(key_profile, "key");
+
+        let entries: Vec<String> = [DEFAULT_PROFILE_ID, key_profile.as_str()].iter()
+            .flat_map(|id| [JiraProfile::config_slot(id), JiraProfile::oauth_slot(id)])
+            .flat_map(|slot| [key_entry_name(&slot), slot])
+            .collect();
+
+        // Cada slot e cada chave AES ocupam uma entrada própria no keyring
+        let unique: HashSet<&String> = entries.iter().collect();
+        assert_eq!(unique.len(), entries.len(), "{:?}", entries);
+    }
+}
@@ -0,0 +1,346 @@
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+use serde::Serialize;
+use ts_rs::TS;
+use chrono::{DateTime, Utc};
+use rand::Rng;
+use reqwest::{header::HeaderMap, Method, StatusCode};
+use crate::jira::error::{JiraError, JiraApiErrorBody};
+
+// Tentativas extras para requisições idempotentes (além da primeira)
+const MAX_RETRIES: u32 = 4;
+const BASE_DELAY_MS: u64 = 1_000;
+const MAX_DELAY_MS: u64 = 30_000;
+// Acima disso não vale a pena segurar o comando; o erro volta para o frontend
+const MAX_RETRY_AFTER_SECS: u64 = 60;
+
+// Orçamento de requisições informado pelo JIRA nos cabeçalhos X-RateLimit-*
+#[derive(Debug, Clone, Default, Serialize, TS)]
+#[ts(export, rename = "JiraRateLimitStatus")]
+pub struct RateLimitStatus {
+    pub limit: Option<u32>,
+    pub remaining: Option<u32>,
+    #[serde(rename = "resetAt")]
+    #[ts(type = "string | null")]
+    pub reset_at: Option<DateTime<Utc>>,
+    // true quando o JIRA sinaliza X-RateLimit-NearLimit
+    #[serde(rename = "nearLimit")]
+    pub near_limit: bool,
+    // Preenchido enquanto houver uma resposta 429/503 aguardando nova tentativa
+    #[serde(rename = "retryAfter")]
+    #[ts(type = "number | null")]
+    pub retry_after: Option<u64>,
+    #[serde(rename = "throttledCount")]
+    #[ts(type = "number")]
+    pub throttled_count: u64,
+    #[serde(rename = "updatedAt")]
+    #[ts(type = "string | null")]
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+// Por perfil: cada perfil aponta para uma instância (e um orçamento) diferente
+static RATE_LIMIT: OnceLock<Mutex<HashMap<String, RateLimitStatus>>> = OnceLock::new();
+
+fn rate_limit_state() -> &'static Mutex<HashMap<String, RateLimitStatus>> {
+    RATE_LIMIT.get_or_init(Default::default)
+}
+
+// Último orçamento conhecido do perfil, compartilhado pelos clientes do processo
+pub fn rate_limit_status(profile_id: &str) -> RateLimitStatus {
+    rate_limit_state().lock()
+        .ok()
+        .and_then(|states| states.get(profile_id).cloned())
+        .unwrap_or_default()
+}
+
+// Executor compartilhado pelas chamadas do JiraClient: registra o orçamento de
+// rate limit e repete requisições idempotentes em 429/503 e falhas de rede.
+#[derive(Clone)]
+pub struct RequestExecutor {
+    http_client: reqwest::Client,
+    profile_id: String,
+}
+
+impl RequestExecutor {
+    pub fn new(http_client: reqwest::Client, profile_id: &str) -> Self {
+        Self {
+            http_client,
+            profile_id: profile_id.to_string(),
+        }
+    }
+
+    // Idempotência decidida pelo método HTTP; respostas não-2xx já voltam como JiraError
+    pub async fn send(&self, request: reqwest::RequestBuilder) -> Result<reqwest::Response, JiraError> {
+        let request = request.build()?;
+        let idempotent = is_idempotent(request.method());
+        check_response(self.execute(request, idempotent).await?).await
+    }
+
+    // Para POSTs que apenas consultam dados (ex.: /search/jql) e podem ser repetidos
+    pub async fn send_read_only(&self, request: reqwest::RequestBuilder) -> Result<reqwest::Response, JiraError> {
+        let request = request.build()?;
+        check_response(self.execute(request, true).await?).await
+    }
+
+    // === MÉTODOS PRIVADOS ===
+
+    async fn execute(&self, request: reqwest::Request, idempotent: bool) -> Result<reqwest::Response, JiraError> {
+        let mut attempt = 0;
+
+        loop {
+            let can_retry = idempotent && attempt < MAX_RETRIES;
+
+            // Corpos em stream não podem ser clonados; nesse caso não há nova tentativa
+            let Some(current) = request.try_clone() else {
+                return Ok(self.http_client.execute(request).await?);
+            };
+
+            let delay = match self.http_client.execute(current).await {
+                Ok(response) => {
+                    let status = response.status();
+                    let retry_after = parse_retry_after(response.headers());
+                    record_response(&self.profile_id, response.headers(), status, retry_after);
+
+                    if !is_retryable_status(status) || !can_retry {
+                        return Ok(response);
+                    }
+
+                    match retry_after {
+                        Some(seconds) if seconds > MAX_RETRY_AFTER_SECS => return Ok(response),
+                        Some(seconds) => Duration::from_secs(seconds),
+                        None => backoff_delay(attempt),
+                    }
+                }
+                Err(e) if can_retry && (e.is_timeout() || e.is_connect()) => backoff_delay(attempt),
+                Err(e) => return Err(e.into()),
+            };
+
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+}
+
+// Converter respostas não-2xx no erro correspondente, preservando o corpo de erro do JIRA
+async fn check_response(response: reqwest::Response) -> Result<reqwest::Response, JiraError> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+
+    let retry_after = parse_retry_after(response.headers());
+
+    let text = response.text().await.unwrap_or_default();
+    let body = serde_json::from_str::<JiraApiErrorBody>(&text)
+        .ok()
+        .filter(|b| !b.error_messages.is_empty() || !b.errors.is_empty())
+        .unwrap_or_else(|| JiraApiErrorBody {
+            error_messages: if text.trim().is_empty() { Vec::new() } else { vec![text.trim().to_string()] },
+            errors: Default::default(),
+        });
+
+    Err(match status.as_u16() {
+        401 => JiraError::AuthenticationFailed,
+        403 => JiraError::PermissionDenied(body),
+        404 => JiraError::NotFound(body),
+        429 => JiraError::RateLimited { retry_after },
+        code => JiraError::Api { status: code, body },
+    })
+}
+
+fn is_idempotent(method: &Method) -> bool {
+    matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS | Method::PUT | Method::DELETE)
+}
+
+fn is_retryable_status(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS || status == StatusCode::SERVICE_UNAVAILABLE
+}
+
+// Backoff exponencial com jitter: entre metade e o total de base * 2^tentativa
+fn backoff_delay(attempt: u32) -> Duration {
+    let ceiling = BASE_DELAY_MS.saturating_mul(1 << attempt.min(16)).min(MAX_DELAY_MS);
+    let millis = rand::thread_rng().gen_range(ceiling / 2..=ceiling);
+    Duration::from_millis(millis)
+}
+
+// Retry-After pode vir em segundos ou como data HTTP
+fn parse_retry_after(headers: &HeaderMap) -> Option<u64> {
+    let value = headers.get(reqwest::header::RETRY_AFTER)?.to_str().ok()?.trim();
+
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(seconds);
+    }
+
+    DateTime::parse_from_rfc2822(value)
+        .ok()
+        .map(|date| (date.with_timezone(&Utc) - Utc::now()).num_seconds().max(0) as u64)
+}
+
+fn header_value<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    headers.get(name).and_then(|v| v.to_str().ok()).map(str::trim)
+}
+
+fn record_response(profile_id: &str, headers: &HeaderMap, status: StatusCode, retry_after: Option<u64>) {
+    let Ok(mut states) = rate_limit_state().lock() else {
+        return;
+    };
+    let state = states.entry(profile_id.to_string()).or_default();
+
+    if let Some(limit) = header_value(headers, "x-ratelimit-limit").and_then(|v| v.parse().ok()) {
+        state.limit = Some(limit);
+    }
+    if let Some(remaining) = header_value(headers, "x-ratelimit-remaining").and_then(|v| v.parse().ok()) {
+        state.remaining = Some(remaining);
+    }
+    if let Some(reset) = header_value(headers, "x-ratelimit-reset") {
+        state.reset_at = DateTime::parse_from_rfc3339(reset)
+            .map(|d| d.with_timezone(&Utc))
+            .ok()
+            .or_else(|| reset.parse::<i64>().ok().and_then(|ts| DateTime::from_timestamp(ts, 0)));
+    }
+    state.near_limit = header_value(headers, "x-ratelimit-nearlimit")
+        .is_some_and(|v| v.eq_ignore_ascii_case("true"));
+
+    if is_retryable_status(status) {
+        state.retry_after = retry_after;
+        state.throttled_count += 1;
+    } else {
+        state.retry_after = None;
+    }
+
+    state.updated_at = Some(Utc::now());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use reqwest::header::HeaderValue;
+
+    fn headers(pairs: &[(&'static str, &str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in pairs {
+            headers.insert(*name, HeaderValue::from_str(value).unwrap());
+        }
+        headers
+    }
+
+    // Servidor local que responde sempre com `status` e conta as requisições recebidas
+    fn throttling_server(status: &'static str) -> (String, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/rest/api/3/issue", listener.local_addr().unwrap());
+        let hits = Arc::new(AtomicUsize::new(0));
+        let counter = hits.clone();
+
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(mut stream) = stream else { break };
+                let mut request = Vec::new();
+                let mut buffer = [0u8; 1024];
+                while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+                    match stream.read(&mut buffer) {
+                        Ok(0) | Err(_) => break,
+                        Ok(n) => request.extend_from_slice(&buffer[..n]),
+                    }
+                }
+                counter.fetch_add(1, Ordering::SeqCst);
+                let response = format!(
+                    "HTTP/1.1 {}\r\nRetry-After: 0\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                    status
+                );
+                let _ = stream.write_all(response.as_bytes());
+            }
+        });
+
+        (url, hits)
+    }
+
+    fn block_on<F: std::future::Future>(future: F) -> F::Output {
+        tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap().block_on(future)
+    }
+
+    #[test]
+    fn retry_after_accepts_seconds_and_http_dates() {
+        assert_eq!(parse_retry_after(&headers(&[("retry-after", " 7 ")])), Some(7));
+
+        let date = (Utc::now() + chrono::Duration::seconds(120)).format("%a, %d %b %Y %H:%M:%S GMT").to_string();
+        let seconds = parse_retry_after(&headers(&[("retry-after", &date)])).unwrap();
+        assert!((118..=120).contains(&seconds), "{}", seconds);
+
+        // Data no passado: tentar de novo imediatamente
+        assert_eq!(parse_retry_after(&headers(&[("retry-after", "Wed, 21 Oct 2015 07:28:00 GMT")])), Some(0));
+
+        assert_eq!(parse_retry_after(&headers(&[("retry-after", "amanhã")])), None);
+        assert_eq!(parse_retry_after(&HeaderMap::new()), None);
+    }
+
+    #[test]
+    fn backoff_is_capped_and_jittered() {
+        for attempt in 0..20 {
+            let ceiling = (BASE_DELAY_MS << attempt.min(16)).min(MAX_DELAY_MS);
+            for _ in 0..50 {
+                let delay = backoff_delay(attempt).as_millis() as u64;
+                assert!(delay >= ceiling / 2 && delay <= ceiling, "tentativa {}: {}ms", attempt, delay);
+                assert!(delay <= MAX_DELAY_MS);
+            }
+        }
+    }
+
+    #[test]
+    fn non_idempotent_requests_are_not_retried() {
+        for status in ["429 Too Many Requests", "503 Service Unavailable"] {
+            let (url, hits) = throttling_server(status);
+            let executor = RequestExecutor::new(reqwest::Client::new(), "test-no-retry");
+
+            let result = block_on(executor.send(reqwest::Client::new().post(&url)));
+            assert!(result.is_err());
+            assert_eq!(hits.load(Ordering::SeqCst), 1, "{}", status);
+        }
+    }
+
+    #[test]
+    fn idempotent_requests_are_retried() {
+        let (url, hits) = throttling_server("503 Service Unavailable");
+        let executor = RequestExecutor::new(reqwest::Client::new(), "test-retry");
+
+        let result = block_on(executor.send(reqwest::Client::new().get(&url)));
+        assert!(matches!(result, Err(JiraError::Api { status: 503, .. })));
+        assert_eq!(hits.load(Ordering::SeqCst), MAX_RETRIES as usize + 1);
+    }
+
+    #[test]
+    fn responses_update_the_rate_limit_status() {
+        let profile = "test-rate-limit";
+        let throttled = headers(&[
+            ("x-ratelimit-limit", "100"),
+            ("x-ratelimit-remaining", "0"),
+            ("x-ratelimit-reset", "2030-01-01T00:00:00Z"),
+            ("x-ratelimit-nearlimit", "true"),
+        ]);
+
+        record_response(profile, &throttled, StatusCode::TOO_MANY_REQUESTS, Some(5));
+        let status = rate_limit_status(profile);
+        assert_eq!(status.limit, Some(100));
+        assert_eq!(status.remaining, Some(0));
+        assert_eq!(status.reset_at.unwrap().to_rfc3339(), "2030-01-01T00:00:00+00:00");
+        assert!(status.near_limit);
+        assert_eq!(status.retry_after, Some(5));
+        assert_eq!(status.throttled_count, 1);
+
+        // Resposta bem-sucedida limpa a espera, mas mantém a contagem de 429/503
+        record_response(profile, &headers(&[("x-ratelimit-remaining", "99")]), StatusCode::OK, None);
+        let status = rate_limit_status(profile);
+        assert_eq!(status.remaining, Some(99));
+        assert_eq!(status.limit, Some(100));
+        assert!(!status.near_limit);
+        assert_eq!(status.retry_after, None);
+        assert_eq!(status.throttled_count, 1);
+
+        // Perfis não compartilham o orçamento
+        assert_eq!(rate_limit_status("test-rate-limit-other").throttled_count, 0);
+    }
+}
@@ -1,277 +1,502 @@
-use std::fs::{self, OpenOptions};
-use std::io::Write;
-use std::path::PathBuf;
-#[cfg(unix)]
-use std::os::unix::fs::PermissionsExt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+use serde::Serialize;
+use ts_rs::TS;
 use keyring::Entry;
-use aes_gcm::{Aes256Gcm, Key, Nonce, KeyInit};
-use aes_gcm::aead::{Aead, OsRng};
-use rand::RngCore;
-use serde::{Serialize, Deserialize};
-use base64::{Engine as _, engine::general_purpose};
-use crate::jira::error::JiraError;
-use crate::jira::environment::{EnvironmentInfo, StorageBackend};
-
-#[derive(Serialize, Deserialize)]
-struct EncryptedData {
-    nonce: Vec<u8>,
-    ciphertext: Vec<u8>,
+use aes_gcm::{Aes256Gcm, Key};
+use crate::jira::error::{JiraError, JiraErrorCode};
+use crate::jira::paths::goji_config_dir;
+use crate::jira::environment::{EnvironmentInfo, StorageBackend, StoragePreference};
+use crate::jira::credential_store::{key_entry_name, CredentialStore, KeyringStore, EncryptedFileStore, InMemoryStore};
+use crate::jira::vault;
+
+// Credenciais encontradas em um backend diferente do atual (ex.: `.enc` de antes
+// de o keyring ser instalado no WSL)
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export)]
+pub struct StorageMigrationStatus {
+    #[serde(rename = "currentBackend")]
+    pub current_backend: StorageBackend,
+    #[serde(rename = "sourceBackend")]
+    pub source_backend: Option<StorageBackend>,
+    // Slots com dados no backend de origem
+    pub slots: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export)]
+pub struct StorageMigrationReport {
+    #[serde(rename = "sourceBackend")]
+    pub source_backend: StorageBackend,
+    #[serde(rename = "targetBackend")]
+    pub target_backend: StorageBackend,
+    pub migrated: Vec<String>,
+    // Slots que já tinham dados no backend atual; a cópia antiga é mantida
+    pub skipped: Vec<String>,
+    // Slots que não puderam ser migrados; a cópia antiga é mantida
+    pub failed: Vec<StorageMigrationFailure>,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export)]
+pub struct StorageMigrationFailure {
+    pub slot: String,
+    // Mesmo código de JiraError usado nos erros de comando, ex.: "credentials_unrecoverable"
+    pub code: JiraErrorCode,
+    pub message: String,
 }
 
+// Serializa as trocas de chave do cofre com a recuperação de uma troca interrompida;
+// `true` depois que a recuperação rodou neste processo
+static STAGING: Mutex<bool> = Mutex::new(false);
+
+// Resultado da migração de um slot
+enum SlotMigration {
+    Migrated,
+    Skipped,
+    Empty,
+}
+
+// Armazenamento escolhido conforme o ambiente detectado
 pub struct SecureStorage {
-    environment: EnvironmentInfo,
-    keyring_entry: Option<Entry>,
-    file_path: Option<PathBuf>,
-    cipher_key: Key<Aes256Gcm>,
+    store: Box<dyn CredentialStore>,
 }
 
 impl SecureStorage {
     pub fn new(service: &str, username: &str) -> Result<Self, JiraError> {
         let environment = EnvironmentInfo::detect();
-        
-        let keyring_entry = match environment.storage_backend {
-            StorageBackend::NativeKeyring => {
-                Some(Entry::new(service, username)?)
-            }
-            _ => None,
-        };
+        let store = Self::open_backend(&environment.storage_backend, service, username)?;
 
-        let file_path = match environment.storage_backend {
-            StorageBackend::EncryptedFile => {
-                Some(Self::get_secure_file_path(service, username)?)
-            }
-            _ => None,
-        };
+        Ok(Self { store })
+    }
 
-        let cipher_key = Self::get_or_create_cipher_key(&environment, &keyring_entry, &file_path)?;
+    // Abrir apenas um slot já gravado, sem criar chave nenhuma: para leituras que não
+    // devem alterar o armazenamento (ex.: diagnóstico). `None` se o slot está vazio.
+    pub fn open_existing(backend: StorageBackend, service: &str, username: &str) -> Result<Option<Self>, JiraError> {
+        if !Self::has_data(backend, service, username) {
+            return Ok(None);
+        }
+        // Dados sem a chave correspondente não podem ser lidos; abrir geraria outra chave
+        if !Self::has_key(backend, service, username) {
+            return Err(JiraError::CredentialsUnrecoverable);
+        }
 
-        Ok(Self {
-            environment,
-            keyring_entry,
-            file_path,
-            cipher_key,
+        let store = Self::open_backend(&backend, service, username)?;
+        Ok(Some(Self { store }))
+    }
+
+    // Procurar credenciais no backend que não está em uso (keyring <-> arquivo)
+    pub fn migration_status(service: &str, slots: &[String]) -> Result<StorageMigrationStatus, JiraError> {
+        let current_backend = EnvironmentInfo::detect().storage_backend;
+        let source_backend = Self::alternate_backend(current_backend);
+
+        let slots = match source_backend {
+            Some(source) => slots.iter()
+                .filter(|slot| Self::has_data(source, service, slot))
+                .cloned()
+                .collect(),
+            None => Vec::new(),
+        };
+
+        Ok(StorageMigrationStatus {
+            current_backend,
+            source_backend: source_backend.filter(|_| !slots.is_empty()),
+            slots,
         })
     }
 
-    pub fn store(&self, data: &str) -> Result<(), JiraError> {
-        match &self.environment.storage_backend {
-            StorageBackend::NativeKeyring => {
-                if let Some(entry) = &self.keyring_entry {
-                    // Ainda criptografar mesmo no keyring para dupla proteção
-                    let encrypted = self.encrypt_data(data.as_bytes())?;
-                    let encrypted_b64 = general_purpose::STANDARD.encode(serde_json::to_vec(&encrypted)?);
-                    entry.set_password(&encrypted_b64)?;
-                } else {
-                    return Err(JiraError::InvalidConfig("Keyring não disponível".to_string()));
-                }
-            }
-            StorageBackend::EncryptedFile => {
-                if let Some(file_path) = &self.file_path {
-                    let encrypted = self.encrypt_data(data.as_bytes())?;
-                    let encrypted_data = serde_json::to_vec(&encrypted)?;
-                    
-                    // Criar diretório se não existir
-                    if let Some(parent) = file_path.parent() {
-                        fs::create_dir_all(parent)
-                            .map_err(|e| JiraError::InvalidConfig(format!("Erro ao criar diretório: {}", e)))?;
-                    }
-                    
-                    // Escrever arquivo com permissões restritas
-                    let mut file = OpenOptions::new()
-                        .write(true)
-                        .create(true)
-                        .truncate(true)
-                        .open(file_path)
-                        .map_err(|e| JiraError::InvalidConfig(format!("Erro ao criar arquivo: {}", e)))?;
-                    
-                    file.write_all(&encrypted_data)
-                        .map_err(|e| JiraError::InvalidConfig(format!("Erro ao escrever arquivo: {}", e)))?;
-                    
-                    // Definir permissões 600 (apenas proprietário pode ler/escrever)
-                    #[cfg(unix)]
-                    {
-                        let metadata = file.metadata()
-                            .map_err(|e| JiraError::InvalidConfig(format!("Erro ao obter metadados: {}", e)))?;
-                        let mut permissions = metadata.permissions();
-                        permissions.set_mode(0o600);
-                        file.set_permissions(permissions)
-                            .map_err(|e| JiraError::InvalidConfig(format!("Erro ao definir permissões: {}", e)))?;
-                    }
-                } else {
-                    return Err(JiraError::InvalidConfig("Caminho do arquivo não disponível".to_string()));
-                }
-            }
-            StorageBackend::InMemory => {
-                // Para testes - não implementar armazenamento persistente
-                return Err(JiraError::InvalidConfig("Armazenamento em memória não suportado".to_string()));
+    // Mover as credenciais do backend alternativo para o atual e apagar a cópia antiga
+    // com segurança. Slots já preenchidos no backend atual não são sobrescritos.
+    pub fn migrate(service: &str, slots: &[String]) -> Result<StorageMigrationReport, JiraError> {
+        let status = Self::migration_status(service, slots)?;
+        let source_backend = status.source_backend
+            .ok_or_else(|| JiraError::InvalidConfig("Nenhuma credencial encontrada em outro backend".to_string()))?;
+
+        let mut migrated = Vec::new();
+        let mut skipped = Vec::new();
+        let mut failed = Vec::new();
+
+        // Um slot ilegível (ex.: credencial irrecuperável) não impede a migração dos demais
+        for slot in &status.slots {
+            match Self::migrate_slot(source_backend, status.current_backend, service, slot) {
+                Ok(SlotMigration::Migrated) => migrated.push(slot.clone()),
+                Ok(SlotMigration::Skipped) => skipped.push(slot.clone()),
+                Ok(SlotMigration::Empty) => {}
+                Err(e) => failed.push(StorageMigrationFailure {
+                    slot: slot.clone(),
+                    code: e.code(),
+                    message: e.to_string(),
+                }),
             }
         }
 
-        Ok(())
+        Ok(StorageMigrationReport {
+            source_backend,
+            target_backend: status.current_backend,
+            migrated,
+            skipped,
+            failed,
+        })
     }
 
-    pub fn retrieve(&self) -> Result<Option<String>, JiraError> {
-        match &self.environment.storage_backend {
-            StorageBackend::NativeKeyring => {
-                if let Some(entry) = &self.keyring_entry {
-                    match entry.get_password() {
-                        Ok(encrypted_b64) => {
-                            let encrypted_bytes = general_purpose::STANDARD.decode(&encrypted_b64)?;
-                            let encrypted: EncryptedData = serde_json::from_slice(&encrypted_bytes)?;
-                            let decrypted = self.decrypt_data(&encrypted)?;
-                            Ok(Some(String::from_utf8_lossy(&decrypted).to_string()))
-                        }
-                        Err(keyring::Error::NoEntry) => Ok(None),
-                        Err(e) => Err(JiraError::Keyring(e)),
-                    }
-                } else {
-                    Err(JiraError::InvalidConfig("Keyring não disponível".to_string()))
-                }
-            }
-            StorageBackend::EncryptedFile => {
-                if let Some(file_path) = &self.file_path {
-                    match fs::read(file_path) {
-                        Ok(encrypted_data) => {
-                            let encrypted: EncryptedData = serde_json::from_slice(&encrypted_data)?;
-                            let decrypted = self.decrypt_data(&encrypted)?;
-                            Ok(Some(String::from_utf8_lossy(&decrypted).to_string()))
-                        }
-                        Err(ref e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
-                        Err(e) => Err(JiraError::InvalidConfig(format!("Erro ao ler arquivo: {}", e))),
-                    }
-                } else {
-                    Err(JiraError::InvalidConfig("Caminho do arquivo não disponível".to_string()))
+    // Ativar a senha mestra: regrava todos os slots informados com a chave derivada
+    // e descarta a chave AES anterior (keyring ou arquivo .key)
+    pub fn enable_passphrase(service: &str, slots: &[String], passphrase: &str) -> Result<(), JiraError> {
+        let previous = slots.iter()
+            .map(|slot| Self::new(service, slot))
+            .collect::<Result<Vec<_>, _>>()?;
+        let contents = previous.iter()
+            .map(|storage| storage.retrieve())
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // Sem o cabeçalho o backend anterior continua em uso com os dados intactos
+        let mut staging = Self::staging_guard()?;
+        let plan = vault::prepare_create(passphrase)?;
+        let staged = Self::stage_slots(slots, &contents, plan.key())?;
+        Self::commit_staged(&mut staging, staged, plan)?;
+        drop(staging);
+
+        for storage in &previous {
+            // No modo arquivo o .enc já foi substituído no mesmo caminho; só a chave sai
+            if !matches!(storage.backend(), StorageBackend::EncryptedFile) {
+                match storage.delete() {
+                    Ok(()) | Err(JiraError::Keyring(keyring::Error::NoEntry)) => {}
+                    Err(e) => return Err(e),
                 }
             }
-            StorageBackend::InMemory => {
-                Ok(None)
-            }
+            storage.delete_key()?;
         }
-    }
 
-    pub fn delete(&self) -> Result<(), JiraError> {
-        match &self.environment.storage_backend {
-            StorageBackend::NativeKeyring => {
-                if let Some(entry) = &self.keyring_entry {
-                    entry.delete_password().map_err(JiraError::Keyring)?;
-                }
-            }
-            StorageBackend::EncryptedFile => {
-                if let Some(file_path) = &self.file_path {
-                    if file_path.exists() {
-                        fs::remove_file(file_path)
-                            .map_err(|e| JiraError::InvalidConfig(format!("Erro ao deletar arquivo: {}", e)))?;
-                    }
-                }
-            }
-            StorageBackend::InMemory => {
-                // Nada a fazer
-            }
+        // Uma preferência explícita por outro backend deixaria o cofre sem uso
+        if StoragePreference::load() != StoragePreference::Auto {
+            StoragePreference::PassphraseFile.save()?;
         }
 
         Ok(())
     }
 
-    pub fn get_environment_info(&self) -> &EnvironmentInfo {
-        &self.environment
+    // Trocar a senha mestra: todos os slots são recifrados com a nova chave antes de
+    // o novo cabeçalho ser gravado; os arquivos atuais só são substituídos depois dele
+    pub fn change_passphrase(
+        slots: &[String],
+        current_passphrase: &str,
+        new_passphrase: &str,
+    ) -> Result<(), JiraError> {
+        let mut staging = Self::staging_guard()?;
+        vault::unlock(current_passphrase)?;
+
+        let contents = slots.iter()
+            .map(|slot| EncryptedFileStore::with_passphrase(Self::get_secure_file_path(slot)?).retrieve())
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let plan = vault::prepare_rekey(new_passphrase)?;
+        let staged = Self::stage_slots(slots, &contents, plan.key())?;
+        Self::commit_staged(&mut staging, staged, plan)
     }
 
     // Métodos privados
 
-    fn get_secure_file_path(service: &str, username: &str) -> Result<PathBuf, JiraError> {
-        let mut path = dirs::config_dir()
-            .or_else(|| dirs::home_dir().map(|p| p.join(".config")))
-            .ok_or_else(|| JiraError::InvalidConfig("Não foi possível encontrar diretório de configuração".to_string()))?;
-        
-        path.push(service);
-        path.push(format!("{}.enc", username));
-        Ok(path)
+    fn migrate_slot(
+        source_backend: StorageBackend,
+        target_backend: StorageBackend,
+        service: &str,
+        slot: &str,
+    ) -> Result<SlotMigration, JiraError> {
+        let source = Self::open_backend(&source_backend, service, slot)?;
+        let target = Self::open_backend(&target_backend, service, slot)?;
+
+        if target.retrieve()?.is_some() {
+            return Ok(SlotMigration::Skipped);
+        }
+
+        let Some(data) = source.retrieve()? else {
+            return Ok(SlotMigration::Empty);
+        };
+        target.store(&data)?;
+
+        // Só apagar a origem depois de confirmar que a cópia nova pode ser lida
+        if target.retrieve()?.as_deref() != Some(data.as_str()) {
+            return Err(JiraError::InvalidConfig(format!("Falha ao verificar credenciais migradas: {}", slot)));
+        }
+        source.secure_delete()?;
+        Ok(SlotMigration::Migrated)
     }
 
-    fn get_or_create_cipher_key(
-        _environment: &EnvironmentInfo,
-        keyring_entry: &Option<Entry>,
-        file_path: &Option<PathBuf>,
-    ) -> Result<Key<Aes256Gcm>, JiraError> {
-        // Tentar recuperar chave existente do keyring se disponível
-        if let Some(entry) = keyring_entry {
-            if let Ok(key_data) = entry.get_password() {
-                if key_data.len() >= 44 { // Base64 de 32 bytes = 44 chars
-                    if let Ok(key_bytes) = general_purpose::STANDARD.decode(&key_data[..44]) {
-                        if key_bytes.len() == 32 {
-                            return Ok(Key::<Aes256Gcm>::from_slice(&key_bytes).clone());
-                        }
+    // Gravar os slots com a nova chave em `<slot>.enc.new`, sem tocar nos arquivos
+    // atuais; em caso de erro os temporários já gravados são removidos
+    fn stage_slots(
+        slots: &[String],
+        contents: &[Option<String>],
+        key: Key<Aes256Gcm>,
+    ) -> Result<Vec<StagedFile>, JiraError> {
+        let mut staged = Vec::new();
+
+        for (slot, data) in slots.iter().zip(contents) {
+            let Some(data) = data else {
+                continue;
+            };
+
+            let written = Self::get_secure_file_path(slot).and_then(|path| {
+                let file = StagedFile::new(path);
+                match EncryptedFileStore::with_key(file.staged_path.clone(), key).store(data) {
+                    Ok(()) => Ok(file),
+                    Err(e) => {
+                        file.discard();
+                        Err(e)
                     }
                 }
+            });
+            match written {
+                Ok(file) => staged.push(file),
+                Err(e) => {
+                    staged.iter().for_each(StagedFile::discard);
+                    return Err(e);
+                }
             }
         }
 
-        // Tentar recuperar chave de arquivo de chave separado
-        if let Some(config_path) = file_path {
-            let key_path = config_path.with_extension("key");
-            if let Ok(key_data) = fs::read_to_string(&key_path) {
-                if let Ok(key_bytes) = general_purpose::STANDARD.decode(key_data.trim()) {
-                    if key_bytes.len() == 32 {
-                        return Ok(Key::<Aes256Gcm>::from_slice(&key_bytes).clone());
-                    }
-                }
+        Ok(staged)
+    }
+
+    // Cabeçalho pendente, arquivos preparados no lugar e só então o cabeçalho novo.
+    // Se o processo parar no meio, `recover_staged` conclui a troca na próxima abertura.
+    fn commit_staged(
+        recovered: &mut MutexGuard<'_, bool>,
+        staged: Vec<StagedFile>,
+        plan: vault::VaultPlan,
+    ) -> Result<(), JiraError> {
+        if let Err(e) = plan.stage() {
+            plan.discard();
+            staged.iter().for_each(StagedFile::discard);
+            return Err(e);
+        }
+
+        let result = staged.iter()
+            .try_for_each(StagedFile::promote)
+            .and_then(|_| plan.commit());
+        if result.is_err() {
+            // Tentar concluir de novo na próxima abertura de um slot
+            **recovered = false;
+        }
+        result
+    }
+
+    // Aguardar trocas de chave em andamento e, na primeira vez, tratar uma troca
+    // interrompida. Não abrir slots (`open_backend`) com o guard em mãos.
+    fn staging_guard() -> Result<MutexGuard<'static, bool>, JiraError> {
+        let mut recovered = STAGING.lock().unwrap_or_else(|e| e.into_inner());
+        if !*recovered {
+            let pending = vault::has_pending();
+            Self::recover_staged(&goji_config_dir()?, pending)?;
+            if pending {
+                vault::commit_pending()?;
             }
+            *recovered = true;
         }
+        Ok(recovered)
+    }
+
+    // `<slot>.enc.new` que sobraram de uma troca de chave: com o cabeçalho pendente
+    // gravado todos estão completos e substituem os atuais; sem ele são descartados
+    fn recover_staged(dir: &Path, roll_forward: bool) -> Result<(), JiraError> {
+        let entries = match fs::read_dir(dir) {
+            Ok(entries) => entries,
+            Err(ref e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+            Err(e) => return Err(JiraError::InvalidConfig(format!("Erro ao ler diretório de configuração: {}", e))),
+        };
+
+        let staged: Vec<StagedFile> = entries
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| {
+                let name = entry.file_name().to_str()?.strip_suffix(".enc.new")?.to_string();
+                Some(StagedFile::new(dir.join(format!("{}.enc", name))))
+            })
+            .collect();
+
+        if roll_forward {
+            staged.iter().try_for_each(StagedFile::promote)
+        } else {
+            staged.iter().for_each(StagedFile::discard);
+            Ok(())
+        }
+    }
 
-        // Gerar nova chave
-        let mut key_bytes = [0u8; 32];
-        OsRng.fill_bytes(&mut key_bytes);
-        let key = Key::<Aes256Gcm>::from_slice(&key_bytes).clone();
-
-        // Salvar nova chave
-        let key_b64 = general_purpose::STANDARD.encode(&key_bytes);
-        
-        if let Some(entry) = keyring_entry {
-            let _ = entry.set_password(&key_b64);
-        } else if let Some(config_path) = file_path {
-            let key_path = config_path.with_extension("key");
-            if let Some(parent) = key_path.parent() {
-                let _ = fs::create_dir_all(parent);
+    fn open_backend(
+        backend: &StorageBackend,
+        service: &str,
+        username: &str,
+    ) -> Result<Box<dyn CredentialStore>, JiraError> {
+        drop(Self::staging_guard()?);
+
+        Ok(match backend {
+            StorageBackend::NativeKeyring => Box::new(KeyringStore::new(service, username)?),
+            StorageBackend::EncryptedFile => {
+                Box::new(EncryptedFileStore::new(Self::get_secure_file_path(username)?)?)
             }
-            if let Ok(mut file) = OpenOptions::new().write(true).create(true).truncate(true).open(&key_path) {
-                let _ = file.write_all(key_b64.as_bytes());
-                
-                // Definir permissões 600 para arquivo de chave
-                #[cfg(unix)]
-                {
-                    if let Ok(metadata) = file.metadata() {
-                        let mut permissions = metadata.permissions();
-                        permissions.set_mode(0o600);
-                        let _ = file.set_permissions(permissions);
-                    }
-                }
+            StorageBackend::PassphraseFile => {
+                Box::new(EncryptedFileStore::with_passphrase(Self::get_secure_file_path(username)?))
             }
+            StorageBackend::InMemory => Box::new(InMemoryStore::new()),
+        })
+    }
+
+    // Os arquivos criptografados (com ou sem senha mestra) e o keyring são as duas
+    // origens possíveis de dados "esquecidos" quando a detecção de ambiente muda
+    fn alternate_backend(current: StorageBackend) -> Option<StorageBackend> {
+        match current {
+            StorageBackend::NativeKeyring if vault::is_enabled() => Some(StorageBackend::PassphraseFile),
+            StorageBackend::NativeKeyring => Some(StorageBackend::EncryptedFile),
+            StorageBackend::EncryptedFile | StorageBackend::PassphraseFile => Some(StorageBackend::NativeKeyring),
+            StorageBackend::InMemory => None,
         }
+    }
 
-        Ok(key)
+    // Verificar presença sem abrir o store, que criaria uma chave nova no backend de origem
+    fn has_data(backend: StorageBackend, service: &str, username: &str) -> bool {
+        match backend {
+            StorageBackend::NativeKeyring => Entry::new(service, username)
+                .and_then(|entry| entry.get_password())
+                .is_ok(),
+            StorageBackend::EncryptedFile | StorageBackend::PassphraseFile => {
+                Self::get_secure_file_path(username)
+                    .map(|path| path.exists())
+                    .unwrap_or(false)
+            }
+            StorageBackend::InMemory => false,
+        }
     }
 
-    fn encrypt_data(&self, plaintext: &[u8]) -> Result<EncryptedData, JiraError> {
-        let cipher = Aes256Gcm::new(&self.cipher_key);
-        
-        let mut nonce_bytes = [0u8; 12];
-        OsRng.fill_bytes(&mut nonce_bytes);
-        let nonce = Nonce::from_slice(&nonce_bytes);
-        
-        let ciphertext = cipher.encrypt(nonce, plaintext)
-            .map_err(|_| JiraError::Encryption)?;
-        
-        Ok(EncryptedData {
-            nonce: nonce_bytes.to_vec(),
-            ciphertext,
-        })
+    fn has_key(backend: StorageBackend, service: &str, username: &str) -> bool {
+        match backend {
+            StorageBackend::NativeKeyring => Entry::new(service, &key_entry_name(username))
+                .and_then(|entry| entry.get_password())
+                .is_ok(),
+            StorageBackend::EncryptedFile => Self::get_secure_file_path(username)
+                .map(|path| path.with_extension("key").exists())
+                .unwrap_or(false),
+            // A chave vem da senha mestra; o cofre é verificado ao ler
+            StorageBackend::PassphraseFile | StorageBackend::InMemory => true,
+        }
+    }
+
+    pub(crate) fn get_secure_file_path(username: &str) -> Result<PathBuf, JiraError> {
+        Ok(goji_config_dir()?.join(format!("{}.enc", username)))
+    }
+}
+
+// Slot regravado ao lado do arquivo atual, até a troca de chave ser confirmada
+struct StagedFile {
+    path: PathBuf,
+    staged_path: PathBuf,
+}
+
+impl StagedFile {
+    fn new(path: PathBuf) -> Self {
+        Self {
+            staged_path: path.with_extension("enc.new"),
+            path,
+        }
     }
 
-    fn decrypt_data(&self, encrypted: &EncryptedData) -> Result<Vec<u8>, JiraError> {
-        let cipher = Aes256Gcm::new(&self.cipher_key);
-        let nonce = Nonce::from_slice(&encrypted.nonce);
-        
-        cipher.decrypt(nonce, encrypted.ciphertext.as_slice())
-            .map_err(|_| JiraError::Decryption)
+    fn promote(&self) -> Result<(), JiraError> {
+        fs::rename(&self.staged_path, &self.path)
+            .map_err(|e| JiraError::InvalidConfig(format!("Erro ao substituir {}: {}", self.path.display(), e)))
     }
-}
\ No newline at end of file
+
+    fn discard(&self) {
+        let _ = fs::remove_file(&self.staged_path);
+    }
+}
+
+impl CredentialStore for SecureStorage {
+    fn store(&self, data: &str) -> Result<(), JiraError> {
+        self.store.store(data)
+    }
+
+    fn retrieve(&self) -> Result<Option<String>, JiraError> {
+        self.store.retrieve()
+    }
+
+    fn delete(&self) -> Result<(), JiraError> {
+        self.store.delete()
+    }
+
+    fn backend(&self) -> StorageBackend {
+        self.store.backend()
+    }
+
+    fn delete_key(&self) -> Result<(), JiraError> {
+        self.store.delete_key()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn staged_file_replaces_original_only_on_promote() {
+        let dir = std::env::temp_dir().join(format!("goji-test-staged-{}", std::process::id()));
+        let path = dir.join("jira_config.enc");
+        let (old_key, new_key) = (Key::<Aes256Gcm>::from([1u8; 32]), Key::<Aes256Gcm>::from([2u8; 32]));
+
+        EncryptedFileStore::with_key(path.clone(), old_key).store("antigo").unwrap();
+
+        let file = StagedFile::new(path.clone());
+        EncryptedFileStore::with_key(file.staged_path.clone(), new_key).store("novo").unwrap();
+        assert_eq!(EncryptedFileStore::with_key(path.clone(), old_key).retrieve().unwrap().as_deref(), Some("antigo"));
+
+        file.discard();
+        assert!(!file.staged_path.exists());
+        assert_eq!(EncryptedFileStore::with_key(path.clone(), old_key).retrieve().unwrap().as_deref(), Some("antigo"));
+
+        EncryptedFileStore::with_key(file.staged_path.clone(), new_key).store("novo").unwrap();
+        file.promote().unwrap();
+        assert!(!file.staged_path.exists());
+        assert_eq!(EncryptedFileStore::with_key(path.clone(), new_key).retrieve().unwrap().as_deref(), Some("novo"));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    fn staged_slot(dir: &Path, slot: &str, old: &str, new: &str) -> StagedFile {
+        let (old_key, new_key) = (Key::<Aes256Gcm>::from([1u8; 32]), Key::<Aes256Gcm>::from([2u8; 32]));
+        let file = StagedFile::new(dir.join(format!("{}.enc", slot)));
+        EncryptedFileStore::with_key(file.path.clone(), old_key).store(old).unwrap();
+        EncryptedFileStore::with_key(file.staged_path.clone(), new_key).store(new).unwrap();
+        file
+    }
+
+    #[test]
+    fn interrupted_rekey_is_completed_with_pending_header() {
+        let dir = std::env::temp_dir().join(format!("goji-test-recover-{}", std::process::id()));
+        let new_key = Key::<Aes256Gcm>::from([2u8; 32]);
+
+        // Parou depois de promover um dos dois slots
+        let promoted = staged_slot(&dir, "jira_config", "antigo", "novo");
+        promoted.promote().unwrap();
+        let pending = staged_slot(&dir, "jira_oauth", "antigo", "novo");
+
+        SecureStorage::recover_staged(&dir, true).unwrap();
+        for file in [&promoted, &pending] {
+            assert!(!file.staged_path.exists());
+            assert_eq!(EncryptedFileStore::with_key(file.path.clone(), new_key).retrieve().unwrap().as_deref(), Some("novo"));
+        }
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn interrupted_staging_is_discarded_without_pending_header() {
+        let dir = std::env::temp_dir().join(format!("goji-test-discard-{}", std::process::id()));
+        let old_key = Key::<Aes256Gcm>::from([1u8; 32]);
+
+        let file = staged_slot(&dir, "jira_config", "antigo", "novo");
+        SecureStorage::recover_staged(&dir, false).unwrap();
+
+        assert!(!file.staged_path.exists());
+        assert_eq!(EncryptedFileStore::with_key(file.path.clone(), old_key).retrieve().unwrap().as_deref(), Some("antigo"));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
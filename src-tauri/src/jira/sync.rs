@@ -0,0 +1,421 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use chrono::{DateTime, Utc};
+use serde::{Serialize, Deserialize};
+use tokio::sync::Notify;
+use ts_rs::TS;
+use crate::jira::error::JiraError;
+use crate::jira::paths::goji_config_dir;
+use crate::jira::client::{JiraClient, JiraIssue};
+use crate::jira::profiles::{JiraProfile, ProfileManager, DEFAULT_PROFILE_ID};
+
+const DEFAULT_INTERVAL_MINUTES: u32 = 5;
+const MAX_INTERVAL_MINUTES: u32 = 24 * 60;
+
+// Primeira sincronização de um perfil: issues alteradas nesse período. Depois de
+// uma ausência maior que isso, a sincronização busca todas as issues do escopo.
+const INITIAL_WINDOW_MINUTES: i64 = 14 * 24 * 60;
+// Sobreposição entre buscas; mudanças repetidas são descartadas pelo `updated`
+const OVERLAP_MINUTES: i64 = 2;
+
+// Espera após falhas consecutivas: 30s, 1min, 2min... até 30min
+const BACKOFF_BASE_SECS: u64 = 30;
+const BACKOFF_MAX_SECS: u64 = 30 * 60;
+
+// Configuração do perfil ativo; cada perfil tem o próprio arquivo
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct SyncSettings {
+    #[serde(rename = "intervalMinutes")]
+    pub interval_minutes: u32,
+    // Chaves dos projetos acompanhados; vazio sincroniza as issues atribuídas ao usuário
+    #[serde(rename = "watchedProjects", default)]
+    pub watched_projects: Vec<String>,
+    #[serde(default)]
+    pub paused: bool,
+}
+
+impl Default for SyncSettings {
+    fn default() -> Self {
+        Self {
+            interval_minutes: DEFAULT_INTERVAL_MINUTES,
+            watched_projects: Vec::new(),
+            paused: false,
+        }
+    }
+}
+
+impl SyncSettings {
+    pub fn load(profile_id: &str) -> Result<Self, JiraError> {
+        let path = Self::get_settings_file_path(profile_id)?;
+
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = std::fs::read_to_string(&path)
+            .map_err(|e| JiraError::InvalidConfig(format!("Erro ao ler configuração de sincronização: {}", e)))?;
+
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    pub fn save(&self, profile_id: &str) -> Result<(), JiraError> {
+        self.validate()?;
+
+        let path = Self::get_settings_file_path(profile_id)?;
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| JiraError::InvalidConfig(format!("Erro ao criar diretório: {}", e)))?;
+        }
+
+        let content = serde_json::to_string_pretty(self)?;
+        std::fs::write(&path, content)
+            .map_err(|e| JiraError::InvalidConfig(format!("Erro ao salvar configuração de sincronização: {}", e)))
+    }
+
+    pub fn delete(profile_id: &str) -> Result<(), JiraError> {
+        match std::fs::remove_file(Self::get_settings_file_path(profile_id)?) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(JiraError::InvalidConfig(
+                format!("Erro ao remover configuração de sincronização: {}", e),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    // === MÉTODOS PRIVADOS ===
+
+    fn validate(&self) -> Result<(), JiraError> {
+        let mut errors = Vec::new();
+
+        if !(1..=MAX_INTERVAL_MINUTES).contains(&self.interval_minutes) {
+            errors.push(format!("intervalMinutes: Deve estar entre 1 e {}", MAX_INTERVAL_MINUTES));
+        }
+
+        for key in &self.watched_projects {
+            if key.is_empty() || !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+                errors.push(format!("watchedProjects: Chave de projeto inválida: {:?}", key));
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(JiraError::Validation(errors))
+        }
+    }
+
+    fn get_settings_file_path(profile_id: &str) -> Result<PathBuf, JiraError> {
+        Ok(goji_config_dir()?.join(JiraProfile::sync_settings_file(profile_id)))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub enum SyncPhase {
+    Idle,
+    Syncing,
+    Paused,
+    // Aguardando nova tentativa após falha
+    Backoff,
+    // Sem credenciais ou cofre bloqueado; tenta de novo no próximo ciclo
+    Waiting,
+}
+
+// Payload do evento `jira://sync-status`
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export)]
+pub struct SyncStatus {
+    pub phase: SyncPhase,
+    #[serde(rename = "lastSyncAt")]
+    #[ts(type = "string | null")]
+    pub last_sync_at: Option<DateTime<Utc>>,
+    #[serde(rename = "nextSyncAt")]
+    #[ts(type = "string | null")]
+    pub next_sync_at: Option<DateTime<Utc>>,
+    // Issues novas ou alteradas na última sincronização
+    #[serde(rename = "lastChanged")]
+    pub last_changed: u32,
+    #[serde(rename = "lastError")]
+    pub last_error: Option<String>,
+    #[serde(rename = "consecutiveFailures")]
+    pub consecutive_failures: u32,
+}
+
+pub enum SyncEvent {
+    IssueUpdated(JiraIssue),
+    Status(SyncStatus),
+}
+
+// Sincronização incremental em segundo plano: a cada intervalo busca as issues
+// alteradas desde a última execução e as incorpora ao cache local
+pub struct SyncEngine {
+    // Carregadas uma vez por perfil; o ciclo usa as do perfil ativo
+    settings: Mutex<HashMap<String, SyncSettings>>,
+    status: Mutex<SyncStatus>,
+    wake: Notify,
+}
+
+impl SyncEngine {
+    pub fn new() -> Self {
+        let profile_id = active_profile_id();
+        let settings = SyncSettings::load(&profile_id).unwrap_or_default();
+        let phase = if settings.paused { SyncPhase::Paused } else { SyncPhase::Idle };
+
+        Self {
+            settings: Mutex::new(HashMap::from([(profile_id, settings)])),
+            status: Mutex::new(SyncStatus {
+                phase,
+                last_sync_at: None,
+                next_sync_at: None,
+                last_changed: 0,
+                last_error: None,
+                consecutive_failures: 0,
+            }),
+            wake: Notify::new(),
+        }
+    }
+
+    pub fn status(&self) -> SyncStatus {
+        self.status.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    pub fn settings(&self, profile_id: &str) -> SyncSettings {
+        self.settings.lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .entry(profile_id.to_string())
+            .or_insert_with(|| SyncSettings::load(profile_id).unwrap_or_default())
+            .clone()
+    }
+
+    // Salvar e aplicar imediatamente (o ciclo em espera é acordado)
+    pub fn update_settings(&self, profile_id: &str, settings: SyncSettings) -> Result<SyncSettings, JiraError> {
+        settings.save(profile_id)?;
+        self.settings.lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(profile_id.to_string(), settings.clone());
+        self.wake.notify_one();
+        Ok(settings)
+    }
+
+    pub fn set_paused(&self, profile_id: &str, paused: bool) -> Result<SyncSettings, JiraError> {
+        let mut settings = self.settings(profile_id);
+        settings.paused = paused;
+        self.update_settings(profile_id, settings)
+    }
+
+    // Apagar a configuração de um perfil removido
+    pub fn delete_settings(&self, profile_id: &str) -> Result<(), JiraError> {
+        self.settings.lock().unwrap_or_else(|e| e.into_inner()).remove(profile_id);
+        SyncSettings::delete(profile_id)
+    }
+
+    // Sincronizar agora, sem esperar o intervalo nem o backoff
+    pub fn sync_now(&self) {
+        self.status.lock().unwrap_or_else(|e| e.into_inner()).consecutive_failures = 0;
+        self.wake.notify_one();
+    }
+
+    // Ciclo principal; `client` devolve o cliente do perfil ativo a cada execução
+    pub async fn run<C, E>(self: Arc<Self>, client: C, emit: E)
+    where
+        C: Fn() -> Result<Arc<JiraClient>, JiraError>,
+        E: Fn(SyncEvent),
+    {
+        let mut delay = Duration::ZERO;
+
+        loop {
+            if self.settings(&active_profile_id()).paused {
+                emit(SyncEvent::Status(self.update_status(|status| {
+                    status.phase = SyncPhase::Paused;
+                    status.next_sync_at = None;
+                })));
+                self.wake.notified().await;
+                // Retomada: sincronizar sem esperar o intervalo
+                delay = Duration::ZERO;
+                continue;
+            }
+
+            // Acordado antes do prazo por sync_now, retomada ou nova configuração
+            let _ = tokio::time::timeout(delay, self.wake.notified()).await;
+            // O perfil ativo pode ter mudado durante a espera
+            if self.settings(&active_profile_id()).paused {
+                continue;
+            }
+
+            emit(SyncEvent::Status(self.update_status(|status| status.phase = SyncPhase::Syncing)));
+
+            let (settings, result) = match client() {
+                Ok(client) => {
+                    let settings = self.settings(client.config_manager().profile_id());
+                    let result = Self::sync_once(&client, &settings, &emit).await;
+                    (settings, result)
+                }
+                Err(e) => (self.settings(&active_profile_id()), Err(e)),
+            };
+
+            let status = self.update_status(|status| match result {
+                Ok(changed) => {
+                    status.phase = SyncPhase::Idle;
+                    status.last_sync_at = Some(Utc::now());
+                    status.last_changed = changed;
+                    status.last_error = None;
+                    status.consecutive_failures = 0;
+                }
+                // Sem credenciais: não conta como falha de rede
+                Err(ref e) if matches!(e, JiraError::VaultLocked | JiraError::CredentialsUnrecoverable | JiraError::InvalidConfig(_)) => {
+                    status.phase = SyncPhase::Waiting;
+                    status.last_error = Some(e.to_string());
+                    status.consecutive_failures = 0;
+                }
+                Err(ref e) => {
+                    status.phase = SyncPhase::Backoff;
+                    status.last_error = Some(e.to_string());
+                    status.consecutive_failures += 1;
+                }
+            });
+
+            delay = next_delay(settings.interval_minutes, status.consecutive_failures);
+            let status = self.update_status(|status| {
+                status.next_sync_at = chrono::Duration::from_std(delay).ok().map(|d| Utc::now() + d);
+            });
+            emit(SyncEvent::Status(status));
+        }
+    }
+
+    // === MÉTODOS PRIVADOS ===
+
+    fn update_status<F>(&self, update: F) -> SyncStatus
+    where
+        F: FnOnce(&mut SyncStatus),
+    {
+        let mut status = self.status.lock().unwrap_or_else(|e| e.into_inner());
+        update(&mut status);
+        status.clone()
+    }
+
+    async fn sync_once<E>(client: &JiraClient, settings: &SyncSettings, emit: &E) -> Result<u32, JiraError>
+    where
+        E: Fn(SyncEvent),
+    {
+        if client.config_manager().get_config()?.is_none() {
+            return Err(JiraError::InvalidConfig("JIRA não configurado".to_string()));
+        }
+
+        let cache = client.cache()
+            .ok_or_else(|| JiraError::InvalidConfig("Cache local indisponível".to_string()))?;
+
+        let started_at = Utc::now();
+        let scope = sync_scope(&settings.watched_projects);
+        let jql = build_sync_jql(&scope, cache.last_sync(&scope)?, started_at);
+
+        // Gravar página a página: uma sincronização completa pode trazer projetos inteiros
+        let mut count = 0u32;
+        client.search_issues_with_text(&jql, None, |page, texts| {
+            let changed = cache.merge_issues(&page.issues)?;
+            cache.index_texts(&texts)?;
+
+            count += changed.len() as u32;
+            for issue in changed {
+                emit(SyncEvent::IssueUpdated(issue));
+            }
+            Ok(())
+        }).await?;
+
+        // Só depois de todas as páginas; uma falha no meio repete a mesma janela
+        cache.set_last_sync(&scope, started_at)?;
+        Ok(count)
+    }
+}
+
+impl Default for SyncEngine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// === FUNÇÕES PRIVADAS ===
+
+fn active_profile_id() -> String {
+    ProfileManager::load()
+        .map(|profiles| profiles.active_id().to_string())
+        .unwrap_or_else(|_| DEFAULT_PROFILE_ID.to_string())
+}
+
+// Issues acompanhadas: as atribuídas ao usuário ou as dos projetos escolhidos
+fn sync_scope(watched_projects: &[String]) -> String {
+    if watched_projects.is_empty() {
+        "assignee = currentUser()".to_string()
+    } else {
+        let keys: Vec<String> = watched_projects.iter().map(|key| format!("\"{}\"", key)).collect();
+        format!("project IN ({})", keys.join(", "))
+    }
+}
+
+// Datas absolutas na JQL usam o fuso do perfil do usuário no JIRA; o deslocamento
+// relativo ("-15m") não depende dele
+fn build_sync_jql(scope: &str, last_sync: Option<DateTime<Utc>>, now: DateTime<Utc>) -> String {
+    // Sem a última sincronização o cache está vazio: basta a janela inicial. Depois de
+    // uma ausência mais longa, limitar a janela deixaria issues do cache desatualizadas.
+    let window = match last_sync {
+        None => Some(INITIAL_WINDOW_MINUTES),
+        Some(last_sync) => {
+            let minutes = (now - last_sync).num_minutes() + OVERLAP_MINUTES;
+            (minutes <= INITIAL_WINDOW_MINUTES).then(|| minutes.max(OVERLAP_MINUTES))
+        }
+    };
+
+    match window {
+        Some(window) => format!("{} AND updated >= \"-{}m\" ORDER BY updated ASC", scope, window),
+        None => format!("{} ORDER BY updated ASC", scope),
+    }
+}
+
+fn next_delay(interval_minutes: u32, consecutive_failures: u32) -> Duration {
+    if consecutive_failures == 0 {
+        return Duration::from_secs(u64::from(interval_minutes) * 60);
+    }
+
+    let exponent = (consecutive_failures - 1).min(10);
+    Duration::from_secs((BACKOFF_BASE_SECS << exponent).min(BACKOFF_MAX_SECS))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration as ChronoDuration;
+
+    #[test]
+    fn first_sync_uses_initial_window() {
+        let jql = build_sync_jql(&sync_scope(&[]), None, Utc::now());
+        assert_eq!(
+            jql,
+            format!("assignee = currentUser() AND updated >= \"-{}m\" ORDER BY updated ASC", INITIAL_WINDOW_MINUTES)
+        );
+    }
+
+    #[test]
+    fn recent_sync_keeps_overlap() {
+        let now = Utc::now();
+        let jql = build_sync_jql(&sync_scope(&["GOJI".to_string()]), Some(now), now);
+        assert_eq!(jql, format!("project IN (\"GOJI\") AND updated >= \"-{}m\" ORDER BY updated ASC", OVERLAP_MINUTES));
+
+        let jql = build_sync_jql(&sync_scope(&[]), Some(now - ChronoDuration::minutes(60)), now);
+        assert!(jql.contains(&format!("\"-{}m\"", 60 + OVERLAP_MINUTES)));
+    }
+
+    #[test]
+    fn long_absence_resyncs_everything() {
+        let now = Utc::now();
+        let jql = build_sync_jql(&sync_scope(&[]), Some(now - ChronoDuration::days(30)), now);
+        assert_eq!(jql, "assignee = currentUser() ORDER BY updated ASC");
+    }
+
+    #[test]
+    fn settings_file_is_per_profile() {
+        assert_eq!(JiraProfile::sync_settings_file(DEFAULT_PROFILE_ID), "sync.json");
+        assert_eq!(JiraProfile::sync_settings_file("empresa-cloud"), "sync_empresa-cloud.json");
+    }
+}
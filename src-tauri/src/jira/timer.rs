@@ -0,0 +1,231 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use serde::{Serialize, Deserialize};
+use ts_rs::TS;
+use chrono::{DateTime, Utc};
+use crate::jira::error::JiraError;
+use crate::jira::paths::goji_config_dir;
+use crate::jira::profiles::JiraProfile;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub enum TimerState {
+    Running,
+    Paused,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct WorkTimer {
+    #[serde(rename = "issueKey")]
+    pub issue_key: String,
+    pub state: TimerState,
+    // Segundos acumulados em períodos já encerrados (antes da última pausa)
+    #[serde(rename = "accumulatedSeconds")]
+    #[ts(type = "number")]
+    pub accumulated_seconds: u64,
+    // Início do período atual, presente apenas quando o cronômetro está rodando
+    #[serde(rename = "runningSince")]
+    #[ts(type = "string | null")]
+    pub running_since: Option<DateTime<Utc>>,
+    // Primeiro início; usado como data do registro de trabalho
+    #[serde(rename = "startedAt")]
+    #[ts(type = "string")]
+    pub started_at: DateTime<Utc>,
+    // Total calculado no momento da consulta (não persistido)
+    #[serde(rename = "elapsedSeconds")]
+    #[serde(default, skip_deserializing)]
+    #[ts(type = "number")]
+    pub elapsed_seconds: u64,
+}
+
+impl WorkTimer {
+    fn new(issue_key: &str, now: DateTime<Utc>) -> Self {
+        Self {
+            issue_key: issue_key.to_string(),
+            state: TimerState::Running,
+            accumulated_seconds: 0,
+            running_since: Some(now),
+            started_at: now,
+            elapsed_seconds: 0,
+        }
+    }
+
+    pub fn elapsed_at(&self, now: DateTime<Utc>) -> u64 {
+        let current = self.running_since
+            .map(|since| (now - since).num_seconds().max(0) as u64)
+            .unwrap_or(0);
+        self.accumulated_seconds + current
+    }
+
+    fn pause_at(&mut self, now: DateTime<Utc>) {
+        self.accumulated_seconds = self.elapsed_at(now);
+        self.running_since = None;
+        self.state = TimerState::Paused;
+    }
+
+    fn with_elapsed(mut self, now: DateTime<Utc>) -> Self {
+        self.elapsed_seconds = self.elapsed_at(now);
+        self
+    }
+}
+
+// Cronômetros de trabalho por issue de um perfil, persistidos no diretório de
+// configuração. Cronômetros rodando continuam contando enquanto o aplicativo está fechado.
+pub struct WorkTimerManager {
+    file_path: PathBuf,
+    timers: BTreeMap<String, WorkTimer>,
+}
+
+impl WorkTimerManager {
+    pub fn load(profile_id: &str) -> Result<Self, JiraError> {
+        let file_path = Self::get_timers_file_path(profile_id)?;
+
+        let timers = match fs::read(&file_path) {
+            Ok(data) => serde_json::from_slice(&data)?,
+            Err(ref e) if e.kind() == std::io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(e) => return Err(JiraError::InvalidConfig(format!("Erro ao ler cronômetros: {}", e))),
+        };
+
+        Ok(Self { file_path, timers })
+    }
+
+    pub fn list(&self) -> Vec<WorkTimer> {
+        let now = Utc::now();
+        self.timers.values().cloned().map(|t| t.with_elapsed(now)).collect()
+    }
+
+    // Iniciar um novo cronômetro ou retomar um pausado
+    pub fn start(&mut self, issue_key: &str) -> Result<WorkTimer, JiraError> {
+        let now = Utc::now();
+        let timer = self.timers.entry(issue_key.to_string())
+            .or_insert_with(|| WorkTimer::new(issue_key, now));
+
+        if timer.state == TimerState::Paused {
+            timer.state = TimerState::Running;
+            timer.running_since = Some(now);
+        }
+
+        let timer = timer.clone().with_elapsed(now);
+        self.save()?;
+        Ok(timer)
+    }
+
+    pub fn pause(&mut self, issue_key: &str) -> Result<WorkTimer, JiraError> {
+        let now = Utc::now();
+        let timer = self.timers.get_mut(issue_key)
+            .ok_or_else(|| Self::not_found(issue_key))?;
+
+        if timer.state == TimerState::Running {
+            timer.pause_at(now);
+        }
+
+        let timer = timer.clone().with_elapsed(now);
+        self.save()?;
+        Ok(timer)
+    }
+
+    // Encerrar o cronômetro, retornando o estado final com o tempo acumulado
+    pub fn stop(&mut self, issue_key: &str) -> Result<WorkTimer, JiraError> {
+        let now = Utc::now();
+        let mut timer = self.timers.remove(issue_key)
+            .ok_or_else(|| Self::not_found(issue_key))?;
+
+        timer.pause_at(now);
+        self.save()?;
+        Ok(timer.with_elapsed(now))
+    }
+
+    // Recolocar um cronômetro encerrado (ex.: quando o envio do registro falha).
+    // Se outro já foi iniciado para a issue nesse meio tempo, o tempo é somado a ele.
+    pub fn restore(&mut self, timer: WorkTimer) -> Result<(), JiraError> {
+        match self.timers.get_mut(&timer.issue_key) {
+            Some(current) => {
+                current.accumulated_seconds += timer.accumulated_seconds;
+                current.started_at = current.started_at.min(timer.started_at);
+            }
+            None => {
+                self.timers.insert(timer.issue_key.clone(), timer);
+            }
+        }
+        self.save()
+    }
+
+    // Apagar os cronômetros de um perfil removido
+    pub fn delete(profile_id: &str) -> Result<(), JiraError> {
+        match fs::remove_file(Self::get_timers_file_path(profile_id)?) {
+            Err(ref e) if e.kind() != std::io::ErrorKind::NotFound => {
+                Err(JiraError::InvalidConfig(format!("Erro ao remover cronômetros: {}", e)))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    // === MÉTODOS PRIVADOS ===
+
+    fn save(&self) -> Result<(), JiraError> {
+        if let Some(parent) = self.file_path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| JiraError::InvalidConfig(format!("Erro ao criar diretório: {}", e)))?;
+        }
+
+        let data = serde_json::to_vec_pretty(&self.timers)?;
+        fs::write(&self.file_path, data)
+            .map_err(|e| JiraError::InvalidConfig(format!("Erro ao salvar cronômetros: {}", e)))
+    }
+
+    fn get_timers_file_path(profile_id: &str) -> Result<PathBuf, JiraError> {
+        Ok(goji_config_dir()?.join(JiraProfile::timers_file(profile_id)))
+    }
+
+    fn not_found(issue_key: &str) -> JiraError {
+        JiraError::InvalidConfig(format!("Nenhum cronômetro ativo para {}", issue_key))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manager(name: &str) -> WorkTimerManager {
+        WorkTimerManager {
+            file_path: std::env::temp_dir().join(format!("goji-test-{}-{}.json", name, std::process::id())),
+            timers: BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn round_trips_camel_case_json() {
+        let stored = r#"{"issueKey":"ABC-1","state":"Paused","accumulatedSeconds":90,"runningSince":null,"startedAt":"2024-01-01T10:00:00Z"}"#;
+
+        let timer: WorkTimer = serde_json::from_str(stored).unwrap();
+        assert_eq!(timer.issue_key, "ABC-1");
+        assert_eq!(timer.accumulated_seconds, 90);
+
+        let json = serde_json::to_value(&timer).unwrap();
+        assert_eq!(json["issueKey"], "ABC-1");
+        assert_eq!(json["accumulatedSeconds"], 90);
+        assert_eq!(json["startedAt"], "2024-01-01T10:00:00Z");
+    }
+
+    #[test]
+    fn restore_merges_into_timer_started_meanwhile() {
+        let mut timers = manager("restore");
+
+        timers.start("ABC-1").unwrap();
+        timers.pause("ABC-1").unwrap();
+        timers.timers.get_mut("ABC-1").unwrap().accumulated_seconds = 600;
+        let stopped = timers.stop("ABC-1").unwrap();
+
+        // Novo cronômetro iniciado enquanto o registro era enviado
+        timers.start("ABC-1").unwrap();
+        timers.restore(stopped).unwrap();
+
+        let current = &timers.timers["ABC-1"];
+        assert_eq!(current.state, TimerState::Running);
+        assert_eq!(current.accumulated_seconds, 600);
+
+        let _ = fs::remove_file(&timers.file_path);
+    }
+}
@@ -0,0 +1,372 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use serde::{Serialize, Deserialize};
+use ts_rs::TS;
+use chrono::{DateTime, Duration, Utc};
+use aes_gcm::{Aes256Gcm, Key, Nonce, KeyInit};
+use aes_gcm::aead::{Aead, OsRng};
+use argon2::{Algorithm, Argon2, Params, Version};
+use rand::RngCore;
+use base64::{Engine as _, engine::general_purpose};
+use crate::jira::error::JiraError;
+use crate::jira::paths::goji_config_dir;
+
+// Cofre protegido por senha mestra: a chave AES é derivada da senha com Argon2id
+// e existe apenas em memória enquanto o cofre está desbloqueado.
+const VAULT_VERSION: u32 = 1;
+// Texto conhecido cifrado no cabeçalho para validar a senha no desbloqueio
+const VERIFIER_PLAINTEXT: &[u8] = b"goji-vault-v1";
+const MIN_PASSPHRASE_LEN: usize = 8;
+pub const DEFAULT_AUTO_LOCK_MINUTES: u32 = 15;
+
+// Parâmetros recomendados pela OWASP para Argon2id (19 MiB, 2 iterações)
+const ARGON2_M_COST: u32 = 19_456;
+const ARGON2_T_COST: u32 = 2;
+const ARGON2_P_COST: u32 = 1;
+
+#[derive(Serialize, Deserialize)]
+struct KdfParams {
+    salt: String,
+    m_cost: u32,
+    t_cost: u32,
+    p_cost: u32,
+}
+
+#[derive(Serialize, Deserialize)]
+struct VaultHeader {
+    version: u32,
+    kdf: KdfParams,
+    verifier_nonce: Vec<u8>,
+    verifier: Vec<u8>,
+    // 0 desativa o bloqueio automático
+    auto_lock_minutes: u32,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export)]
+pub struct VaultStatus {
+    pub enabled: bool,
+    pub unlocked: bool,
+    #[serde(rename = "autoLockMinutes")]
+    pub auto_lock_minutes: u32,
+    // Momento em que o cofre será bloqueado se não for usado
+    #[serde(rename = "locksAt")]
+    #[ts(type = "string | null")]
+    pub locks_at: Option<DateTime<Utc>>,
+}
+
+struct UnlockedKey {
+    key: Key<Aes256Gcm>,
+    last_used: DateTime<Utc>,
+}
+
+static UNLOCKED: OnceLock<Mutex<Option<UnlockedKey>>> = OnceLock::new();
+
+fn unlocked_state() -> &'static Mutex<Option<UnlockedKey>> {
+    UNLOCKED.get_or_init(|| Mutex::new(None))
+}
+
+pub fn is_enabled() -> bool {
+    get_header_path().map(|path| path.exists()).unwrap_or(false)
+}
+
+pub fn status() -> VaultStatus {
+    let header = load_header().ok();
+    let auto_lock_minutes = header.as_ref()
+        .map(|h| h.auto_lock_minutes)
+        .unwrap_or(DEFAULT_AUTO_LOCK_MINUTES);
+
+    let mut state = unlocked_state().lock().unwrap_or_else(|e| e.into_inner());
+    expire_if_idle(&mut state, auto_lock_minutes);
+
+    VaultStatus {
+        enabled: header.is_some(),
+        unlocked: state.is_some(),
+        auto_lock_minutes,
+        locks_at: state.as_ref()
+            .filter(|_| auto_lock_minutes > 0)
+            .map(|u| u.last_used + Duration::minutes(auto_lock_minutes as i64)),
+    }
+}
+
+// Chave do cofre desbloqueado; cada uso adia o bloqueio automático
+pub fn current_key() -> Result<Key<Aes256Gcm>, JiraError> {
+    let header = load_header()?;
+    let mut state = unlocked_state().lock().unwrap_or_else(|e| e.into_inner());
+    expire_if_idle(&mut state, header.auto_lock_minutes);
+
+    let unlocked = state.as_mut().ok_or(JiraError::VaultLocked)?;
+    unlocked.last_used = Utc::now();
+    Ok(unlocked.key)
+}
+
+pub fn unlock(passphrase: &str) -> Result<(), JiraError> {
+    let header = load_header()?;
+    let key = derive_key(passphrase, &header.kdf)?;
+    verify(&header, &key)?;
+    set_unlocked(key);
+    Ok(())
+}
+
+pub fn lock() {
+    *unlocked_state().lock().unwrap_or_else(|e| e.into_inner()) = None;
+}
+
+pub fn set_auto_lock_minutes(minutes: u32) -> Result<(), JiraError> {
+    let mut header = load_header()?;
+    header.auto_lock_minutes = minutes;
+    save_header(&header)
+}
+
+// Cabeçalho e chave do cofre a ser criado; a migração dos dados é feita pelo
+// SecureStorage, que chama `commit` depois de preparar os arquivos com `key()`
+pub(crate) fn prepare_create(passphrase: &str) -> Result<VaultPlan, JiraError> {
+    if is_enabled() {
+        return Err(JiraError::InvalidConfig("Senha mestra já está ativada".to_string()));
+    }
+
+    let (header, key) = new_header(passphrase, DEFAULT_AUTO_LOCK_MINUTES)?;
+    Ok(VaultPlan { header, key })
+}
+
+// Novo salt e chave para a nova senha; `commit` só depois de regravar os dados com `key()`
+pub(crate) fn prepare_rekey(new_passphrase: &str) -> Result<VaultPlan, JiraError> {
+    let current = load_header()?;
+    let (header, key) = new_header(new_passphrase, current.auto_lock_minutes)?;
+    Ok(VaultPlan { header, key })
+}
+
+// Cabeçalho ainda não gravado; até o `commit` a senha anterior (ou o backend
+// anterior) continua valendo
+pub(crate) struct VaultPlan {
+    header: VaultHeader,
+    key: Key<Aes256Gcm>,
+}
+
+impl VaultPlan {
+    pub fn key(&self) -> Key<Aes256Gcm> {
+        self.key
+    }
+
+    // Gravar o cabeçalho novo como pendente (`vault.json.new`), depois de todos os
+    // dados preparados: a partir daqui uma troca interrompida é concluída, não desfeita
+    pub fn stage(&self) -> Result<(), JiraError> {
+        write_header(&get_pending_header_path()?, &self.header)
+    }
+
+    pub fn discard(&self) {
+        if let Ok(path) = get_pending_header_path() {
+            let _ = fs::remove_file(path);
+        }
+    }
+
+    pub fn commit(self) -> Result<(), JiraError> {
+        commit_pending()?;
+        set_unlocked(self.key);
+        Ok(())
+    }
+}
+
+// Troca de chave interrompida depois de `VaultPlan::stage`
+pub(crate) fn has_pending() -> bool {
+    get_pending_header_path().map(|path| path.exists()).unwrap_or(false)
+}
+
+// Colocar o cabeçalho pendente no lugar; o cofre continua bloqueado
+pub(crate) fn commit_pending() -> Result<(), JiraError> {
+    fs::rename(get_pending_header_path()?, get_header_path()?)
+        .map_err(|e| JiraError::InvalidConfig(format!("Erro ao salvar cofre: {}", e)))
+}
+
+// === FUNÇÕES PRIVADAS ===
+
+fn new_header(passphrase: &str, auto_lock_minutes: u32) -> Result<(VaultHeader, Key<Aes256Gcm>), JiraError> {
+    if passphrase.chars().count() < MIN_PASSPHRASE_LEN {
+        return Err(JiraError::InvalidConfig(format!(
+            "Senha mestra deve ter pelo menos {} caracteres",
+            MIN_PASSPHRASE_LEN,
+        )));
+    }
+
+    let mut salt = [0u8; 16];
+    OsRng.fill_bytes(&mut salt);
+
+    let kdf = KdfParams {
+        salt: general_purpose::STANDARD.encode(salt),
+        m_cost: ARGON2_M_COST,
+        t_cost: ARGON2_T_COST,
+        p_cost: ARGON2_P_COST,
+    };
+    let key = derive_key(passphrase, &kdf)?;
+
+    let mut nonce = [0u8; 12];
+    OsRng.fill_bytes(&mut nonce);
+    let verifier = Aes256Gcm::new(&key)
+        .encrypt(Nonce::from_slice(&nonce), VERIFIER_PLAINTEXT)
+        .map_err(|_| JiraError::Encryption)?;
+
+    let header = VaultHeader {
+        version: VAULT_VERSION,
+        kdf,
+        verifier_nonce: nonce.to_vec(),
+        verifier,
+        auto_lock_minutes,
+    };
+
+    Ok((header, key))
+}
+
+fn derive_key(passphrase: &str, kdf: &KdfParams) -> Result<Key<Aes256Gcm>, JiraError> {
+    let salt = general_purpose::STANDARD.decode(&kdf.salt)?;
+    let params = Params::new(kdf.m_cost, kdf.t_cost, kdf.p_cost, Some(32))
+        .map_err(|e| JiraError::InvalidConfig(format!("Parâmetros Argon2 inválidos: {}", e)))?;
+
+    let mut key_bytes = [0u8; 32];
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password_into(passphrase.as_bytes(), &salt, &mut key_bytes)
+        .map_err(|_| JiraError::Encryption)?;
+
+    Ok(*Key::<Aes256Gcm>::from_slice(&key_bytes))
+}
+
+fn verify(header: &VaultHeader, key: &Key<Aes256Gcm>) -> Result<(), JiraError> {
+    // from_slice entra em pânico com tamanhos diferentes de 12 bytes (vault.json editado)
+    if header.verifier_nonce.len() != 12 {
+        return Err(JiraError::Decryption);
+    }
+
+    let plaintext = Aes256Gcm::new(key)
+        .decrypt(Nonce::from_slice(&header.verifier_nonce), header.verifier.as_slice())
+        .map_err(|_| JiraError::InvalidPassphrase)?;
+
+    if plaintext != VERIFIER_PLAINTEXT {
+        return Err(JiraError::InvalidPassphrase);
+    }
+    Ok(())
+}
+
+fn set_unlocked(key: Key<Aes256Gcm>) {
+    *unlocked_state().lock().unwrap_or_else(|e| e.into_inner()) = Some(UnlockedKey {
+        key,
+        last_used: Utc::now(),
+    });
+}
+
+fn expire_if_idle(state: &mut Option<UnlockedKey>, auto_lock_minutes: u32) {
+    let idle = state.as_ref().is_some_and(|u| {
+        auto_lock_minutes > 0 && Utc::now() - u.last_used >= Duration::minutes(auto_lock_minutes as i64)
+    });
+    if idle {
+        *state = None;
+    }
+}
+
+fn load_header() -> Result<VaultHeader, JiraError> {
+    let path = get_header_path()?;
+    let data = fs::read(&path).map_err(|e| match e.kind() {
+        std::io::ErrorKind::NotFound => JiraError::InvalidConfig("Senha mestra não está ativada".to_string()),
+        _ => JiraError::InvalidConfig(format!("Erro ao ler cofre: {}", e)),
+    })?;
+
+    let header: VaultHeader = serde_json::from_slice(&data)?;
+    if header.version != VAULT_VERSION {
+        return Err(JiraError::InvalidConfig(format!("Versão do cofre não suportada: {}", header.version)));
+    }
+    Ok(header)
+}
+
+fn save_header(header: &VaultHeader) -> Result<(), JiraError> {
+    write_header(&get_header_path()?, header)
+}
+
+fn write_header(path: &Path, header: &VaultHeader) -> Result<(), JiraError> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| JiraError::InvalidConfig(format!("Erro ao criar diretório: {}", e)))?;
+    }
+
+    // Gravar em arquivo temporário e renomear para não corromper o cabeçalho
+    let tmp_path = path.with_extension("json.tmp");
+    fs::write(&tmp_path, serde_json::to_vec_pretty(header)?)
+        .map_err(|e| JiraError::InvalidConfig(format!("Erro ao salvar cofre: {}", e)))?;
+    fs::rename(&tmp_path, path)
+        .map_err(|e| JiraError::InvalidConfig(format!("Erro ao salvar cofre: {}", e)))
+}
+
+fn get_header_path() -> Result<PathBuf, JiraError> {
+    Ok(goji_config_dir()?.join("vault.json"))
+}
+
+fn get_pending_header_path() -> Result<PathBuf, JiraError> {
+    Ok(goji_config_dir()?.join("vault.json.new"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PASSPHRASE: &str = "senha mestra";
+
+    #[test]
+    fn argon2_key_depends_on_passphrase_and_salt() {
+        let (header, key) = new_header(PASSPHRASE, DEFAULT_AUTO_LOCK_MINUTES).unwrap();
+        assert_eq!(derive_key(PASSPHRASE, &header.kdf).unwrap(), key);
+        assert_ne!(derive_key("outra senha", &header.kdf).unwrap(), key);
+
+        let (other, other_key) = new_header(PASSPHRASE, DEFAULT_AUTO_LOCK_MINUTES).unwrap();
+        assert_ne!(other.kdf.salt, header.kdf.salt);
+        assert_ne!(other_key, key);
+    }
+
+    #[test]
+    fn wrong_passphrase_is_rejected() {
+        let (header, _) = new_header(PASSPHRASE, DEFAULT_AUTO_LOCK_MINUTES).unwrap();
+        let wrong = derive_key("senha errada", &header.kdf).unwrap();
+        assert!(matches!(verify(&header, &wrong), Err(JiraError::InvalidPassphrase)));
+
+        assert!(matches!(new_header("curta", DEFAULT_AUTO_LOCK_MINUTES), Err(JiraError::InvalidConfig(_))));
+    }
+
+    #[test]
+    fn truncated_verifier_nonce_does_not_panic() {
+        let (mut header, key) = new_header(PASSPHRASE, DEFAULT_AUTO_LOCK_MINUTES).unwrap();
+        header.verifier_nonce.truncate(5);
+        assert!(matches!(verify(&header, &key), Err(JiraError::Decryption)));
+    }
+
+    #[test]
+    fn rekey_replaces_salt_and_keeps_auto_lock() {
+        let (current, current_key) = new_header(PASSPHRASE, 5).unwrap();
+        let (rekeyed, new_key) = new_header("nova senha mestra", current.auto_lock_minutes).unwrap();
+
+        assert_eq!(rekeyed.auto_lock_minutes, 5);
+        assert_ne!(new_key, current_key);
+        assert!(verify(&rekeyed, &new_key).is_ok());
+        assert!(matches!(
+            verify(&rekeyed, &derive_key(PASSPHRASE, &rekeyed.kdf).unwrap()),
+            Err(JiraError::InvalidPassphrase),
+        ));
+    }
+
+    #[test]
+    fn idle_vault_locks_automatically() {
+        let unlocked = |minutes_ago: i64| Some(UnlockedKey {
+            key: Key::<Aes256Gcm>::from([7u8; 32]),
+            last_used: Utc::now() - Duration::minutes(minutes_ago),
+        });
+
+        let mut state = unlocked(20);
+        expire_if_idle(&mut state, 15);
+        assert!(state.is_none());
+
+        let mut state = unlocked(10);
+        expire_if_idle(&mut state, 15);
+        assert!(state.is_some());
+
+        // 0 desativa o bloqueio automático
+        let mut state = unlocked(600);
+        expire_if_idle(&mut state, 0);
+        assert!(state.is_some());
+    }
+}
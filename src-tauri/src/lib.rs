@@ -1,8 +1,15 @@
 // Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
 
 mod jira;
-use jira::{config_manager::{JiraConfigManager, JiraConfig}, client::JiraClient};
+mod state;
+use jira::{config_manager::{JiraConfigManager, JiraConfig, JiraAuthMode, JiraApiVersion}, client::{JiraUser, JiraProject, JiraIssue, JiraIssuePage, JiraIssueCount, JiraIssueDetail, JiraCreateMetaIssueType, JiraFieldMeta, JiraIssueDraft, JiraCreatedIssue, JiraTransition, JiraComment, JiraWorklog, JiraWorklogInput}, timer::WorkTimer, request::{self, RateLimitStatus}, oauth::{self, OAuthClientConfig, OAuthSessionStore}, profiles::{ProfileManager, JiraProfile, JiraProfileList}, environment::{EnvironmentInfo, StoragePreference}, keyring_probe, diagnostics::{DiagnosticsReport, DiagnosticsFormat}, secure_storage::{SecureStorage, StorageMigrationStatus, StorageMigrationReport}, vault::{self, VaultStatus}, cache::{JiraCache, JiraCached, CacheKind, CacheRefreshed, LocalIssueMatch, LOCAL_SEARCH_LIMIT}, sync::{SyncEvent, SyncSettings, SyncStatus}, paths::goji_config_dir};
+use tauri_plugin_opener::OpenerExt;
+use jira::error::{JiraError, CommandError};
+use tauri::ipc::Channel;
+use tauri::{Emitter, Manager};
+use std::future::Future;
 use chrono::Utc;
+use state::AppState;
 
 #[tauri::command]
 fn save_theme_to_config(theme: &str) -> Result<(), String> {
@@ -54,30 +61,41 @@ async fn save_jira_config(
     url: String,
     email: String,
     token: String,
-) -> Result<(), String> {
+    auth_mode: Option<JiraAuthMode>,
+    api_version: Option<JiraApiVersion>,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), CommandError> {
     let config = JiraConfig {
         url: url.trim_end_matches('/').to_string(),
         email,
         token,
+        auth_mode: auth_mode.unwrap_or_default(),
+        api_version,
+        api_url: None,
         created_at: Utc::now(),
         last_used: None,
     };
 
-    let manager = JiraConfigManager::new()
-        .map_err(|e| format!("Erro ao inicializar gerenciador: {:?}", e))?;
+    // Novas credenciais: o próximo comando recria o cliente
+    state.invalidate();
+    let client = state.client()
+        .map_err(|e| CommandError::with_context("Erro ao inicializar gerenciador", e))?;
     
-    manager.save_config(&config)
-        .map_err(|e| format!("Erro ao salvar configuração: {:?}", e))
+    client.config_manager().save_config(&config)
+        .map_err(|e| CommandError::with_context("Erro ao salvar configuração", e))?;
+
+    state.sync().sync_now();
+    Ok(())
 }
 
 #[tauri::command]
-async fn get_jira_config() -> Result<Option<JiraConfig>, String> {
-    let manager = JiraConfigManager::new()
-        .map_err(|e| format!("Erro ao inicializar gerenciador: {:?}", e))?;
+async fn get_jira_config(state: tauri::State<'_, AppState>) -> Result<Option<JiraConfig>, CommandError> {
+    let client = state.client()
+        .map_err(|e| CommandError::with_context("Erro ao inicializar gerenciador", e))?;
     
     // Não retornar o token por segurança - mascarar
-    if let Some(mut config) = manager.get_config()
-        .map_err(|e| format!("Erro ao recuperar configuração: {:?}", e))? {
+    if let Some(mut config) = client.config_manager().get_config()
+        .map_err(|e| CommandError::with_context("Erro ao recuperar configuração", e))? {
         
         config.token = "••••••••••••••••".to_string(); // Mascarar token
         Ok(Some(config))
@@ -87,79 +105,692 @@ async fn get_jira_config() -> Result<Option<JiraConfig>, String> {
 }
 
 #[tauri::command]
-async fn test_jira_connection() -> Result<bool, String> {
-    let manager = JiraConfigManager::new()
-        .map_err(|e| format!("Erro ao inicializar gerenciador: {:?}", e))?;
+async fn test_jira_connection(state: tauri::State<'_, AppState>) -> Result<bool, CommandError> {
+    let client = state.client()
+        .map_err(|e| CommandError::with_context("Erro ao inicializar gerenciador", e))?;
     
-    manager.test_config().await
-        .map_err(|e| format!("Erro ao testar conexão: {:?}", e))
+    // No modo OAuth, renovar o access token antes de testar
+    client.auth().get_auth_header().await
+        .map_err(|e| CommandError::with_context("Erro ao renovar autenticação", e))?;
+
+    client.config_manager().test_config().await
+        .map_err(|e| CommandError::with_context("Erro ao testar conexão", e))
 }
 
 #[tauri::command]
-async fn clear_jira_config() -> Result<(), String> {
+async fn clear_jira_config(state: tauri::State<'_, AppState>) -> Result<(), CommandError> {
     let manager = JiraConfigManager::new()
-        .map_err(|e| format!("Erro ao inicializar gerenciador: {:?}", e))?;
+        .map_err(|e| CommandError::with_context("Erro ao inicializar gerenciador", e))?;
+    state.invalidate();
     
     manager.clear_config()
-        .map_err(|e| format!("Erro ao limpar configuração: {:?}", e))
+        .map_err(|e| CommandError::with_context("Erro ao limpar configuração", e))?;
+
+    OAuthSessionStore::for_profile(manager.profile_id())
+        .and_then(|store| store.clear())
+        .map_err(|e| CommandError::with_context("Erro ao limpar sessão OAuth", e))?;
+
+    JiraCache::open(manager.profile_id())
+        .and_then(|cache| cache.clear())
+        .map_err(|e| CommandError::with_context("Erro ao limpar cache local", e))
 }
 
+// Login OAuth 2.0 (3LO): abre o navegador e aguarda a callback no loopback
 #[tauri::command]
-async fn has_jira_config() -> Result<bool, String> {
-    let manager = JiraConfigManager::new()
-        .map_err(|e| format!("Erro ao inicializar gerenciador: {:?}", e))?;
+async fn start_jira_oauth_login(
+    app: tauri::AppHandle,
+    client: OAuthClientConfig,
+    site_url: Option<String>,
+    state: tauri::State<'_, AppState>,
+) -> Result<JiraConfig, CommandError> {
+    let profiles = ProfileManager::load()
+        .map_err(|e| CommandError::with_context("Erro ao carregar perfis", e))?;
+
+    let pending = oauth::begin_authorization(client).await
+        .map_err(|e| CommandError::with_context("Erro ao iniciar login OAuth", e))?;
+
+    app.opener().open_url(pending.authorize_url(), None::<&str>)
+        .map_err(|e| CommandError::with_context(
+            "Erro ao abrir o navegador",
+            JiraError::OAuth(e.to_string()),
+        ))?;
+
+    let mut config = pending.complete(profiles.active_id(), site_url.as_deref()).await
+        .map_err(|e| CommandError::with_context("Erro no login OAuth", e))?;
+    state.invalidate();
+    state.sync().sync_now();
+
+    config.token = "••••••••••••••••".to_string(); // Mascarar token
+    Ok(config)
+}
+
+// Perfis: cada um com credenciais próprias; os comandos usam o perfil ativo
+#[tauri::command]
+fn list_jira_profiles() -> Result<JiraProfileList, CommandError> {
+    let profiles = ProfileManager::load()
+        .map_err(|e| CommandError::with_context("Erro ao carregar perfis", e))?;
+
+    Ok(profiles.list())
+}
+
+#[tauri::command]
+fn create_jira_profile(name: String) -> Result<JiraProfile, CommandError> {
+    ProfileManager::update(|profiles| profiles.create(&name))
+        .map_err(|e| CommandError::with_context("Erro ao criar perfil", e))
+}
+
+#[tauri::command]
+fn rename_jira_profile(profile_id: String, name: String) -> Result<JiraProfile, CommandError> {
+    ProfileManager::update(|profiles| profiles.rename(&profile_id, &name))
+        .map_err(|e| CommandError::with_context("Erro ao renomear perfil", e))
+}
+
+#[tauri::command]
+fn delete_jira_profile(profile_id: String, state: tauri::State<'_, AppState>) -> Result<(), CommandError> {
+    ProfileManager::update(|profiles| profiles.delete(&profile_id))
+        .map_err(|e| CommandError::with_context("Erro ao remover perfil", e))?;
+    state.invalidate();
+
+    state.delete_timers(&profile_id)
+        .map_err(|e| CommandError::with_context("Erro ao remover cronômetros do perfil", e))?;
+    state.sync().delete_settings(&profile_id)
+        .map_err(|e| CommandError::with_context("Erro ao remover configuração de sincronização do perfil", e))?;
+
+    // Apagar as credenciais do perfil removido
+    JiraConfigManager::for_profile(&profile_id)
+        .and_then(|manager| match manager.clear_config() {
+            Err(JiraError::Keyring(keyring::Error::NoEntry)) => Ok(()),
+            result => result,
+        })
+        .map_err(|e| CommandError::with_context("Erro ao limpar configuração do perfil", e))?;
+
+    OAuthSessionStore::for_profile(&profile_id)
+        .and_then(|store| store.clear())
+        .map_err(|e| CommandError::with_context("Erro ao limpar sessão OAuth do perfil", e))?;
+
+    JiraCache::open(&profile_id)
+        .and_then(|cache| cache.clear())
+        .map_err(|e| CommandError::with_context("Erro ao limpar cache local do perfil", e))
+}
+
+#[tauri::command]
+fn activate_jira_profile(profile_id: String, state: tauri::State<'_, AppState>) -> Result<JiraProfile, CommandError> {
+    let profile = ProfileManager::update(|profiles| profiles.activate(&profile_id))
+        .map_err(|e| CommandError::with_context("Erro ao ativar perfil", e))?;
+
+    // O cliente em cache pertence ao perfil anterior
+    state.invalidate();
+    state.sync().sync_now();
+    Ok(profile)
+}
+
+#[tauri::command]
+async fn has_jira_config(state: tauri::State<'_, AppState>) -> Result<bool, CommandError> {
+    let client = state.client()
+        .map_err(|e| CommandError::with_context("Erro ao inicializar gerenciador", e))?;
     
-    let config = manager.get_config()
-        .map_err(|e| format!("Erro ao verificar configuração: {:?}", e))?;
+    let config = client.config_manager().get_config()
+        .map_err(|e| CommandError::with_context("Erro ao verificar configuração", e))?;
     
     Ok(config.is_some())
 }
 
+// === CACHE OFFLINE ===
+
+// Resposta recente do cache é devolvida direto; obsoleta é devolvida na hora e
+// atualizada em segundo plano (`jira://cache-refreshed`), uma vez por consulta;
+// sem cache, buscar no JIRA
+async fn serve_cached<T, F, Fut>(
+    app: &tauri::AppHandle,
+    state: &AppState,
+    profile_id: &str,
+    cached: Option<JiraCached<T>>,
+    kind: CacheKind,
+    query: String,
+    fetch: F,
+) -> Result<JiraCached<T>, JiraError>
+where
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = Result<T, JiraError>> + Send + 'static,
+{
+    match cached {
+        Some(cached) if !cached.stale => Ok(cached),
+        Some(mut cached) => {
+            // Já em andamento: o evento da atualização em curso também serve a esta chamada
+            if let Some(guard) = state.begin_refresh(profile_id, kind, &query) {
+                let app = app.clone();
+                let refresh = fetch();
+
+                tauri::async_runtime::spawn(async move {
+                    // O cliente grava o resultado no cache; o frontend busca de novo ao receber o evento
+                    let error = refresh.await.err().map(|e| e.to_string());
+                    drop(guard);
+                    let _ = app.emit("jira://cache-refreshed", CacheRefreshed { kind, query, error });
+                });
+            }
+
+            cached.refreshing = true;
+            Ok(cached)
+        }
+        None => fetch().await.map(JiraCached::fresh),
+    }
+}
+
 #[tauri::command]
-async fn get_current_jira_user() -> Result<String, String> {
-    let client = JiraClient::new()
-        .map_err(|e| format!("Erro ao criar cliente JIRA: {:?}", e))?;
+async fn get_current_jira_user(app: tauri::AppHandle, state: tauri::State<'_, AppState>) -> Result<JiraCached<JiraUser>, CommandError> {
+    let client = state.client()
+        .map_err(|e| CommandError::with_context("Erro ao criar cliente JIRA", e))?;
     
-    let user = client.get_current_user().await
-        .map_err(|e| format!("Erro ao obter usuário atual: {:?}", e))?;
+    let cached = client.cached_current_user();
+    let profile_id = client.config_manager().profile_id().to_string();
+    serve_cached(&app, &state, &profile_id, cached, CacheKind::CurrentUser, String::new(), move || async move {
+        client.get_current_user().await
+    }).await
+        .map_err(|e| CommandError::with_context("Erro ao obter usuário atual", e))
+}
+
+#[tauri::command]
+async fn get_jira_projects(app: tauri::AppHandle, state: tauri::State<'_, AppState>, max_results: Option<u32>) -> Result<JiraCached<Vec<JiraProject>>, CommandError> {
+    let client = state.client()
+        .map_err(|e| CommandError::with_context("Erro ao criar cliente JIRA", e))?;
     
-    Ok(serde_json::to_string(&user).unwrap())
+    let cached = client.cached_projects(max_results);
+    let profile_id = client.config_manager().profile_id().to_string();
+    serve_cached(&app, &state, &profile_id, cached, CacheKind::Projects, String::new(), move || async move {
+        client.get_projects(max_results).await
+    }).await
+        .map_err(|e| CommandError::with_context("Erro ao obter projetos", e))
 }
 
 #[tauri::command]
-async fn get_jira_projects() -> Result<String, String> {
-    let client = JiraClient::new()
-        .map_err(|e| format!("Erro ao criar cliente JIRA: {:?}", e))?;
+async fn search_jira_issues(app: tauri::AppHandle, state: tauri::State<'_, AppState>, jql: String, max_results: Option<u32>) -> Result<JiraCached<Vec<JiraIssue>>, CommandError> {
+    let client = state.client()
+        .map_err(|e| CommandError::with_context("Erro ao criar cliente JIRA", e))?;
     
-    let projects = client.get_projects().await
-        .map_err(|e| format!("Erro ao obter projetos: {:?}", e))?;
+    let cached = client.cached_search(&jql, max_results);
+    let profile_id = client.config_manager().profile_id().to_string();
+    serve_cached(&app, &state, &profile_id, cached, CacheKind::Search, jql.clone(), move || async move {
+        client.search_issues(&jql, max_results).await
+    }).await
+        .map_err(|e| CommandError::with_context("Erro ao buscar issues", e))
+}
+
+// Busca textual instantânea nas issues em cache, sem consultar o JIRA
+#[tauri::command]
+async fn search_local_issues(state: tauri::State<'_, AppState>, query: String, limit: Option<u32>) -> Result<Vec<LocalIssueMatch>, CommandError> {
+    let client = state.client()
+        .map_err(|e| CommandError::with_context("Erro ao criar cliente JIRA", e))?;
+
+    let cache = client.cache()
+        .ok_or_else(|| CommandError::with_context("Erro na busca local", JiraError::InvalidConfig("Cache local indisponível".to_string())))?;
+
+    cache.search_local(&query, limit.unwrap_or(LOCAL_SEARCH_LIMIT))
+        .map_err(|e| CommandError::with_context("Erro na busca local", e))
+}
+
+// Busca paginada que envia cada página ao frontend assim que chega
+#[tauri::command]
+async fn stream_jira_issues(
+    state: tauri::State<'_, AppState>,
+    jql: String,
+    max_results: Option<u32>,
+    on_page: Channel<JiraIssuePage>,
+) -> Result<u32, CommandError> {
+    let client = state.client()
+        .map_err(|e| CommandError::with_context("Erro ao criar cliente JIRA", e))?;
     
-    Ok(serde_json::to_string(&projects).unwrap())
+    client.search_issues_paged(&jql, max_results, |page| {
+        on_page.send(page)
+            .map_err(|e| JiraError::InvalidConfig(format!("Erro ao enviar página: {}", e)))
+    }).await
+        .map_err(|e| CommandError::with_context("Erro ao buscar issues", e))
 }
 
 #[tauri::command]
-async fn search_jira_issues(jql: String, max_results: u32) -> Result<String, String> {
-    let client = JiraClient::new()
-        .map_err(|e| format!("Erro ao criar cliente JIRA: {:?}", e))?;
+async fn count_jira_issues(state: tauri::State<'_, AppState>, jql: String) -> Result<JiraIssueCount, CommandError> {
+    let client = state.client()
+        .map_err(|e| CommandError::with_context("Erro ao criar cliente JIRA", e))?;
     
-    let issues = client.search_issues(&jql, max_results).await
-        .map_err(|e| format!("Erro ao buscar issues: {:?}", e))?;
+    client.count_issues(&jql).await
+        .map_err(|e| CommandError::with_context("Erro ao contar issues", e))
+}
+
+#[tauri::command]
+async fn get_jira_issue(state: tauri::State<'_, AppState>, key: String) -> Result<JiraIssueDetail, CommandError> {
+    let client = state.client()
+        .map_err(|e| CommandError::with_context("Erro ao criar cliente JIRA", e))?;
     
-    Ok(serde_json::to_string(&issues).unwrap())
+    client.get_issue(&key).await
+        .map_err(|e| CommandError::with_context(&format!("Erro ao obter issue {}", key), e))
 }
 
 #[tauri::command]
-async fn get_jira_environment_info() -> Result<String, String> {
-    let manager = JiraConfigManager::new()
-        .map_err(|e| format!("Erro ao inicializar gerenciador: {:?}", e))?;
+async fn get_jira_create_issue_types(state: tauri::State<'_, AppState>, project_key: String) -> Result<Vec<JiraCreateMetaIssueType>, CommandError> {
+    let client = state.client()
+        .map_err(|e| CommandError::with_context("Erro ao criar cliente JIRA", e))?;
+    
+    client.get_create_issue_types(&project_key).await
+        .map_err(|e| CommandError::with_context(&format!("Erro ao obter tipos de issue de {}", project_key), e))
+}
+
+#[tauri::command]
+async fn get_jira_create_fields(state: tauri::State<'_, AppState>, project_key: String, issue_type_id: String) -> Result<Vec<JiraFieldMeta>, CommandError> {
+    let client = state.client()
+        .map_err(|e| CommandError::with_context("Erro ao criar cliente JIRA", e))?;
+    
+    client.get_create_fields(&project_key, &issue_type_id).await
+        .map_err(|e| CommandError::with_context("Erro ao obter campos de criação", e))
+}
+
+#[tauri::command]
+async fn validate_jira_issue_draft(state: tauri::State<'_, AppState>, draft: JiraIssueDraft) -> Result<Vec<String>, CommandError> {
+    let client = state.client()
+        .map_err(|e| CommandError::with_context("Erro ao criar cliente JIRA", e))?;
+    
+    let fields = client.get_create_fields(&draft.project_key, &draft.issue_type_id).await
+        .map_err(|e| CommandError::with_context("Erro ao obter campos de criação", e))?;
+    
+    Ok(draft.validate(&fields))
+}
+
+#[tauri::command]
+async fn create_jira_issue(state: tauri::State<'_, AppState>, draft: JiraIssueDraft) -> Result<JiraCreatedIssue, CommandError> {
+    let client = state.client()
+        .map_err(|e| CommandError::with_context("Erro ao criar cliente JIRA", e))?;
     
-    let env_info = manager.get_environment_info();
-    Ok(serde_json::to_string(&env_info).unwrap())
+    client.create_issue(&draft).await
+        .map_err(|e| CommandError::with_context("Erro ao criar issue", e))
+}
+
+#[tauri::command]
+async fn get_jira_transitions(state: tauri::State<'_, AppState>, key: String) -> Result<Vec<JiraTransition>, CommandError> {
+    let client = state.client()
+        .map_err(|e| CommandError::with_context("Erro ao criar cliente JIRA", e))?;
+    
+    client.get_transitions(&key).await
+        .map_err(|e| CommandError::with_context(&format!("Erro ao obter transições de {}", key), e))
+}
+
+#[tauri::command]
+async fn transition_jira_issue(
+    state: tauri::State<'_, AppState>,
+    key: String,
+    transition_id: String,
+    fields: Option<serde_json::Map<String, serde_json::Value>>,
+    comment: Option<String>,
+) -> Result<(), CommandError> {
+    let client = state.client()
+        .map_err(|e| CommandError::with_context("Erro ao criar cliente JIRA", e))?;
+    
+    client.transition_issue(&key, &transition_id, fields, comment.as_deref()).await
+        .map_err(|e| CommandError::with_context(&format!("Erro ao executar transição em {}", key), e))
+}
+
+#[tauri::command]
+async fn list_jira_comments(state: tauri::State<'_, AppState>, key: String) -> Result<Vec<JiraComment>, CommandError> {
+    let client = state.client()
+        .map_err(|e| CommandError::with_context("Erro ao criar cliente JIRA", e))?;
+    
+    client.list_comments(&key).await
+        .map_err(|e| CommandError::with_context(&format!("Erro ao obter comentários de {}", key), e))
+}
+
+#[tauri::command]
+async fn add_jira_comment(state: tauri::State<'_, AppState>, key: String, body: String) -> Result<JiraComment, CommandError> {
+    let client = state.client()
+        .map_err(|e| CommandError::with_context("Erro ao criar cliente JIRA", e))?;
+    
+    client.add_comment(&key, &body).await
+        .map_err(|e| CommandError::with_context(&format!("Erro ao adicionar comentário em {}", key), e))
+}
+
+#[tauri::command]
+async fn edit_jira_comment(state: tauri::State<'_, AppState>, key: String, comment_id: String, body: String) -> Result<JiraComment, CommandError> {
+    let client = state.client()
+        .map_err(|e| CommandError::with_context("Erro ao criar cliente JIRA", e))?;
+    
+    client.edit_comment(&key, &comment_id, &body).await
+        .map_err(|e| CommandError::with_context(&format!("Erro ao editar comentário {}", comment_id), e))
+}
+
+#[tauri::command]
+async fn delete_jira_comment(state: tauri::State<'_, AppState>, key: String, comment_id: String) -> Result<(), CommandError> {
+    let client = state.client()
+        .map_err(|e| CommandError::with_context("Erro ao criar cliente JIRA", e))?;
+    
+    client.delete_comment(&key, &comment_id).await
+        .map_err(|e| CommandError::with_context(&format!("Erro ao remover comentário {}", comment_id), e))
+}
+
+#[tauri::command]
+async fn list_jira_worklogs(state: tauri::State<'_, AppState>, key: String) -> Result<Vec<JiraWorklog>, CommandError> {
+    let client = state.client()
+        .map_err(|e| CommandError::with_context("Erro ao criar cliente JIRA", e))?;
+    
+    client.list_worklogs(&key).await
+        .map_err(|e| CommandError::with_context(&format!("Erro ao obter registros de trabalho de {}", key), e))
+}
+
+#[tauri::command]
+async fn add_jira_worklog(state: tauri::State<'_, AppState>, key: String, worklog: JiraWorklogInput) -> Result<JiraWorklog, CommandError> {
+    let client = state.client()
+        .map_err(|e| CommandError::with_context("Erro ao criar cliente JIRA", e))?;
+    
+    client.add_worklog(&key, &worklog).await
+        .map_err(|e| CommandError::with_context(&format!("Erro ao registrar trabalho em {}", key), e))
+}
+
+#[tauri::command]
+async fn update_jira_worklog(state: tauri::State<'_, AppState>, key: String, worklog_id: String, worklog: JiraWorklogInput) -> Result<JiraWorklog, CommandError> {
+    let client = state.client()
+        .map_err(|e| CommandError::with_context("Erro ao criar cliente JIRA", e))?;
+    
+    client.update_worklog(&key, &worklog_id, &worklog).await
+        .map_err(|e| CommandError::with_context(&format!("Erro ao atualizar registro de trabalho {}", worklog_id), e))
+}
+
+#[tauri::command]
+async fn delete_jira_worklog(state: tauri::State<'_, AppState>, key: String, worklog_id: String) -> Result<(), CommandError> {
+    let client = state.client()
+        .map_err(|e| CommandError::with_context("Erro ao criar cliente JIRA", e))?;
+    
+    client.delete_worklog(&key, &worklog_id).await
+        .map_err(|e| CommandError::with_context(&format!("Erro ao remover registro de trabalho {}", worklog_id), e))
+}
+
+// Comandos do cronômetro de trabalho; cada perfil tem os seus
+fn active_profile_id() -> Result<String, CommandError> {
+    ProfileManager::load()
+        .map(|profiles| profiles.active_id().to_string())
+        .map_err(|e| CommandError::with_context("Erro ao carregar perfis", e))
+}
+
+#[tauri::command]
+fn get_work_timers(state: tauri::State<'_, AppState>) -> Result<Vec<WorkTimer>, CommandError> {
+    let profile_id = active_profile_id()?;
+
+    state.with_timers(&profile_id, |timers| Ok(timers.list()))
+        .map_err(|e| CommandError::with_context("Erro ao carregar cronômetros", e))
+}
+
+#[tauri::command]
+fn start_work_timer(state: tauri::State<'_, AppState>, issue_key: String) -> Result<WorkTimer, CommandError> {
+    let profile_id = active_profile_id()?;
+
+    state.with_timers(&profile_id, |timers| timers.start(&issue_key))
+        .map_err(|e| CommandError::with_context("Erro ao iniciar cronômetro", e))
+}
+
+#[tauri::command]
+fn pause_work_timer(state: tauri::State<'_, AppState>, issue_key: String) -> Result<WorkTimer, CommandError> {
+    let profile_id = active_profile_id()?;
+
+    state.with_timers(&profile_id, |timers| timers.pause(&issue_key))
+        .map_err(|e| CommandError::with_context("Erro ao pausar cronômetro", e))
+}
+
+// Encerrar o cronômetro e, se solicitado, enviar o tempo acumulado como registro de trabalho
+#[tauri::command]
+async fn stop_work_timer(
+    state: tauri::State<'_, AppState>,
+    issue_key: String,
+    comment: Option<String>,
+    submit: bool,
+) -> Result<Option<JiraWorklog>, CommandError> {
+    let client = if submit {
+        Some(state.client().map_err(|e| CommandError::with_context("Erro ao criar cliente JIRA", e))?)
+    } else {
+        None
+    };
+
+    // O registro vai para o perfil do cliente; o cronômetro precisa ser do mesmo perfil
+    let profile_id = match &client {
+        Some(client) => client.config_manager().profile_id().to_string(),
+        None => active_profile_id()?,
+    };
+
+    // O lock é liberado antes do envio; outros cronômetros seguem utilizáveis
+    let timer = state.with_timers(&profile_id, |timers| timers.stop(&issue_key))
+        .map_err(|e| CommandError::with_context("Erro ao encerrar cronômetro", e))?;
+    
+    let Some(client) = client else {
+        return Ok(None);
+    };
+
+    // O JIRA registra em minutos; arredondar para cima
+    let worklog = JiraWorklogInput {
+        time_spent_seconds: timer.elapsed_seconds.div_ceil(60).max(1) * 60,
+        started: Some(timer.started_at),
+        comment,
+    };
+
+    match client.add_worklog(&issue_key, &worklog).await {
+        Ok(created) => Ok(Some(created)),
+        Err(e) => {
+            // Não perder o tempo acumulado se o envio falhar
+            let context = match state.with_timers(&profile_id, |timers| timers.restore(timer)) {
+                Ok(()) => format!("Erro ao registrar trabalho em {}", issue_key),
+                Err(restore_error) => format!(
+                    "Erro ao registrar trabalho em {}; o cronômetro não pôde ser restaurado ({})",
+                    issue_key, restore_error
+                ),
+            };
+            Err(CommandError::with_context(&context, e))
+        }
+    }
+}
+
+// Orçamento de requisições mais recente informado pelo JIRA para o perfil ativo
+#[tauri::command]
+fn get_jira_rate_limit_status() -> RateLimitStatus {
+    ProfileManager::load()
+        .map(|profiles| request::rate_limit_status(profiles.active_id()))
+        .unwrap_or_default()
+}
+
+#[tauri::command]
+async fn get_jira_environment_info() -> EnvironmentInfo {
+    EnvironmentInfo::detect()
+}
+
+// === DIAGNÓSTICO ===
+
+// Relatório de ambiente, armazenamento e conectividade para anexar a chamados
+#[tauri::command]
+async fn get_diagnostics_report() -> Result<DiagnosticsReport, CommandError> {
+    DiagnosticsReport::collect().await
+        .map_err(|e| CommandError::with_context("Erro ao gerar diagnóstico", e))
+}
+
+// Relatório como texto (JSON ou Markdown), para copiar ou salvar
+#[tauri::command]
+async fn render_diagnostics_report(format: DiagnosticsFormat) -> Result<String, CommandError> {
+    get_diagnostics_report().await?
+        .render(format)
+        .map_err(|e| CommandError::with_context("Erro ao formatar diagnóstico", e))
+}
+
+// Salvar o relatório na pasta de downloads (ou de configuração) e devolver o caminho
+#[tauri::command]
+async fn save_diagnostics_report(format: DiagnosticsFormat) -> Result<String, CommandError> {
+    let content = render_diagnostics_report(format).await?;
+
+    let extension = match format {
+        DiagnosticsFormat::Json => "json",
+        DiagnosticsFormat::Markdown => "md",
+    };
+    let dir = match dirs::download_dir() {
+        Some(dir) => dir,
+        None => goji_config_dir()?,
+    };
+    let path = dir.join(format!("goji-diagnostico-{}.{}", Utc::now().format("%Y%m%d-%H%M%S"), extension));
+
+    std::fs::write(&path, content)
+        .map_err(|e| CommandError::from(JiraError::InvalidConfig(format!("Erro ao salvar diagnóstico: {}", e))))?;
+
+    Ok(path.display().to_string())
+}
+
+// === MIGRAÇÃO DE BACKEND ===
+
+// Refazer a sondagem do keyring (ex.: após iniciar o gnome-keyring) e redetectar o ambiente
+#[tauri::command]
+fn recheck_keyring(state: tauri::State<'_, AppState>) -> EnvironmentInfo {
+    keyring_probe::refresh_keyring_probe();
+    // O backend escolhido pode ter mudado com a nova sondagem
+    state.invalidate();
+    EnvironmentInfo::detect()
+}
+
+// Escolher o backend de armazenamento; credenciais no backend anterior aparecem
+// em get_storage_migration_status
+#[tauri::command]
+fn set_storage_preference(preference: StoragePreference, state: tauri::State<'_, AppState>) -> Result<EnvironmentInfo, CommandError> {
+    preference.save()
+        .map_err(|e| CommandError::with_context("Erro ao salvar preferência de armazenamento", e))?;
+    state.invalidate();
+
+    Ok(EnvironmentInfo::detect())
+}
+
+// Credenciais deixadas em outro backend (ex.: `.enc` anterior à instalação do keyring)
+#[tauri::command]
+fn get_storage_migration_status() -> Result<StorageMigrationStatus, CommandError> {
+    let profiles = ProfileManager::load()
+        .map_err(|e| CommandError::with_context("Erro ao carregar perfis", e))?;
+
+    SecureStorage::migration_status("goji", &profiles.storage_slots())
+        .map_err(|e| CommandError::with_context("Erro ao verificar armazenamento", e))
+}
+
+#[tauri::command]
+fn migrate_credential_storage(state: tauri::State<'_, AppState>) -> Result<StorageMigrationReport, CommandError> {
+    let profiles = ProfileManager::load()
+        .map_err(|e| CommandError::with_context("Erro ao carregar perfis", e))?;
+
+    let report = SecureStorage::migrate("goji", &profiles.storage_slots())
+        .map_err(|e| CommandError::with_context("Erro ao migrar credenciais", e))?;
+
+    state.invalidate();
+    Ok(report)
+}
+
+// === SENHA MESTRA ===
+
+#[tauri::command]
+fn get_vault_status() -> VaultStatus {
+    vault::status()
+}
+
+#[tauri::command]
+fn enable_vault_passphrase(passphrase: String, state: tauri::State<'_, AppState>) -> Result<VaultStatus, CommandError> {
+    let profiles = ProfileManager::load()
+        .map_err(|e| CommandError::with_context("Erro ao carregar perfis", e))?;
+
+    SecureStorage::enable_passphrase("goji", &profiles.storage_slots(), &passphrase)
+        .map_err(|e| CommandError::with_context("Erro ao ativar senha mestra", e))?;
+    state.invalidate();
+
+    Ok(vault::status())
+}
+
+#[tauri::command]
+fn unlock_vault(passphrase: String, state: tauri::State<'_, AppState>) -> Result<VaultStatus, CommandError> {
+    vault::unlock(&passphrase)
+        .map_err(|e| CommandError::with_context("Erro ao desbloquear cofre", e))?;
+
+    // A sincronização aguardava o desbloqueio
+    state.sync().sync_now();
+
+    Ok(vault::status())
+}
+
+#[tauri::command]
+fn lock_vault() -> VaultStatus {
+    vault::lock();
+    vault::status()
+}
+
+#[tauri::command]
+fn change_vault_passphrase(current_passphrase: String, new_passphrase: String, state: tauri::State<'_, AppState>) -> Result<VaultStatus, CommandError> {
+    let profiles = ProfileManager::load()
+        .map_err(|e| CommandError::with_context("Erro ao carregar perfis", e))?;
+
+    SecureStorage::change_passphrase(&profiles.storage_slots(), &current_passphrase, &new_passphrase)
+        .map_err(|e| CommandError::with_context("Erro ao alterar senha mestra", e))?;
+    state.invalidate();
+
+    Ok(vault::status())
+}
+
+// 0 desativa o bloqueio automático
+#[tauri::command]
+fn set_vault_auto_lock(minutes: u32) -> Result<VaultStatus, CommandError> {
+    vault::set_auto_lock_minutes(minutes)
+        .map_err(|e| CommandError::with_context("Erro ao configurar bloqueio automático", e))?;
+
+    Ok(vault::status())
+}
+
+// === SINCRONIZAÇÃO ===
+
+#[tauri::command]
+fn get_jira_sync_status(state: tauri::State<'_, AppState>) -> SyncStatus {
+    state.sync().status()
+}
+
+#[tauri::command]
+fn get_jira_sync_settings(state: tauri::State<'_, AppState>) -> Result<SyncSettings, CommandError> {
+    Ok(state.sync().settings(&active_profile_id()?))
+}
+
+#[tauri::command]
+fn set_jira_sync_settings(settings: SyncSettings, state: tauri::State<'_, AppState>) -> Result<SyncSettings, CommandError> {
+    state.sync().update_settings(&active_profile_id()?, settings)
+        .map_err(|e| CommandError::with_context("Erro ao salvar configuração de sincronização", e))
+}
+
+#[tauri::command]
+fn pause_jira_sync(state: tauri::State<'_, AppState>) -> Result<SyncSettings, CommandError> {
+    state.sync().set_paused(&active_profile_id()?, true)
+        .map_err(|e| CommandError::with_context("Erro ao pausar sincronização", e))
+}
+
+#[tauri::command]
+fn resume_jira_sync(state: tauri::State<'_, AppState>) -> Result<SyncSettings, CommandError> {
+    state.sync().set_paused(&active_profile_id()?, false)
+        .map_err(|e| CommandError::with_context("Erro ao retomar sincronização", e))
+}
+
+#[tauri::command]
+fn sync_jira_now(state: tauri::State<'_, AppState>) {
+    state.sync().sync_now();
+}
+
+// Ciclo de sincronização; os eventos vão para todas as janelas
+fn spawn_sync(app: &tauri::AppHandle) {
+    let app = app.clone();
+
+    tauri::async_runtime::spawn(async move {
+        let state = app.state::<AppState>();
+
+        state.sync().clone().run(|| state.client(), |event| {
+            let _ = match event {
+                SyncEvent::IssueUpdated(issue) => app.emit("jira://issue-updated", issue),
+                SyncEvent::Status(status) => app.emit("jira://sync-status", status),
+            };
+        }).await;
+    });
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
+        .manage(AppState::new())
+        .setup(|app| {
+            spawn_sync(app.handle());
+            Ok(())
+        })
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_window_state::Builder::default().build())
         .invoke_handler(tauri::generate_handler![
@@ -168,11 +799,59 @@ pub fn run() {
             get_jira_config,
             test_jira_connection,
             clear_jira_config,
+            start_jira_oauth_login,
+            list_jira_profiles,
+            create_jira_profile,
+            rename_jira_profile,
+            delete_jira_profile,
+            activate_jira_profile,
             has_jira_config,
             get_current_jira_user,
             get_jira_projects,
             search_jira_issues,
-            get_jira_environment_info
+            search_local_issues,
+            stream_jira_issues,
+            count_jira_issues,
+            get_jira_issue,
+            get_jira_create_issue_types,
+            get_jira_create_fields,
+            validate_jira_issue_draft,
+            create_jira_issue,
+            get_jira_transitions,
+            transition_jira_issue,
+            list_jira_comments,
+            add_jira_comment,
+            edit_jira_comment,
+            delete_jira_comment,
+            list_jira_worklogs,
+            add_jira_worklog,
+            update_jira_worklog,
+            delete_jira_worklog,
+            get_work_timers,
+            start_work_timer,
+            pause_work_timer,
+            stop_work_timer,
+            get_jira_rate_limit_status,
+            get_jira_environment_info,
+            get_diagnostics_report,
+            render_diagnostics_report,
+            save_diagnostics_report,
+            recheck_keyring,
+            set_storage_preference,
+            get_storage_migration_status,
+            migrate_credential_storage,
+            get_vault_status,
+            enable_vault_passphrase,
+            unlock_vault,
+            lock_vault,
+            change_vault_passphrase,
+            set_vault_auto_lock,
+            get_jira_sync_status,
+            get_jira_sync_settings,
+            set_jira_sync_settings,
+            pause_jira_sync,
+            resume_jira_sync,
+            sync_jira_now
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -0,0 +1,122 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use crate::jira::{cache::CacheKind, client::JiraClient, error::JiraError, sync::SyncEngine, timer::WorkTimerManager};
+
+// Perfil, tipo e consulta de uma atualização do cache em segundo plano
+type RefreshKey = (String, CacheKind, String);
+
+// Estado compartilhado entre os comandos, registrado com `tauri::Builder::manage`.
+// O cliente do perfil ativo é criado uma vez (detecção de ambiente, leitura do
+// keyring e pool de conexões do reqwest) e reaproveitado até ser invalidado.
+#[derive(Default)]
+pub struct AppState {
+    client: Mutex<Option<Arc<JiraClient>>>,
+    // Sincronização em segundo plano, iniciada em `run()`
+    sync: Arc<SyncEngine>,
+    // Cronômetros carregados uma vez por perfil; o Mutex impede que comandos
+    // simultâneos gravem versões diferentes do arquivo de cronômetros
+    timers: Mutex<HashMap<String, WorkTimerManager>>,
+    // Atualizações do cache em andamento; renderizações repetidas ou buscas enquanto
+    // o usuário digita não disparam outra requisição para a mesma consulta
+    refreshing: Arc<Mutex<HashSet<RefreshKey>>>,
+}
+
+impl AppState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Cliente do perfil ativo, criado na primeira chamada
+    pub fn client(&self) -> Result<Arc<JiraClient>, JiraError> {
+        let mut client = self.client.lock().unwrap_or_else(|e| e.into_inner());
+
+        if let Some(client) = client.as_ref() {
+            return Ok(client.clone());
+        }
+
+        let created = Arc::new(JiraClient::new()?);
+        *client = Some(created.clone());
+        Ok(created)
+    }
+
+    pub fn sync(&self) -> &Arc<SyncEngine> {
+        &self.sync
+    }
+
+    // Executar uma operação nos cronômetros do perfil com acesso exclusivo
+    pub fn with_timers<T, F>(&self, profile_id: &str, operation: F) -> Result<T, JiraError>
+    where
+        F: FnOnce(&mut WorkTimerManager) -> Result<T, JiraError>,
+    {
+        let mut timers = self.timers.lock().unwrap_or_else(|e| e.into_inner());
+
+        if let Some(timers) = timers.get_mut(profile_id) {
+            return operation(timers);
+        }
+
+        let loaded = WorkTimerManager::load(profile_id)?;
+        operation(timers.entry(profile_id.to_string()).or_insert(loaded))
+    }
+
+    // Apagar os cronômetros de um perfil removido
+    pub fn delete_timers(&self, profile_id: &str) -> Result<(), JiraError> {
+        let mut timers = self.timers.lock().unwrap_or_else(|e| e.into_inner());
+        timers.remove(profile_id);
+        WorkTimerManager::delete(profile_id)
+    }
+
+    // Reservar a atualização da consulta; `None` se outra já está em andamento.
+    // A reserva é liberada quando o guard é descartado, ao fim da atualização.
+    pub fn begin_refresh(&self, profile_id: &str, kind: CacheKind, query: &str) -> Option<RefreshGuard> {
+        let key = (profile_id.to_string(), kind, query.to_string());
+        let inserted = self.refreshing.lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(key.clone());
+
+        inserted.then(|| RefreshGuard {
+            refreshing: self.refreshing.clone(),
+            key,
+        })
+    }
+
+    // Descartar o cliente em cache; chamado ao salvar/limpar credenciais, trocar de
+    // perfil ou de backend de armazenamento
+    pub fn invalidate(&self) {
+        *self.client.lock().unwrap_or_else(|e| e.into_inner()) = None;
+    }
+}
+
+pub struct RefreshGuard {
+    refreshing: Arc<Mutex<HashSet<RefreshKey>>>,
+    key: RefreshKey,
+}
+
+impl Drop for RefreshGuard {
+    fn drop(&mut self) {
+        self.refreshing.lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&self.key);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn refresh_is_reserved_once_per_query() {
+        let state = AppState::new();
+
+        let guard = state.begin_refresh("default", CacheKind::Search, "project = GOJI");
+        assert!(guard.is_some());
+        assert!(state.begin_refresh("default", CacheKind::Search, "project = GOJI").is_none());
+
+        // Outra consulta, tipo ou perfil não é bloqueado
+        assert!(state.begin_refresh("default", CacheKind::Search, "project = OUTRO").is_some());
+        assert!(state.begin_refresh("default", CacheKind::Projects, "project = GOJI").is_some());
+        assert!(state.begin_refresh("cliente", CacheKind::Search, "project = GOJI").is_some());
+
+        drop(guard);
+        assert!(state.begin_refresh("default", CacheKind::Search, "project = GOJI").is_some());
+    }
+}
@@ -1,5 +1,6 @@
 import { useNavigation } from '../contexts/NavigationContext';
 import AppLayout from './AppLayout';
+import { VaultUnlockDialog } from './jira/VaultUnlockDialog';
 import Dashboard from '../pages/Dashboard';
 import Settings from '../pages/Settings';
 import Development from '../pages/Development';
@@ -21,6 +22,7 @@ export default function AppRouter() {
   return (
     <AppLayout>
       <CurrentPageComponent />
+      <VaultUnlockDialog />
     </AppLayout>
   );
 }
\ No newline at end of file
@@ -0,0 +1,84 @@
+import React, { useState } from 'react';
+import { JiraService } from '../../services/jiraService';
+import { DiagnosticsFormat } from '../../types/jira';
+import { IconClipboard, IconDeviceFloppy, IconLoader2 } from '@tabler/icons-react';
+
+interface DiagnosticsPanelProps {
+  className?: string;
+}
+
+// Gera o relatório de diagnóstico para anexar a chamados ("o JIRA não funciona")
+export const DiagnosticsPanel: React.FC<DiagnosticsPanelProps> = ({ className = '' }) => {
+  const [isBusy, setIsBusy] = useState(false);
+  const [message, setMessage] = useState<string | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  const runAction = async (action: () => Promise<string>, fallback: string) => {
+    setIsBusy(true);
+    setMessage(null);
+    setError(null);
+
+    try {
+      setMessage(await action());
+    } catch (err) {
+      setError(JiraService.getErrorMessage(err, fallback));
+    } finally {
+      setIsBusy(false);
+    }
+  };
+
+  const handleCopy = (format: DiagnosticsFormat) =>
+    runAction(async () => {
+      const report = await JiraService.renderDiagnosticsReport(format);
+      await navigator.clipboard.writeText(report);
+      return 'Relatório copiado para a área de transferência';
+    }, 'Erro ao gerar diagnóstico');
+
+  const handleSave = (format: DiagnosticsFormat) =>
+    runAction(async () => {
+      const path = await JiraService.saveDiagnosticsReport(format);
+      return `Relatório salvo em ${path}`;
+    }, 'Erro ao salvar diagnóstico');
+
+  const buttonClass = 'flex items-center gap-1 px-2 py-1 text-xs rounded-md border border-gray-300 text-gray-700 hover:bg-gray-100 disabled:opacity-50 disabled:cursor-not-allowed dark:border-gray-600 dark:text-gray-300 dark:hover:bg-gray-700';
+
+  return (
+    <div className={`rounded-lg bg-gray-50 border border-gray-200 p-3 dark:bg-gray-800 dark:border-gray-700 ${className}`}>
+      <div className="flex items-center justify-between mb-2">
+        <h4 className="text-xs font-medium text-gray-800 dark:text-gray-200">
+          Relatório de Diagnóstico
+        </h4>
+        {isBusy && <IconLoader2 className="h-4 w-4 animate-spin text-gray-500" />}
+      </div>
+      <p className="text-xs text-gray-600 dark:text-gray-400 mb-2">
+        Ambiente, keyring, arquivos, armazenamento e conectividade com o JIRA. Tokens não são incluídos.
+      </p>
+
+      <div className="flex flex-wrap items-center gap-2">
+        <button type="button" onClick={() => handleCopy(DiagnosticsFormat.Markdown)} disabled={isBusy} className={buttonClass}>
+          <IconClipboard className="h-3 w-3" />
+          Copiar Markdown
+        </button>
+        <button type="button" onClick={() => handleCopy(DiagnosticsFormat.Json)} disabled={isBusy} className={buttonClass}>
+          <IconClipboard className="h-3 w-3" />
+          Copiar JSON
+        </button>
+        <button type="button" onClick={() => handleSave(DiagnosticsFormat.Markdown)} disabled={isBusy} className={buttonClass}>
+          <IconDeviceFloppy className="h-3 w-3" />
+          Salvar .md
+        </button>
+        <button type="button" onClick={() => handleSave(DiagnosticsFormat.Json)} disabled={isBusy} className={buttonClass}>
+          <IconDeviceFloppy className="h-3 w-3" />
+          Salvar .json
+        </button>
+      </div>
+
+      {message && (
+        <p className="mt-2 text-xs text-green-600 dark:text-green-400">{message}</p>
+      )}
+      {error && (
+        <p className="mt-2 text-xs text-red-600 dark:text-red-400">{error}</p>
+      )}
+    </div>
+  );
+};
@@ -1,7 +1,10 @@
 import React, { useState, useEffect } from 'react';
 import { useJira } from '../../hooks/useJira';
 import { SecurityStatus } from './SecurityStatus';
+import { ProfileSelector } from './ProfileSelector';
+import { SyncPanel } from './SyncPanel';
 import type { JiraConfig } from '../../types/jira';
+import { JiraAuthMode } from '../../types/jira';
 import { IconCheck, IconX, IconExternalLink, IconLoader2 } from '@tabler/icons-react';
 
 export const JiraConfigForm: React.FC = () => {
@@ -9,12 +12,13 @@ export const JiraConfigForm: React.FC = () => {
     isLoading,
     error,
     saveConfig,
+    startOAuthLogin,
     getConfig,
     testConnection,
     clearConfig,
     clearError,
     validateJiraUrl,
-    validateEmail,
+    validateUser,
     validateToken,
   } = useJira();
 
@@ -23,6 +27,12 @@ export const JiraConfigForm: React.FC = () => {
     url: '',
     email: '',
     token: '',
+    authMode: JiraAuthMode.CloudApiToken,
+  });
+  const [oauthClient, setOAuthClient] = useState({
+    clientId: '',
+    clientSecret: '',
+    redirectPort: 8765,
   });
   const [isTestingConnection, setIsTestingConnection] = useState(false);
   const [connectionStatus, setConnectionStatus] = useState<'idle' | 'success' | 'error'>('idle');
@@ -35,12 +45,15 @@ export const JiraConfigForm: React.FC = () => {
 
   const loadConfig = async () => {
     const existingConfig = await getConfig();
-    if (existingConfig) {
-      setConfig(existingConfig);
+    setConfig(existingConfig);
+    if (!existingConfig) {
+      setFormData({ url: '', email: '', token: '', authMode: JiraAuthMode.CloudApiToken });
+    } else {
       setFormData({
         url: existingConfig.url,
         email: existingConfig.email,
         token: '••••••••••••••••', // Mascarar token existente
+        authMode: existingConfig.auth_mode,
       });
     }
   };
@@ -53,9 +66,9 @@ export const JiraConfigForm: React.FC = () => {
       errors.url = urlValidation.error || 'URL inválida';
     }
 
-    const emailValidation = validateEmail(formData.email);
-    if (!emailValidation.valid) {
-      errors.email = emailValidation.error || 'Email inválido';
+    const userValidation = validateUser(formData.authMode, formData.email);
+    if (!userValidation.valid) {
+      errors.email = userValidation.error || 'Usuário inválido';
     }
 
     const tokenValidation = validateToken(formData.token);
@@ -72,6 +85,23 @@ export const JiraConfigForm: React.FC = () => {
     clearError();
     setSuccessMessage(null);
 
+    if (formData.authMode === JiraAuthMode.OAuth) {
+      const success = await startOAuthLogin(
+        {
+          clientId: oauthClient.clientId.trim(),
+          clientSecret: oauthClient.clientSecret.trim() || undefined,
+          redirectPort: oauthClient.redirectPort,
+        },
+        formData.url.trim() || undefined
+      );
+
+      if (success) {
+        setSuccessMessage('Login OAuth concluído com sucesso!');
+        await loadConfig();
+      }
+      return;
+    }
+
     if (!validateForm()) {
       return;
     }
@@ -83,8 +113,9 @@ export const JiraConfigForm: React.FC = () => {
 
     const success = await saveConfig(
       formData.url,
-      formData.email,
-      tokenToSave
+      formData.authMode === JiraAuthMode.DataCenterPat ? '' : formData.email,
+      tokenToSave,
+      formData.authMode
     );
 
     if (success) {
@@ -115,7 +146,7 @@ export const JiraConfigForm: React.FC = () => {
       const success = await clearConfig();
       if (success) {
         setConfig(null);
-        setFormData({ url: '', email: '', token: '' });
+        setFormData({ url: '', email: '', token: '', authMode: JiraAuthMode.CloudApiToken });
         setConnectionStatus('idle');
         setSuccessMessage('Credenciais JIRA removidas com sucesso!');
       }
@@ -133,7 +164,19 @@ export const JiraConfigForm: React.FC = () => {
     }
   };
 
-  const isFormValid = formData.url.trim() && formData.email.trim() && formData.token.trim() && Object.keys(validationErrors).length === 0;
+  const isOAuth = formData.authMode === JiraAuthMode.OAuth;
+  const isCloud = formData.authMode === JiraAuthMode.CloudApiToken || isOAuth;
+  const needsUser = formData.authMode === JiraAuthMode.CloudApiToken || formData.authMode === JiraAuthMode.BasicPassword;
+  const tokenLabel = {
+    [JiraAuthMode.CloudApiToken]: 'Token de API',
+    [JiraAuthMode.DataCenterPat]: 'Personal Access Token',
+    [JiraAuthMode.BasicPassword]: 'Senha',
+    [JiraAuthMode.OAuth]: '',
+  }[formData.authMode];
+
+  const isFormValid = isOAuth
+    ? oauthClient.clientId.trim() !== ''
+    : formData.url.trim() && (!needsUser || formData.email.trim()) && formData.token.trim() && Object.keys(validationErrors).length === 0;
 
   return (
     <div className="space-y-6">
@@ -156,6 +199,16 @@ export const JiraConfigForm: React.FC = () => {
         )}
       </div>
 
+      {/* Perfil ativo */}
+      <ProfileSelector
+        onProfileChange={() => {
+          setConnectionStatus('idle');
+          setSuccessMessage(null);
+          setValidationErrors({});
+          loadConfig();
+        }}
+      />
+
       {/* Status de Segurança */}
       <SecurityStatus />
 
@@ -187,71 +240,135 @@ export const JiraConfigForm: React.FC = () => {
       <form onSubmit={handleSubmit} className="space-y-4">
         <div>
           <label className="block text-sm font-medium text-neutral-700 dark:text-neutral-300 mb-2">
-            URL da Instância JIRA
+            Tipo de Instância
+          </label>
+          <select
+            value={formData.authMode}
+            onChange={(e) => setFormData(prev => ({ ...prev, authMode: e.target.value as JiraAuthMode }))}
+            className="w-full px-3 py-2 border border-neutral-300 rounded-md focus:ring-2 focus:ring-blue-500 focus:border-blue-500 dark:bg-neutral-800 dark:border-neutral-600 dark:text-neutral-200"
+          >
+            <option value={JiraAuthMode.CloudApiToken}>JIRA Cloud (email + token de API)</option>
+            <option value={JiraAuthMode.OAuth}>JIRA Cloud (login OAuth)</option>
+            <option value={JiraAuthMode.DataCenterPat}>Server/Data Center (Personal Access Token)</option>
+            <option value={JiraAuthMode.BasicPassword}>Server/Data Center (usuário e senha)</option>
+          </select>
+        </div>
+
+        <div>
+          <label className="block text-sm font-medium text-neutral-700 dark:text-neutral-300 mb-2">
+            URL da Instância JIRA{isOAuth && ' (opcional)'}
           </label>
           <input
             type="url"
             value={formData.url}
             onChange={(e) => setFormData(prev => ({ ...prev, url: e.target.value }))}
-            placeholder="https://minhaempresa.atlassian.net"
+            placeholder={isCloud ? 'https://minhaempresa.atlassian.net' : 'https://jira.minhaempresa.com'}
             className={`w-full px-3 py-2 border rounded-md focus:ring-2 focus:ring-blue-500 focus:border-blue-500 dark:bg-neutral-800 dark:border-neutral-600 dark:text-neutral-200 ${
               validationErrors.url ? 'border-red-300 dark:border-red-600' : 'border-neutral-300 dark:border-neutral-600'
             }`}
-            required
+            required={!isOAuth}
           />
           {validationErrors.url && (
             <p className="mt-1 text-sm text-red-600 dark:text-red-400">{validationErrors.url}</p>
           )}
         </div>
 
-        <div>
-          <label className="block text-sm font-medium text-neutral-700 dark:text-neutral-300 mb-2">
-            Email do JIRA
-          </label>
-          <input
-            type="email"
-            value={formData.email}
-            onChange={(e) => setFormData(prev => ({ ...prev, email: e.target.value }))}
-            placeholder="usuario@empresa.com"
-            className={`w-full px-3 py-2 border rounded-md focus:ring-2 focus:ring-blue-500 focus:border-blue-500 dark:bg-neutral-800 dark:border-neutral-600 dark:text-neutral-200 ${
-              validationErrors.email ? 'border-red-300 dark:border-red-600' : 'border-neutral-300 dark:border-neutral-600'
-            }`}
-            required
-          />
-          {validationErrors.email && (
-            <p className="mt-1 text-sm text-red-600 dark:text-red-400">{validationErrors.email}</p>
-          )}
-        </div>
-
-        <div>
-          <div className="flex items-center justify-between mb-2">
-            <label className="block text-sm font-medium text-neutral-700 dark:text-neutral-300">
-              Token de API
+        {needsUser && (
+          <div>
+            <label className="block text-sm font-medium text-neutral-700 dark:text-neutral-300 mb-2">
+              {isCloud ? 'Email do JIRA' : 'Usuário'}
             </label>
-            <a 
-              href="https://support.atlassian.com/atlassian-account/docs/manage-api-tokens-for-your-atlassian-account/" 
-              target="_blank" 
-              rel="noopener noreferrer"
-              className="text-xs text-blue-600 hover:text-blue-800 dark:text-blue-400 dark:hover:text-blue-200 flex items-center gap-1"
-            >
-              Como criar um token
-              <IconExternalLink className="h-3 w-3" />
-            </a>
+            <input
+              type={isCloud ? 'email' : 'text'}
+              value={formData.email}
+              onChange={(e) => setFormData(prev => ({ ...prev, email: e.target.value }))}
+              placeholder={isCloud ? 'usuario@empresa.com' : 'usuario'}
+              className={`w-full px-3 py-2 border rounded-md focus:ring-2 focus:ring-blue-500 focus:border-blue-500 dark:bg-neutral-800 dark:border-neutral-600 dark:text-neutral-200 ${
+                validationErrors.email ? 'border-red-300 dark:border-red-600' : 'border-neutral-300 dark:border-neutral-600'
+              }`}
+              required
+            />
+            {validationErrors.email && (
+              <p className="mt-1 text-sm text-red-600 dark:text-red-400">{validationErrors.email}</p>
+            )}
           </div>
-          <input
-            type="password"
-            value={formData.token}
-            onChange={(e) => setFormData(prev => ({ ...prev, token: e.target.value }))}
-            placeholder="ATATT3xFfGF0T5..."
-            className={`w-full px-3 py-2 border rounded-md focus:ring-2 focus:ring-blue-500 focus:border-blue-500 dark:bg-neutral-800 dark:border-neutral-600 dark:text-neutral-200 ${
-              validationErrors.token ? 'border-red-300 dark:border-red-600' : 'border-neutral-300 dark:border-neutral-600'
-            }`}
-            required
-          />
-          {validationErrors.token && (
-            <p className="mt-1 text-sm text-red-600 dark:text-red-400">{validationErrors.token}</p>
-          )}
-        </div>
+        )}
+
+        {isOAuth ? (
+          <div className="space-y-4">
+            <div>
+              <label className="block text-sm font-medium text-neutral-700 dark:text-neutral-300 mb-2">
+                Client ID
+              </label>
+              <input
+                type="text"
+                value={oauthClient.clientId}
+                onChange={(e) => setOAuthClient(prev => ({ ...prev, clientId: e.target.value }))}
+                className="w-full px-3 py-2 border border-neutral-300 rounded-md focus:ring-2 focus:ring-blue-500 focus:border-blue-500 dark:bg-neutral-800 dark:border-neutral-600 dark:text-neutral-200"
+                required
+              />
+            </div>
+            <div>
+              <label className="block text-sm font-medium text-neutral-700 dark:text-neutral-300 mb-2">
+                Client Secret (opcional)
+              </label>
+              <input
+                type="password"
+                value={oauthClient.clientSecret}
+                onChange={(e) => setOAuthClient(prev => ({ ...prev, clientSecret: e.target.value }))}
+                className="w-full px-3 py-2 border border-neutral-300 rounded-md focus:ring-2 focus:ring-blue-500 focus:border-blue-500 dark:bg-neutral-800 dark:border-neutral-600 dark:text-neutral-200"
+              />
+            </div>
+            <div>
+              <label className="block text-sm font-medium text-neutral-700 dark:text-neutral-300 mb-2">
+                Porta da callback
+              </label>
+              <input
+                type="number"
+                min={1024}
+                max={65535}
+                value={oauthClient.redirectPort}
+                onChange={(e) => setOAuthClient(prev => ({ ...prev, redirectPort: Number(e.target.value) }))}
+                className="w-full px-3 py-2 border border-neutral-300 rounded-md focus:ring-2 focus:ring-blue-500 focus:border-blue-500 dark:bg-neutral-800 dark:border-neutral-600 dark:text-neutral-200"
+              />
+              <p className="mt-1 text-xs text-neutral-500 dark:text-neutral-400">
+                Cadastre http://127.0.0.1:{oauthClient.redirectPort}/callback como callback URL do app OAuth
+              </p>
+            </div>
+          </div>
+        ) : (
+          <div>
+            <div className="flex items-center justify-between mb-2">
+              <label className="block text-sm font-medium text-neutral-700 dark:text-neutral-300">
+                {tokenLabel}
+              </label>
+              {isCloud && (
+                <a 
+                  href="https://support.atlassian.com/atlassian-account/docs/manage-api-tokens-for-your-atlassian-account/" 
+                  target="_blank" 
+                  rel="noopener noreferrer"
+                  className="text-xs text-blue-600 hover:text-blue-800 dark:text-blue-400 dark:hover:text-blue-200 flex items-center gap-1"
+                >
+                  Como criar um token
+                  <IconExternalLink className="h-3 w-3" />
+                </a>
+              )}
+            </div>
+            <input
+              type="password"
+              value={formData.token}
+              onChange={(e) => setFormData(prev => ({ ...prev, token: e.target.value }))}
+              placeholder={isCloud ? 'ATATT3xFfGF0T5...' : ''}
+              className={`w-full px-3 py-2 border rounded-md focus:ring-2 focus:ring-blue-500 focus:border-blue-500 dark:bg-neutral-800 dark:border-neutral-600 dark:text-neutral-200 ${
+                validationErrors.token ? 'border-red-300 dark:border-red-600' : 'border-neutral-300 dark:border-neutral-600'
+              }`}
+              required
+            />
+            {validationErrors.token && (
+              <p className="mt-1 text-sm text-red-600 dark:text-red-400">{validationErrors.token}</p>
+            )}
+          </div>
+        )}
 
         <div className="flex gap-3 pt-4">
           <button
@@ -262,10 +379,10 @@ export const JiraConfigForm: React.FC = () => {
             {isLoading ? (
               <>
                 <IconLoader2 className="h-4 w-4 mr-2 animate-spin" />
-                Salvando...
+                {isOAuth ? 'Aguardando autorização...' : 'Salvando...'}
               </>
             ) : (
-              'Salvar Configuração'
+              isOAuth ? 'Entrar com Atlassian' : 'Salvar Configuração'
             )}
           </button>
 
@@ -311,6 +428,8 @@ export const JiraConfigForm: React.FC = () => {
           </div>
         </div>
       )}
+
+      {config && <SyncPanel className="mt-4" />}
     </div>
   );
 };
\ No newline at end of file
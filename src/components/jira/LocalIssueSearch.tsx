@@ -0,0 +1,113 @@
+import React, { useState, useEffect } from 'react';
+import { JiraService } from '../../services/jiraService';
+import type { LocalIssueMatch } from '../../types/jira';
+import { MATCH_START, MATCH_END } from '../../types/jira';
+import { IconSearch } from '@tabler/icons-react';
+
+interface LocalIssueSearchProps {
+  className?: string;
+}
+
+// Espera entre teclas antes de consultar o índice local
+const SEARCH_DELAY_MS = 120;
+
+// Trecho com os termos encontrados destacados, sem interpretar o texto como HTML
+const Snippet: React.FC<{ text: string }> = ({ text }) => (
+  <>
+    {text.split(MATCH_START).map((part, index) => {
+      if (index === 0) return <span key={index}>{part}</span>;
+
+      const [highlighted, rest = ''] = part.split(MATCH_END);
+      return (
+        <span key={index}>
+          <mark className="rounded bg-yellow-200 px-0.5 dark:bg-yellow-700 dark:text-neutral-100">{highlighted}</mark>
+          {rest}
+        </span>
+      );
+    })}
+  </>
+);
+
+// Busca instantânea nas issues sincronizadas (resumo, descrição e comentários)
+export const LocalIssueSearch: React.FC<LocalIssueSearchProps> = ({ className = '' }) => {
+  const [query, setQuery] = useState('');
+  const [matches, setMatches] = useState<LocalIssueMatch[]>([]);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    if (!query.trim()) {
+      setMatches([]);
+      setError(null);
+      return;
+    }
+
+    let cancelled = false;
+    const timer = setTimeout(async () => {
+      try {
+        const result = await JiraService.searchLocalIssues(query);
+        if (!cancelled) {
+          setMatches(result);
+          setError(null);
+        }
+      } catch (err) {
+        if (!cancelled) {
+          setError(JiraService.getErrorMessage(err, 'Erro na busca local'));
+        }
+      }
+    }, SEARCH_DELAY_MS);
+
+    return () => {
+      cancelled = true;
+      clearTimeout(timer);
+    };
+  }, [query]);
+
+  return (
+    <div className={className}>
+      <div className="relative">
+        <IconSearch className="pointer-events-none absolute left-3 top-1/2 h-4 w-4 -translate-y-1/2 text-neutral-400" />
+        <input
+          type="search"
+          value={query}
+          onChange={(e) => setQuery(e.target.value)}
+          placeholder="Buscar nas issues sincronizadas..."
+          className="w-full rounded-md border border-neutral-300 bg-white py-2 pl-9 pr-3 text-sm dark:border-neutral-600 dark:bg-neutral-800 dark:text-neutral-200"
+        />
+      </div>
+
+      {error && (
+        <p className="mt-2 text-xs text-red-600 dark:text-red-400">{error}</p>
+      )}
+
+      {query.trim() && !error && (
+        <ul className="mt-2 divide-y divide-neutral-200 rounded-md border border-neutral-200 bg-white dark:divide-neutral-700 dark:border-neutral-700 dark:bg-neutral-800">
+          {matches.length === 0 ? (
+            <li className="p-3 text-sm text-neutral-500 dark:text-neutral-400">Nenhuma issue encontrada no cache local</li>
+          ) : (
+            matches.map(({ issue, snippet, fuzzy }) => (
+              <li key={issue.id} className="p-3">
+                <div className="flex items-center gap-2">
+                  <span className="rounded bg-blue-100 px-2 py-0.5 text-xs font-medium text-blue-700 dark:bg-blue-900 dark:text-blue-300">
+                    {issue.key}
+                  </span>
+                  <span className="truncate text-sm font-medium text-neutral-800 dark:text-neutral-200">{issue.summary}</span>
+                  <span className="ml-auto shrink-0 text-xs text-neutral-500 dark:text-neutral-400">{issue.status}</span>
+                  {fuzzy && (
+                    <span className="shrink-0 rounded bg-neutral-100 px-1.5 py-0.5 text-xs text-neutral-500 dark:bg-neutral-700 dark:text-neutral-400" title="Resultado aproximado">
+                      ≈
+                    </span>
+                  )}
+                </div>
+                {snippet && snippet !== issue.summary && (
+                  <p className="mt-1 line-clamp-2 text-xs text-neutral-600 dark:text-neutral-400">
+                    <Snippet text={snippet} />
+                  </p>
+                )}
+              </li>
+            ))
+          )}
+        </ul>
+      )}
+    </div>
+  );
+};
@@ -0,0 +1,123 @@
+import React, { useState, useEffect } from 'react';
+import { JiraService } from '../../services/jiraService';
+import type { JiraProfileList } from '../../types/jira';
+import { IconPlus, IconPencil, IconTrash, IconLoader2 } from '@tabler/icons-react';
+
+interface ProfileSelectorProps {
+  // Chamado após trocar, criar ou remover perfis para recarregar a configuração
+  onProfileChange?: () => void;
+  className?: string;
+}
+
+export const ProfileSelector: React.FC<ProfileSelectorProps> = ({ onProfileChange, className = '' }) => {
+  const [profileList, setProfileList] = useState<JiraProfileList | null>(null);
+  const [isBusy, setIsBusy] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    loadProfiles();
+  }, []);
+
+  const loadProfiles = async () => {
+    try {
+      setProfileList(await JiraService.listProfiles());
+    } catch (err) {
+      setError(JiraService.getErrorMessage(err, 'Erro ao carregar perfis'));
+    }
+  };
+
+  const runAction = async (action: () => Promise<unknown>, fallback: string) => {
+    setIsBusy(true);
+    setError(null);
+
+    try {
+      await action();
+      await loadProfiles();
+      onProfileChange?.();
+    } catch (err) {
+      setError(JiraService.getErrorMessage(err, fallback));
+    } finally {
+      setIsBusy(false);
+    }
+  };
+
+  const handleActivate = (profileId: string) =>
+    runAction(() => JiraService.activateProfile(profileId), 'Erro ao trocar de perfil');
+
+  const handleCreate = () => {
+    const name = prompt('Nome do novo perfil (ex.: empresa-cloud)');
+    if (!name?.trim()) return;
+
+    runAction(async () => {
+      const profile = await JiraService.createProfile(name);
+      await JiraService.activateProfile(profile.id);
+    }, 'Erro ao criar perfil');
+  };
+
+  const handleRename = () => {
+    const active = profileList?.profiles.find(p => p.id === profileList.active);
+    if (!active) return;
+
+    const name = prompt('Novo nome do perfil', active.name);
+    if (!name?.trim() || name === active.name) return;
+
+    runAction(() => JiraService.renameProfile(active.id, name), 'Erro ao renomear perfil');
+  };
+
+  const handleDelete = () => {
+    const active = profileList?.profiles.find(p => p.id === profileList.active);
+    if (!active) return;
+
+    if (confirm(`Remover o perfil "${active.name}" e suas credenciais?`)) {
+      runAction(() => JiraService.deleteProfile(active.id), 'Erro ao remover perfil');
+    }
+  };
+
+  if (!profileList) {
+    return null;
+  }
+
+  const buttonClass = 'p-2 rounded-md text-neutral-600 hover:bg-neutral-100 disabled:opacity-50 disabled:cursor-not-allowed dark:text-neutral-300 dark:hover:bg-neutral-700';
+
+  return (
+    <div className={className}>
+      <label className="block text-sm font-medium text-neutral-700 dark:text-neutral-300 mb-2">
+        Perfil
+      </label>
+      <div className="flex items-center gap-2">
+        <select
+          value={profileList.active}
+          onChange={(e) => handleActivate(e.target.value)}
+          disabled={isBusy}
+          className="flex-1 px-3 py-2 border border-neutral-300 rounded-md focus:ring-2 focus:ring-blue-500 focus:border-blue-500 dark:bg-neutral-800 dark:border-neutral-600 dark:text-neutral-200"
+        >
+          {profileList.profiles.map(profile => (
+            <option key={profile.id} value={profile.id}>{profile.name}</option>
+          ))}
+        </select>
+
+        {isBusy && <IconLoader2 className="h-4 w-4 animate-spin text-neutral-500" />}
+
+        <button type="button" onClick={handleCreate} disabled={isBusy} className={buttonClass} title="Novo perfil">
+          <IconPlus className="h-4 w-4" />
+        </button>
+        <button type="button" onClick={handleRename} disabled={isBusy} className={buttonClass} title="Renomear perfil">
+          <IconPencil className="h-4 w-4" />
+        </button>
+        <button
+          type="button"
+          onClick={handleDelete}
+          disabled={isBusy || profileList.profiles.length <= 1}
+          className={buttonClass}
+          title="Remover perfil"
+        >
+          <IconTrash className="h-4 w-4" />
+        </button>
+      </div>
+
+      {error && (
+        <p className="mt-1 text-sm text-red-600 dark:text-red-400">{error}</p>
+      )}
+    </div>
+  );
+};
@@ -1,7 +1,11 @@
 import React, { useState, useEffect } from 'react';
 import { useJira } from '../../hooks/useJira';
 import type { EnvironmentInfo } from '../../types/jira';
-import { SecurityLevel } from '../../types/jira';
+import { SecurityLevel, StoragePreference } from '../../types/jira';
+import { JiraService } from '../../services/jiraService';
+import { VaultSettings } from './VaultSettings';
+import { StorageMigrationBanner } from './StorageMigrationBanner';
+import { DiagnosticsPanel } from './DiagnosticsPanel';
 import { IconShield, IconShieldCheck, IconShieldX, IconInfoCircle, IconChevronDown, IconChevronUp } from '@tabler/icons-react';
 
 interface SecurityStatusProps {
@@ -13,18 +17,28 @@ export const SecurityStatus: React.FC<SecurityStatusProps> = ({ className = '' }
   const [envInfo, setEnvInfo] = useState<EnvironmentInfo | null>(null);
   const [showDetails, setShowDetails] = useState(false);
   const [isLoading, setIsLoading] = useState(true);
+  const [preferenceError, setPreferenceError] = useState<string | null>(null);
 
   useEffect(() => {
     loadEnvironmentInfo();
   }, []);
 
+  // Recarregamentos posteriores mantêm o painel montado (sem skeleton)
   const loadEnvironmentInfo = async () => {
-    setIsLoading(true);
     const info = await getEnvironmentInfo();
     setEnvInfo(info);
     setIsLoading(false);
   };
 
+  const handlePreferenceChange = async (preference: StoragePreference) => {
+    setPreferenceError(null);
+    try {
+      setEnvInfo(await JiraService.setStoragePreference(preference));
+    } catch (err) {
+      setPreferenceError(JiraService.getErrorMessage(err, 'Erro ao alterar armazenamento'));
+    }
+  };
+
   const getSecurityIcon = (level: SecurityLevel) => {
     switch (level) {
       case 'High':
@@ -77,7 +91,7 @@ export const SecurityStatus: React.FC<SecurityStatusProps> = ({ className = '' }
     return null;
   }
 
-  const securityDescription = getSecurityDescription(envInfo.security_level, envInfo.is_wsl);
+  const securityDescription = getSecurityDescription(envInfo.security_level, envInfo.is_wsl, envInfo.storage_backend);
   const suggestions = getSecuritySuggestions(envInfo);
   const showWarning = shouldShowSecurityWarning(envInfo.security_level);
 
@@ -113,6 +127,9 @@ export const SecurityStatus: React.FC<SecurityStatusProps> = ({ className = '' }
         </button>
       </div>
 
+      {/* Remontado ao trocar de backend para procurar credenciais no anterior */}
+      <StorageMigrationBanner key={envInfo.storage_backend} onMigrated={loadEnvironmentInfo} />
+
       {/* Aviso de Segurança */}
       {showWarning && (
         <div className="rounded-lg bg-yellow-50 border border-yellow-200 p-3 dark:bg-yellow-900/20 dark:border-yellow-800">
@@ -120,7 +137,8 @@ export const SecurityStatus: React.FC<SecurityStatusProps> = ({ className = '' }
             <IconInfoCircle className="h-4 w-4 text-yellow-500 mt-0.5 flex-shrink-0" />
             <div className="text-xs">
               <p className="font-medium text-yellow-800 dark:text-yellow-200">
-                {envInfo.is_wsl ? 'Ambiente WSL Detectado' : 'Keyring Nativo Indisponível'}
+                {envInfo.is_wsl ? 'Ambiente WSL Detectado' :
+                 envInfo.container ? 'Container Detectado' : 'Keyring Nativo Indisponível'}
               </p>
               <p className="text-yellow-700 dark:text-yellow-300 mt-1">
                 {envInfo.is_wsl 
@@ -150,8 +168,12 @@ export const SecurityStatus: React.FC<SecurityStatusProps> = ({ className = '' }
               </div>
               <div className="flex justify-between">
                 <span className="text-gray-600 dark:text-gray-400">Keyring:</span>
-                <span className={envInfo.has_keyring ? 'text-green-600 dark:text-green-400' : 'text-red-500 dark:text-red-400'}>
-                  {envInfo.has_keyring ? 'Disponível' : 'Indisponível'}
+                <span
+                  className={!envInfo.has_keyring ? 'text-red-500 dark:text-red-400' :
+                    envInfo.keyring_probe.locked ? 'text-yellow-600 dark:text-yellow-400' : 'text-green-600 dark:text-green-400'}
+                  title={envInfo.keyring_probe.message ?? undefined}
+                >
+                  {!envInfo.has_keyring ? 'Indisponível' : envInfo.keyring_probe.locked ? 'Bloqueado' : 'Disponível'}
                 </span>
               </div>
               <div className="flex justify-between">
@@ -160,16 +182,73 @@ export const SecurityStatus: React.FC<SecurityStatusProps> = ({ className = '' }
                   {envInfo.has_desktop_environment ? 'Sim' : 'Não'}
                 </span>
               </div>
+              <div className="flex justify-between">
+                <span className="text-gray-600 dark:text-gray-400">Container:</span>
+                <span className={envInfo.container ? 'text-blue-600 dark:text-blue-400' : 'text-gray-500'}>
+                  {envInfo.container ?? 'Não'}
+                </span>
+              </div>
+              <div className="flex justify-between">
+                <span className="text-gray-600 dark:text-gray-400">Sandbox:</span>
+                <span className={envInfo.sandbox ? 'text-blue-600 dark:text-blue-400' : 'text-gray-500'}>
+                  {envInfo.sandbox
+                    ? `${envInfo.sandbox}${envInfo.has_secret_portal ? '' : ' (sem acesso a segredos)'}`
+                    : 'Não'}
+                </span>
+              </div>
+              <div className="flex justify-between">
+                <span className="text-gray-600 dark:text-gray-400">Sessão SSH:</span>
+                <span className={envInfo.is_ssh_session ? 'text-blue-600 dark:text-blue-400' : 'text-gray-500'}>
+                  {envInfo.is_ssh_session ? 'Sim' : 'Não'}
+                </span>
+              </div>
               <div className="flex justify-between">
                 <span className="text-gray-600 dark:text-gray-400">Armazenamento:</span>
                 <span className="font-medium">
                   {envInfo.storage_backend === 'NativeKeyring' ? 'Keyring' :
-                   envInfo.storage_backend === 'EncryptedFile' ? 'Arquivo' : 'Memória'}
+                   envInfo.storage_backend === 'EncryptedFile' ? 'Arquivo' :
+                   envInfo.storage_backend === 'PassphraseFile' ? 'Senha mestra' : 'Memória'}
+                </span>
+              </div>
+            </div>
+
+            {!envInfo.has_keyring && (
+              <div className="mt-2 flex items-center justify-between text-xs">
+                <span className="text-gray-600 dark:text-gray-400">
+                  {JiraService.getKeyringFailureDescription(envInfo.keyring_probe.failure)}
                 </span>
+                <button
+                  type="button"
+                  onClick={async () => setEnvInfo(await JiraService.recheckKeyring())}
+                  className="text-blue-600 hover:underline dark:text-blue-400"
+                >
+                  Verificar novamente
+                </button>
               </div>
+            )}
+
+            <div className="mt-3 flex items-center justify-between text-xs">
+              <label className="text-gray-600 dark:text-gray-400">Preferência de armazenamento:</label>
+              <select
+                value={envInfo.storage_preference}
+                onChange={(e) => handlePreferenceChange(e.target.value as StoragePreference)}
+                className="px-2 py-1 border border-gray-300 rounded-md dark:bg-gray-800 dark:border-gray-600 dark:text-gray-200"
+              >
+                <option value={StoragePreference.Auto}>Automático</option>
+                <option value={StoragePreference.NativeKeyring}>Keyring do sistema</option>
+                <option value={StoragePreference.EncryptedFile}>Arquivo criptografado</option>
+                <option value={StoragePreference.PassphraseFile}>Senha mestra</option>
+              </select>
             </div>
+            {preferenceError && (
+              <p className="mt-1 text-xs text-red-600 dark:text-red-400">{preferenceError}</p>
+            )}
           </div>
 
+          <VaultSettings onChange={loadEnvironmentInfo} />
+
+          <DiagnosticsPanel />
+
           {/* Sugestões de Melhoria */}
           {suggestions.length > 0 && (
             <div className="rounded-lg bg-blue-50 border border-blue-200 p-3 dark:bg-blue-900/20 dark:border-blue-800">
@@ -179,7 +258,7 @@ export const SecurityStatus: React.FC<SecurityStatusProps> = ({ className = '' }
               <div className="space-y-1">
                 {suggestions.map((suggestion, index) => (
                   <div key={index} className="text-xs text-blue-700 dark:text-blue-300">
-                    {suggestion.startsWith('sudo ') || suggestion.startsWith('gnome-keyring') ? (
+                    {suggestion.startsWith('sudo ') || suggestion.startsWith('gnome-keyring') || suggestion.startsWith('flatpak ') ? (
                       <code className="bg-blue-100 dark:bg-blue-900/30 px-1 py-0.5 rounded font-mono text-xs">
                         {suggestion}
                       </code>
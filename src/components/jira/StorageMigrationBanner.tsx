@@ -0,0 +1,109 @@
+import React, { useState, useEffect } from 'react';
+import { JiraService } from '../../services/jiraService';
+import type { StorageMigrationStatus, StorageMigrationReport } from '../../types/jira';
+import { StorageBackend } from '../../types/jira';
+import { IconArrowsExchange, IconCheck, IconLoader2 } from '@tabler/icons-react';
+
+interface StorageMigrationBannerProps {
+  // Chamado após migrar, para recarregar a configuração e o status de segurança
+  onMigrated?: () => void;
+}
+
+const backendLabel = (backend: StorageBackend) => {
+  switch (backend) {
+    case StorageBackend.NativeKeyring:
+      return 'keyring do sistema';
+    case StorageBackend.EncryptedFile:
+      return 'arquivo criptografado';
+    case StorageBackend.PassphraseFile:
+      return 'arquivo protegido por senha mestra';
+    default:
+      return 'memória';
+  }
+};
+
+export const StorageMigrationBanner: React.FC<StorageMigrationBannerProps> = ({ onMigrated }) => {
+  const [status, setStatus] = useState<StorageMigrationStatus | null>(null);
+  const [report, setReport] = useState<StorageMigrationReport | null>(null);
+  const [isMigrating, setIsMigrating] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    JiraService.getStorageMigrationStatus()
+      .then(setStatus)
+      .catch(() => setStatus(null));
+  }, []);
+
+  const handleMigrate = async () => {
+    setIsMigrating(true);
+    setError(null);
+
+    try {
+      const result = await JiraService.migrateCredentialStorage();
+      setReport(result);
+      setStatus(await JiraService.getStorageMigrationStatus());
+      onMigrated?.();
+    } catch (err) {
+      setError(JiraService.getErrorMessage(err, 'Erro ao migrar credenciais'));
+    } finally {
+      setIsMigrating(false);
+    }
+  };
+
+  if (report) {
+    return (
+      <div className="rounded-lg bg-green-50 border border-green-200 p-3 dark:bg-green-900/20 dark:border-green-800">
+        <div className="flex items-start gap-2 text-xs">
+          <IconCheck className="h-4 w-4 text-green-500 mt-0.5 flex-shrink-0" />
+          <div className="text-green-800 dark:text-green-200">
+            <p className="font-medium">
+              {report.migrated.length} credencial(is) movida(s) para o {backendLabel(report.targetBackend)}
+            </p>
+            {report.skipped.length > 0 && (
+              <p className="mt-1 text-green-700 dark:text-green-300">
+                {report.skipped.length} já existia(m) no armazenamento atual e foi(ram) mantida(s) no {backendLabel(report.sourceBackend)}.
+              </p>
+            )}
+            {report.failed.length > 0 && (
+              <p className="mt-1 text-yellow-700 dark:text-yellow-400">
+                {report.failed.length} não pôde(puderam) ser migrada(s) e continua(m) no {backendLabel(report.sourceBackend)}: {report.failed.map((failure) => failure.message).join('; ')}
+              </p>
+            )}
+          </div>
+        </div>
+      </div>
+    );
+  }
+
+  if (!status?.sourceBackend) {
+    return null;
+  }
+
+  return (
+    <div className="rounded-lg bg-blue-50 border border-blue-200 p-3 dark:bg-blue-900/20 dark:border-blue-800">
+      <div className="flex items-start gap-2 text-xs">
+        <IconArrowsExchange className="h-4 w-4 text-blue-500 mt-0.5 flex-shrink-0" />
+        <div className="flex-1">
+          <p className="font-medium text-blue-800 dark:text-blue-200">
+            Credenciais encontradas no {backendLabel(status.sourceBackend)}
+          </p>
+          <p className="text-blue-700 dark:text-blue-300 mt-1">
+            O armazenamento atual é o {backendLabel(status.currentBackend)}. Migre para continuar usando a configuração salva; a cópia antiga será apagada com segurança.
+          </p>
+          <button
+            type="button"
+            onClick={handleMigrate}
+            disabled={isMigrating}
+            className="mt-2 flex items-center gap-1 px-2 py-1 rounded-md bg-blue-600 text-white hover:bg-blue-700 disabled:opacity-50 disabled:cursor-not-allowed"
+          >
+            {isMigrating && <IconLoader2 className="h-3 w-3 animate-spin" />}
+            Migrar credenciais
+          </button>
+          {error && (
+            <p className="mt-2 text-red-600 dark:text-red-400">{error}</p>
+          )}
+        </div>
+      </div>
+    </div>
+  );
+};
@@ -0,0 +1,189 @@
+import React, { useState, useEffect } from 'react';
+import { JiraService } from '../../services/jiraService';
+import type { SyncPhase, SyncSettings, SyncStatus } from '../../types/jira';
+import { IconPlayerPause, IconPlayerPlay, IconRefresh, IconLoader2 } from '@tabler/icons-react';
+
+interface SyncPanelProps {
+  className?: string;
+}
+
+const INTERVAL_OPTIONS = [
+  { value: 1, label: '1 minuto' },
+  { value: 5, label: '5 minutos' },
+  { value: 15, label: '15 minutos' },
+  { value: 30, label: '30 minutos' },
+  { value: 60, label: '1 hora' },
+];
+
+const phaseLabel = (phase: SyncPhase) => {
+  switch (phase) {
+    case 'Syncing':
+      return 'Sincronizando...';
+    case 'Paused':
+      return 'Pausada';
+    case 'Backoff':
+      return 'Falhou; nova tentativa em breve';
+    case 'Waiting':
+      return 'Aguardando credenciais';
+    default:
+      return 'Ativa';
+  }
+};
+
+// Sincronização das issues em segundo plano (intervalo, projetos acompanhados, pausa)
+export const SyncPanel: React.FC<SyncPanelProps> = ({ className = '' }) => {
+  const [settings, setSettings] = useState<SyncSettings | null>(null);
+  const [status, setStatus] = useState<SyncStatus | null>(null);
+  const [projectsInput, setProjectsInput] = useState('');
+  const [isBusy, setIsBusy] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    Promise.all([JiraService.getSyncSettings(), JiraService.getSyncStatus()])
+      .then(([loadedSettings, loadedStatus]) => {
+        setSettings(loadedSettings);
+        setProjectsInput(loadedSettings.watchedProjects.join(', '));
+        setStatus(loadedStatus);
+      })
+      .catch((err) => setError(JiraService.getErrorMessage(err, 'Erro ao obter sincronização')));
+
+    const unlisten = JiraService.onSyncStatus(setStatus);
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  const runAction = async (action: () => Promise<SyncSettings>, fallback: string) => {
+    setIsBusy(true);
+    setError(null);
+
+    try {
+      const updated = await action();
+      setSettings(updated);
+      setProjectsInput(updated.watchedProjects.join(', '));
+    } catch (err) {
+      setError(JiraService.getErrorMessage(err, fallback));
+    } finally {
+      setIsBusy(false);
+    }
+  };
+
+  const handleSave = (changes: Partial<SyncSettings>) => {
+    if (!settings) return;
+
+    runAction(
+      () => JiraService.setSyncSettings({ ...settings, ...changes }),
+      'Erro ao salvar configuração de sincronização'
+    );
+  };
+
+  const handleProjectsBlur = () => {
+    const watchedProjects = projectsInput
+      .split(',')
+      .map((key) => key.trim().toUpperCase())
+      .filter(Boolean);
+
+    if (settings && watchedProjects.join(',') !== settings.watchedProjects.join(',')) {
+      handleSave({ watchedProjects });
+    }
+  };
+
+  if (!settings || !status) {
+    return error ? <p className="text-xs text-red-600 dark:text-red-400">{error}</p> : null;
+  }
+
+  const buttonClass = 'flex items-center gap-1 px-2 py-1 text-xs rounded-md border border-gray-300 text-gray-700 hover:bg-gray-100 disabled:opacity-50 disabled:cursor-not-allowed dark:border-gray-600 dark:text-gray-300 dark:hover:bg-gray-700';
+
+  return (
+    <div className={`rounded-lg bg-gray-50 border border-gray-200 p-3 dark:bg-gray-800 dark:border-gray-700 ${className}`}>
+      <div className="flex items-center justify-between mb-2">
+        <h4 className="text-xs font-medium text-gray-800 dark:text-gray-200">
+          Sincronização em Segundo Plano
+        </h4>
+        {(isBusy || status.phase === 'Syncing') && <IconLoader2 className="h-4 w-4 animate-spin text-gray-500" />}
+      </div>
+
+      <div className="space-y-2 text-xs">
+        <div className="text-gray-600 dark:text-gray-400">
+          <span className="font-medium text-gray-700 dark:text-gray-300">{phaseLabel(status.phase)}</span>
+          {status.lastSyncAt && (
+            <span> · última às {new Date(status.lastSyncAt).toLocaleTimeString()} ({status.lastChanged} alteração(ões))</span>
+          )}
+          {status.nextSyncAt && status.phase !== 'Paused' && (
+            <span> · próxima às {new Date(status.nextSyncAt).toLocaleTimeString()}</span>
+          )}
+        </div>
+
+        {status.lastError && status.phase !== 'Idle' && (
+          <p className="text-yellow-700 dark:text-yellow-400">{status.lastError}</p>
+        )}
+
+        <div className="flex items-center gap-2">
+          <label className="text-gray-600 dark:text-gray-400">Intervalo:</label>
+          <select
+            value={settings.intervalMinutes}
+            onChange={(e) => handleSave({ intervalMinutes: Number(e.target.value) })}
+            disabled={isBusy}
+            className="px-2 py-1 border border-gray-300 rounded-md dark:bg-gray-800 dark:border-gray-600 dark:text-gray-200"
+          >
+            {INTERVAL_OPTIONS.map(option => (
+              <option key={option.value} value={option.value}>{option.label}</option>
+            ))}
+          </select>
+        </div>
+
+        <div>
+          <label className="block text-gray-600 dark:text-gray-400 mb-1">
+            Projetos acompanhados (chaves separadas por vírgula; vazio usa as issues atribuídas a você):
+          </label>
+          <input
+            type="text"
+            value={projectsInput}
+            onChange={(e) => setProjectsInput(e.target.value)}
+            onBlur={handleProjectsBlur}
+            disabled={isBusy}
+            placeholder="ABC, DEF"
+            className="w-full px-2 py-1 border border-gray-300 rounded-md dark:bg-gray-800 dark:border-gray-600 dark:text-gray-200"
+          />
+        </div>
+
+        <div className="flex items-center gap-2">
+          {settings.paused ? (
+            <button
+              type="button"
+              onClick={() => runAction(JiraService.resumeSync, 'Erro ao retomar sincronização')}
+              disabled={isBusy}
+              className={buttonClass}
+            >
+              <IconPlayerPlay className="h-3 w-3" />
+              Retomar
+            </button>
+          ) : (
+            <button
+              type="button"
+              onClick={() => runAction(JiraService.pauseSync, 'Erro ao pausar sincronização')}
+              disabled={isBusy}
+              className={buttonClass}
+            >
+              <IconPlayerPause className="h-3 w-3" />
+              Pausar
+            </button>
+          )}
+          <button
+            type="button"
+            onClick={() => JiraService.syncNow()}
+            disabled={isBusy || settings.paused || status.phase === 'Syncing'}
+            className={buttonClass}
+          >
+            <IconRefresh className="h-3 w-3" />
+            Sincronizar agora
+          </button>
+        </div>
+      </div>
+
+      {error && (
+        <p className="mt-2 text-xs text-red-600 dark:text-red-400">{error}</p>
+      )}
+    </div>
+  );
+};
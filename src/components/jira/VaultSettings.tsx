@@ -0,0 +1,177 @@
+import React, { useState, useEffect } from 'react';
+import { JiraService } from '../../services/jiraService';
+import type { VaultStatus } from '../../types/jira';
+import { IconLock, IconLockOpen, IconKey, IconLoader2 } from '@tabler/icons-react';
+
+interface VaultSettingsProps {
+  // Chamado após ativar a senha mestra, que troca o backend de armazenamento
+  onChange?: () => void;
+  className?: string;
+}
+
+const AUTO_LOCK_OPTIONS = [
+  { value: 5, label: '5 minutos' },
+  { value: 15, label: '15 minutos' },
+  { value: 30, label: '30 minutos' },
+  { value: 60, label: '1 hora' },
+  { value: 0, label: 'Nunca' },
+];
+
+export const VaultSettings: React.FC<VaultSettingsProps> = ({ onChange, className = '' }) => {
+  const [status, setStatus] = useState<VaultStatus | null>(null);
+  const [isBusy, setIsBusy] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    loadStatus();
+  }, []);
+
+  const loadStatus = async () => {
+    try {
+      setStatus(await JiraService.getVaultStatus());
+    } catch (err) {
+      setError(JiraService.getErrorMessage(err, 'Erro ao obter estado da senha mestra'));
+    }
+  };
+
+  const runAction = async (action: () => Promise<VaultStatus>, fallback: string) => {
+    setIsBusy(true);
+    setError(null);
+
+    try {
+      setStatus(await action());
+      return true;
+    } catch (err) {
+      setError(JiraService.getErrorMessage(err, fallback));
+      return false;
+    } finally {
+      setIsBusy(false);
+    }
+  };
+
+  const handleEnable = async () => {
+    const passphrase = prompt('Defina a senha mestra (mínimo de 8 caracteres)');
+    if (!passphrase) return;
+
+    if (prompt('Confirme a senha mestra') !== passphrase) {
+      setError('As senhas não conferem');
+      return;
+    }
+
+    const ok = await runAction(() => JiraService.enableVaultPassphrase(passphrase), 'Erro ao ativar senha mestra');
+    if (ok) onChange?.();
+  };
+
+  const handleChange = () => {
+    const current = prompt('Senha mestra atual');
+    if (!current) return;
+
+    const next = prompt('Nova senha mestra (mínimo de 8 caracteres)');
+    if (!next) return;
+
+    if (prompt('Confirme a nova senha mestra') !== next) {
+      setError('As senhas não conferem');
+      return;
+    }
+
+    runAction(() => JiraService.changeVaultPassphrase(current, next), 'Erro ao alterar senha mestra');
+  };
+
+  const handleUnlock = () => {
+    const passphrase = prompt('Senha mestra');
+    if (!passphrase) return;
+
+    runAction(() => JiraService.unlockVault(passphrase), 'Erro ao desbloquear cofre');
+  };
+
+  const handleAutoLock = (minutes: number) =>
+    runAction(() => JiraService.setVaultAutoLock(minutes), 'Erro ao configurar bloqueio automático');
+
+  if (!status) {
+    return null;
+  }
+
+  const buttonClass = 'flex items-center gap-1 px-2 py-1 text-xs rounded-md border border-gray-300 text-gray-700 hover:bg-gray-100 disabled:opacity-50 disabled:cursor-not-allowed dark:border-gray-600 dark:text-gray-300 dark:hover:bg-gray-700';
+
+  return (
+    <div className={`rounded-lg bg-gray-50 border border-gray-200 p-3 dark:bg-gray-800 dark:border-gray-700 ${className}`}>
+      <div className="flex items-center justify-between mb-2">
+        <h4 className="text-xs font-medium text-gray-800 dark:text-gray-200">
+          Senha Mestra
+        </h4>
+        {isBusy && <IconLoader2 className="h-4 w-4 animate-spin text-gray-500" />}
+      </div>
+
+      {!status.enabled ? (
+        <div className="space-y-2">
+          <p className="text-xs text-gray-600 dark:text-gray-400">
+            Protege as credenciais com uma chave derivada da sua senha (Argon2id). A senha será pedida ao abrir o aplicativo.
+          </p>
+          <button type="button" onClick={handleEnable} disabled={isBusy} className={buttonClass}>
+            <IconKey className="h-3 w-3" />
+            Ativar senha mestra
+          </button>
+        </div>
+      ) : (
+        <div className="space-y-2">
+          <div className="flex items-center gap-2 text-xs">
+            {status.unlocked ? (
+              <IconLockOpen className="h-4 w-4 text-green-500" />
+            ) : (
+              <IconLock className="h-4 w-4 text-yellow-500" />
+            )}
+            <span className="text-gray-700 dark:text-gray-300">
+              {status.unlocked ? 'Desbloqueado' : 'Bloqueado'}
+            </span>
+            {status.unlocked && status.locksAt && (
+              <span className="text-gray-500 dark:text-gray-400">
+                (bloqueia às {new Date(status.locksAt).toLocaleTimeString()})
+              </span>
+            )}
+          </div>
+
+          <div className="flex items-center gap-2 text-xs">
+            <label className="text-gray-600 dark:text-gray-400">Bloqueio automático:</label>
+            <select
+              value={status.autoLockMinutes}
+              onChange={(e) => handleAutoLock(Number(e.target.value))}
+              disabled={isBusy}
+              className="px-2 py-1 border border-gray-300 rounded-md dark:bg-gray-800 dark:border-gray-600 dark:text-gray-200"
+            >
+              {AUTO_LOCK_OPTIONS.map(option => (
+                <option key={option.value} value={option.value}>{option.label}</option>
+              ))}
+            </select>
+          </div>
+
+          <div className="flex items-center gap-2">
+            {status.unlocked ? (
+              <button
+                type="button"
+                onClick={() => runAction(JiraService.lockVault, 'Erro ao bloquear cofre')}
+                disabled={isBusy}
+                className={buttonClass}
+              >
+                <IconLock className="h-3 w-3" />
+                Bloquear agora
+              </button>
+            ) : (
+              <button type="button" onClick={handleUnlock} disabled={isBusy} className={buttonClass}>
+                <IconLockOpen className="h-3 w-3" />
+                Desbloquear
+              </button>
+            )}
+            <button type="button" onClick={handleChange} disabled={isBusy} className={buttonClass}>
+              <IconKey className="h-3 w-3" />
+              Alterar senha
+            </button>
+          </div>
+        </div>
+      )}
+
+      {error && (
+        <p className="mt-2 text-xs text-red-600 dark:text-red-400">{error}</p>
+      )}
+    </div>
+  );
+};
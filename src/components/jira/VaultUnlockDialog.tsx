@@ -0,0 +1,91 @@
+import React, { useState, useEffect } from 'react';
+import { JiraService } from '../../services/jiraService';
+import { IconLock, IconLoader2 } from '@tabler/icons-react';
+
+// Pede a senha mestra na inicialização quando o cofre está ativado e bloqueado
+export const VaultUnlockDialog: React.FC = () => {
+  const [isOpen, setIsOpen] = useState(false);
+  const [passphrase, setPassphrase] = useState('');
+  const [isBusy, setIsBusy] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    JiraService.getVaultStatus()
+      .then(status => setIsOpen(status.enabled && !status.unlocked))
+      .catch(() => setIsOpen(false));
+  }, []);
+
+  const handleSubmit = async (e: React.FormEvent) => {
+    e.preventDefault();
+    if (!passphrase) return;
+
+    setIsBusy(true);
+    setError(null);
+
+    try {
+      await JiraService.unlockVault(passphrase);
+      setPassphrase('');
+      setIsOpen(false);
+    } catch (err) {
+      setError(JiraService.getErrorMessage(err, 'Erro ao desbloquear cofre'));
+    } finally {
+      setIsBusy(false);
+    }
+  };
+
+  if (!isOpen) {
+    return null;
+  }
+
+  return (
+    <div className="fixed inset-0 z-50 flex items-center justify-center bg-black/50">
+      <form
+        onSubmit={handleSubmit}
+        className="w-full max-w-sm rounded-lg bg-white p-6 shadow-xl dark:bg-neutral-800"
+      >
+        <div className="flex items-center gap-2 mb-2">
+          <IconLock className="h-5 w-5 text-neutral-600 dark:text-neutral-300" />
+          <h2 className="text-lg font-medium text-neutral-800 dark:text-neutral-100">
+            Desbloquear credenciais
+          </h2>
+        </div>
+        <p className="text-sm text-neutral-600 dark:text-neutral-400 mb-4">
+          Informe a senha mestra para acessar as credenciais do JIRA.
+        </p>
+
+        <input
+          type="password"
+          value={passphrase}
+          onChange={(e) => setPassphrase(e.target.value)}
+          autoFocus
+          disabled={isBusy}
+          placeholder="Senha mestra"
+          className="w-full px-3 py-2 border border-neutral-300 rounded-md focus:ring-2 focus:ring-blue-500 focus:border-blue-500 dark:bg-neutral-800 dark:border-neutral-600 dark:text-neutral-200"
+        />
+
+        {error && (
+          <p className="mt-2 text-sm text-red-600 dark:text-red-400">{error}</p>
+        )}
+
+        <div className="mt-4 flex justify-end gap-2">
+          <button
+            type="button"
+            onClick={() => setIsOpen(false)}
+            disabled={isBusy}
+            className="px-4 py-2 text-sm rounded-md text-neutral-600 hover:bg-neutral-100 dark:text-neutral-300 dark:hover:bg-neutral-700"
+          >
+            Agora não
+          </button>
+          <button
+            type="submit"
+            disabled={isBusy || !passphrase}
+            className="flex items-center gap-2 px-4 py-2 text-sm rounded-md bg-blue-600 text-white hover:bg-blue-700 disabled:opacity-50 disabled:cursor-not-allowed"
+          >
+            {isBusy && <IconLoader2 className="h-4 w-4 animate-spin" />}
+            Desbloquear
+          </button>
+        </div>
+      </form>
+    </div>
+  );
+};
@@ -1,6 +1,7 @@
 import { useState, useCallback } from 'react';
 import { JiraService } from '../services/jiraService';
-import type { JiraConfig, JiraUser, JiraProject, JiraIssue, JiraConnectionStatus, EnvironmentInfo } from '../types/jira';
+import type { JiraConfig, JiraUser, JiraProject, JiraIssue, JiraCached, JiraConnectionStatus, EnvironmentInfo, JiraApiVersion, OAuthClientInput } from '../types/jira';
+import { JiraAuthMode } from '../types/jira';
 
 export const useJira = () => {
   const [isLoading, setIsLoading] = useState(false);
@@ -8,7 +9,13 @@ export const useJira = () => {
 
   const clearError = useCallback(() => setError(null), []);
 
-  const saveConfig = useCallback(async (url: string, email: string, token: string) => {
+  const saveConfig = useCallback(async (
+    url: string,
+    email: string,
+    token: string,
+    authMode: JiraAuthMode = JiraAuthMode.CloudApiToken,
+    apiVersion?: JiraApiVersion
+  ) => {
     setIsLoading(true);
     setError(null);
     
@@ -19,9 +26,9 @@ export const useJira = () => {
         throw new Error(urlValidation.error);
       }
 
-      const emailValidation = JiraService.validateEmail(email);
-      if (!emailValidation.valid) {
-        throw new Error(emailValidation.error);
+      const userValidation = JiraService.validateUser(authMode, email);
+      if (!userValidation.valid) {
+        throw new Error(userValidation.error);
       }
 
       const tokenValidation = JiraService.validateToken(token);
@@ -29,10 +36,26 @@ export const useJira = () => {
         throw new Error(tokenValidation.error);
       }
 
-      await JiraService.saveConfig(url, email, token);
+      await JiraService.saveConfig(url, email, token, authMode, apiVersion);
       return true;
     } catch (err) {
-      const errorMessage = err instanceof Error ? err.message : 'Erro desconhecido ao salvar configuração';
+      const errorMessage = JiraService.getErrorMessage(err, 'Erro desconhecido ao salvar configuração');
+      setError(errorMessage);
+      return false;
+    } finally {
+      setIsLoading(false);
+    }
+  }, []);
+
+  const startOAuthLogin = useCallback(async (client: OAuthClientInput, siteUrl?: string) => {
+    setIsLoading(true);
+    setError(null);
+
+    try {
+      await JiraService.startOAuthLogin(client, siteUrl);
+      return true;
+    } catch (err) {
+      const errorMessage = JiraService.getErrorMessage(err, 'Erro desconhecido no login OAuth');
       setError(errorMessage);
       return false;
     } finally {
@@ -47,7 +70,7 @@ export const useJira = () => {
     try {
       return await JiraService.getConfig();
     } catch (err) {
-      const errorMessage = err instanceof Error ? err.message : 'Erro desconhecido ao obter configuração';
+      const errorMessage = JiraService.getErrorMessage(err, 'Erro desconhecido ao obter configuração');
       setError(errorMessage);
       return null;
     } finally {
@@ -62,7 +85,7 @@ export const useJira = () => {
     try {
       return await JiraService.testConnection();
     } catch (err) {
-      const errorMessage = err instanceof Error ? err.message : 'Erro desconhecido ao testar conexão';
+      const errorMessage = JiraService.getErrorMessage(err, 'Erro desconhecido ao testar conexão');
       setError(errorMessage);
       return false;
     } finally {
@@ -78,7 +101,7 @@ export const useJira = () => {
       await JiraService.clearConfig();
       return true;
     } catch (err) {
-      const errorMessage = err instanceof Error ? err.message : 'Erro desconhecido ao limpar configuração';
+      const errorMessage = JiraService.getErrorMessage(err, 'Erro desconhecido ao limpar configuração');
       setError(errorMessage);
       return false;
     } finally {
@@ -93,7 +116,7 @@ export const useJira = () => {
     try {
       return await JiraService.hasConfig();
     } catch (err) {
-      const errorMessage = err instanceof Error ? err.message : 'Erro desconhecido ao verificar configuração';
+      const errorMessage = JiraService.getErrorMessage(err, 'Erro desconhecido ao verificar configuração');
       setError(errorMessage);
       return false;
     } finally {
@@ -101,14 +124,14 @@ export const useJira = () => {
     }
   }, []);
 
-  const getCurrentUser = useCallback(async (): Promise<JiraUser | null> => {
+  const getCurrentUser = useCallback(async (): Promise<JiraCached<JiraUser> | null> => {
     setIsLoading(true);
     setError(null);
     
     try {
       return await JiraService.getCurrentUser();
     } catch (err) {
-      const errorMessage = err instanceof Error ? err.message : 'Erro desconhecido ao obter usuário';
+      const errorMessage = JiraService.getErrorMessage(err, 'Erro desconhecido ao obter usuário');
       setError(errorMessage);
       return null;
     } finally {
@@ -116,31 +139,31 @@ export const useJira = () => {
     }
   }, []);
 
-  const getProjects = useCallback(async (): Promise<JiraProject[]> => {
+  const getProjects = useCallback(async (): Promise<JiraCached<JiraProject[]> | null> => {
     setIsLoading(true);
     setError(null);
     
     try {
       return await JiraService.getProjects();
     } catch (err) {
-      const errorMessage = err instanceof Error ? err.message : 'Erro desconhecido ao obter projetos';
+      const errorMessage = JiraService.getErrorMessage(err, 'Erro desconhecido ao obter projetos');
       setError(errorMessage);
-      return [];
+      return null;
     } finally {
       setIsLoading(false);
     }
   }, []);
 
-  const searchIssues = useCallback(async (jql: string, maxResults: number = 50): Promise<JiraIssue[]> => {
+  const searchIssues = useCallback(async (jql: string, maxResults?: number): Promise<JiraCached<JiraIssue[]> | null> => {
     setIsLoading(true);
     setError(null);
     
     try {
       return await JiraService.searchIssues(jql, maxResults);
     } catch (err) {
-      const errorMessage = err instanceof Error ? err.message : 'Erro desconhecido ao buscar issues';
+      const errorMessage = JiraService.getErrorMessage(err, 'Erro desconhecido ao buscar issues');
       setError(errorMessage);
-      return [];
+      return null;
     } finally {
       setIsLoading(false);
     }
@@ -153,7 +176,7 @@ export const useJira = () => {
     try {
       return await JiraService.getConnectionStatus();
     } catch (err) {
-      const errorMessage = err instanceof Error ? err.message : 'Erro desconhecido ao verificar status';
+      const errorMessage = JiraService.getErrorMessage(err, 'Erro desconhecido ao verificar status');
       setError(errorMessage);
       return {
         connected: false,
@@ -172,7 +195,7 @@ export const useJira = () => {
     try {
       return await JiraService.getEnvironmentInfo();
     } catch (err) {
-      const errorMessage = err instanceof Error ? err.message : 'Erro desconhecido ao obter informações do ambiente';
+      const errorMessage = JiraService.getErrorMessage(err, 'Erro desconhecido ao obter informações do ambiente');
       setError(errorMessage);
       return null;
     } finally {
@@ -188,6 +211,7 @@ export const useJira = () => {
     // Ações
     clearError,
     saveConfig,
+    startOAuthLogin,
     getConfig,
     testConnection,
     clearConfig,
@@ -201,6 +225,7 @@ export const useJira = () => {
     // Utilitários de validação
     validateJiraUrl: JiraService.validateJiraUrl,
     validateEmail: JiraService.validateEmail,
+    validateUser: JiraService.validateUser,
     validateToken: JiraService.validateToken,
 
     // Utilitários de segurança
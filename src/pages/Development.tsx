@@ -4,19 +4,28 @@ import { JiraService } from '../services/jiraService';
 import { saveWindowState, restoreStateCurrent, StateFlags } from '@tauri-apps/plugin-window-state';
 import type { JiraIssue } from '../types/jira';
 import { CometCard } from '../components/ui/CometCard';
+import { LocalIssueSearch } from '../components/jira/LocalIssueSearch';
+
+const DEVELOPMENT_JQL = 'status IN ("Desenvolvendo", "Desenvolver") AND assignee = currentUser()';
 
 export default function Development() {
   const { searchIssues, getConnectionStatus } = useJira();
   const [issues, setIssues] = useState<JiraIssue[]>([]);
+  // Horário dos dados quando vieram de um cache obsoleto (ex.: VPN fora do ar)
+  const [staleSince, setStaleSince] = useState<string | null>(null);
 
-  // Carregar tarefas automaticamente ao abrir a página
+  // Carregar tarefas automaticamente ao abrir a página; o cache local dispensa a conexão
   useEffect(() => {
     const loadJiraIssues = async () => {
       try {
-        const status = await getConnectionStatus();
-        if (status.connected) {
-          let jiraIssues = await searchIssues('status IN ("Desenvolvendo", "Desenvolver") AND assignee = currentUser()');
-          setIssues(jiraIssues);
+        if (!(await JiraService.hasConfig())) {
+          return;
+        }
+
+        const result = await searchIssues(DEVELOPMENT_JQL);
+        if (result) {
+          setIssues(result.data);
+          setStaleSince(result.stale ? result.fetchedAt : null);
         }
       } catch (error) {
         console.error('Erro ao carregar tarefas do JIRA:', error);
@@ -24,7 +33,27 @@ export default function Development() {
     };
 
     loadJiraIssues();
-  }, [searchIssues, getConnectionStatus]);
+
+    // Recarregar quando a atualização em segundo plano terminar com sucesso
+    const unlisten = JiraService.onCacheRefreshed((event) => {
+      if (event.kind === 'Search' && event.query === DEVELOPMENT_JQL && !event.error) {
+        loadJiraIssues();
+      }
+    });
+
+    // A sincronização emite um evento por issue alterada; agrupar antes de recarregar
+    let reloadTimer: ReturnType<typeof setTimeout> | undefined;
+    const unlistenSync = JiraService.onIssueUpdated(() => {
+      clearTimeout(reloadTimer);
+      reloadTimer = setTimeout(loadJiraIssues, 500);
+    });
+
+    return () => {
+      clearTimeout(reloadTimer);
+      unlisten.then((fn) => fn());
+      unlistenSync.then((fn) => fn());
+    };
+  }, [searchIssues]);
 
   // Função para formatar data
   const formatDate = (dateString: string) => {
@@ -53,16 +82,16 @@ export default function Development() {
       console.log('🔍 Buscando tarefas com status "Desenvolvendo"...');
 
       // Buscar tarefas com status 'Desenvolvendo' - vamos tentar variações do nome do status
-      let issues = await searchIssues('status = "Desenvolvendo" AND assignee = currentUser()');
+      let issues = (await searchIssues('status = "Desenvolvendo" AND assignee = currentUser()'))?.data ?? [];
       
       if (issues.length === 0) {
         console.log('ℹ️  Nenhuma tarefa encontrada com status "Desenvolvendo". Tentando "In Progress"...');
-        issues = await searchIssues('status = "In Progress" AND assignee = currentUser()');
+        issues = (await searchIssues('status = "In Progress" AND assignee = currentUser()'))?.data ?? [];
       }
 
       if (issues.length === 0) {
         console.log('ℹ️  Nenhuma tarefa encontrada com status "In Progress". Buscando todas as tarefas atribuídas...');
-        issues = await searchIssues('assignee = currentUser()');
+        issues = (await searchIssues('assignee = currentUser()'))?.data ?? [];
       }
 
       console.log(`🔍 Total de tarefas encontradas: ${issues.length}`);
@@ -70,6 +99,7 @@ export default function Development() {
       
       // Salvar as issues no estado para exibir na tela
       setIssues(issues);
+      setStaleSince(null);
       
     } catch (error) {
       console.error('❌ Erro ao buscar tarefas do JIRA:', error);
@@ -133,15 +163,28 @@ export default function Development() {
         </div>
       </div>
 
+      {/* Busca nas issues em cache, sem consultar o JIRA */}
+      <LocalIssueSearch />
+
       {/* Lista de Tarefas em Desenvolvimento */}
       <div>
         <div className="mb-4 flex items-center justify-between">
           <h2 className="text-xl font-semibold text-neutral-800 dark:text-neutral-200">
             Tarefas em Desenvolvimento
           </h2>
-          <span className="rounded bg-blue-100 px-2 py-1 text-sm text-blue-700 dark:bg-blue-900 dark:text-blue-300">
-            {issues.length} {issues.length === 1 ? 'tarefa' : 'tarefas'}
-          </span>
+          <div className="flex items-center gap-2">
+            {staleSince && (
+              <span
+                className="rounded bg-amber-100 px-2 py-1 text-sm text-amber-700 dark:bg-amber-900 dark:text-amber-300"
+                title="Exibindo a última versão salva localmente; a atualização acontece quando o JIRA responder"
+              >
+                Desatualizado · salvo em {new Date(staleSince).toLocaleString('pt-BR')}
+              </span>
+            )}
+            <span className="rounded bg-blue-100 px-2 py-1 text-sm text-blue-700 dark:bg-blue-900 dark:text-blue-300">
+              {issues.length} {issues.length === 1 ? 'tarefa' : 'tarefas'}
+            </span>
+          </div>
         </div>
 
         {issues.length === 0 ? (
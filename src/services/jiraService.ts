@@ -1,8 +1,32 @@
-import { invoke } from '@tauri-apps/api/core';
-import type { JiraConfig, JiraUser, JiraProject, JiraIssue, JiraConnectionStatus, EnvironmentInfo } from '../types/jira';
-import { SecurityLevel } from '../types/jira';
+import { invoke, Channel } from '@tauri-apps/api/core';
+import { listen, type UnlistenFn } from '@tauri-apps/api/event';
+import type { JiraConfig, OAuthClientInput, JiraProfile, JiraProfileList, JiraUser, JiraProject, JiraIssue, JiraIssuePage, JiraIssueCount, JiraIssueDetail, JiraCreateMetaIssueType, JiraFieldMeta, JiraIssueDraft, JiraCreatedIssue, JiraTransition, JiraComment, JiraWorklog, JiraWorklogInput, WorkTimer, JiraCommandError, JiraRateLimitStatus, JiraConnectionStatus, EnvironmentInfo, VaultStatus, StorageMigrationStatus, StorageMigrationReport, DiagnosticsReport, JiraCached, CacheRefreshed, SyncSettings, SyncStatus, LocalIssueMatch } from '../types/jira';
+import { SecurityLevel, StorageBackend, StoragePreference, KeyringFailure, Sandbox, DiagnosticsFormat, JiraAuthMode, JiraApiVersion } from '../types/jira';
 
 export class JiraService {
+  // Listar perfis e o perfil ativo
+  static async listProfiles(): Promise<JiraProfileList> {
+    return invoke('list_jira_profiles');
+  }
+
+  static async createProfile(name: string): Promise<JiraProfile> {
+    return invoke('create_jira_profile', { name });
+  }
+
+  static async renameProfile(profileId: string, name: string): Promise<JiraProfile> {
+    return invoke('rename_jira_profile', { profileId, name });
+  }
+
+  // Remove o perfil e suas credenciais
+  static async deleteProfile(profileId: string): Promise<void> {
+    return invoke('delete_jira_profile', { profileId });
+  }
+
+  // Trocar o perfil usado pelos demais comandos
+  static async activateProfile(profileId: string): Promise<JiraProfile> {
+    return invoke('activate_jira_profile', { profileId });
+  }
+
   // Verificar se tem configuração JIRA
   static async hasConfig(): Promise<boolean> {
     return invoke('has_jira_config');
@@ -12,9 +36,16 @@ export class JiraService {
   static async saveConfig(
     url: string,
     email: string,
-    token: string
+    token: string,
+    authMode: JiraAuthMode = JiraAuthMode.CloudApiToken,
+    apiVersion?: JiraApiVersion
   ): Promise<void> {
-    return invoke('save_jira_config', { url, email, token });
+    return invoke('save_jira_config', { url, email, token, authMode, apiVersion });
+  }
+
+  // Login OAuth 2.0: abre o navegador e resolve quando a callback é recebida
+  static async startOAuthLogin(client: OAuthClientInput, siteUrl?: string): Promise<JiraConfig> {
+    return invoke('start_jira_oauth_login', { client, siteUrl });
   }
 
   // Recuperar configuração JIRA (token mascarado)
@@ -32,22 +63,179 @@ export class JiraService {
     return invoke('clear_jira_config');
   }
 
-  // Obter usuário atual do JIRA
-  static async getCurrentUser(): Promise<JiraUser> {
-    const userJson: string = await invoke('get_current_jira_user');
-    return JSON.parse(userJson);
+  // Obter usuário atual do JIRA; pode vir do cache local (ver `stale`)
+  static async getCurrentUser(): Promise<JiraCached<JiraUser>> {
+    return invoke('get_current_jira_user');
+  }
+
+  // Obter projetos do JIRA (todas as páginas, ou até maxResults); pode vir do cache local
+  static async getProjects(maxResults?: number): Promise<JiraCached<JiraProject[]>> {
+    return invoke('get_jira_projects', { maxResults });
+  }
+
+  // Buscar issues usando JQL (todas as páginas, ou até maxResults); pode vir do cache local
+  static async searchIssues(jql: string, maxResults?: number): Promise<JiraCached<JiraIssue[]>> {
+    return invoke('search_jira_issues', { jql, maxResults });
+  }
+
+  // Busca textual nas issues em cache (resumo, descrição e comentários), sem rede
+  static async searchLocalIssues(query: string, limit?: number): Promise<LocalIssueMatch[]> {
+    return invoke('search_local_issues', { query, limit });
+  }
+
+  // Fim de uma atualização do cache em segundo plano; buscar de novo para obter os dados novos
+  static async onCacheRefreshed(handler: (event: CacheRefreshed) => void): Promise<UnlistenFn> {
+    return listen<CacheRefreshed>('jira://cache-refreshed', (event) => handler(event.payload));
+  }
+
+  // Sincronização em segundo plano
+  static async getSyncStatus(): Promise<SyncStatus> {
+    return invoke('get_jira_sync_status');
+  }
+
+  static async getSyncSettings(): Promise<SyncSettings> {
+    return invoke('get_jira_sync_settings');
+  }
+
+  static async setSyncSettings(settings: SyncSettings): Promise<SyncSettings> {
+    return invoke('set_jira_sync_settings', { settings });
+  }
+
+  static async pauseSync(): Promise<SyncSettings> {
+    return invoke('pause_jira_sync');
+  }
+
+  static async resumeSync(): Promise<SyncSettings> {
+    return invoke('resume_jira_sync');
+  }
+
+  static async syncNow(): Promise<void> {
+    return invoke('sync_jira_now');
   }
 
-  // Obter projetos do JIRA
-  static async getProjects(): Promise<JiraProject[]> {
-    const projectsJson: string = await invoke('get_jira_projects');
-    return JSON.parse(projectsJson);
+  static async onSyncStatus(handler: (status: SyncStatus) => void): Promise<UnlistenFn> {
+    return listen<SyncStatus>('jira://sync-status', (event) => handler(event.payload));
   }
 
-  // Buscar issues usando JQL
-  static async searchIssues(jql: string, maxResults: number = 50): Promise<JiraIssue[]> {
-    const issuesJson: string = await invoke('search_jira_issues', { jql, maxResults });
-    return JSON.parse(issuesJson);
+  // Issue nova ou alterada recebida pela sincronização
+  static async onIssueUpdated(handler: (issue: JiraIssue) => void): Promise<UnlistenFn> {
+    return listen<JiraIssue>('jira://issue-updated', (event) => handler(event.payload));
+  }
+
+  // Buscar issues recebendo cada página assim que chega; retorna o total entregue
+  static async streamIssues(
+    jql: string,
+    onPage: (page: JiraIssuePage) => void,
+    maxResults?: number
+  ): Promise<number> {
+    const channel = new Channel<JiraIssuePage>();
+    channel.onmessage = onPage;
+    return invoke('stream_jira_issues', { jql, maxResults, onPage: channel });
+  }
+
+  // Contar issues da consulta (aproximado no JIRA Cloud)
+  static async countIssues(jql: string): Promise<JiraIssueCount> {
+    return invoke('count_jira_issues', { jql });
+  }
+
+  // Obter detalhes completos de uma issue
+  static async getIssue(key: string): Promise<JiraIssueDetail> {
+    return invoke('get_jira_issue', { key });
+  }
+
+  // Obter tipos de issue disponíveis para criação no projeto selecionado
+  static async getCreateIssueTypes(projectKey: string): Promise<JiraCreateMetaIssueType[]> {
+    return invoke('get_jira_create_issue_types', { projectKey });
+  }
+
+  // Obter campos da tela de criação
+  static async getCreateFields(projectKey: string, issueTypeId: string): Promise<JiraFieldMeta[]> {
+    return invoke('get_jira_create_fields', { projectKey, issueTypeId });
+  }
+
+  // Validar rascunho de issue (retorna lista de erros)
+  static async validateIssueDraft(draft: JiraIssueDraft): Promise<string[]> {
+    return invoke('validate_jira_issue_draft', { draft });
+  }
+
+  // Criar issue no JIRA
+  static async createIssue(draft: JiraIssueDraft): Promise<JiraCreatedIssue> {
+    return invoke('create_jira_issue', { draft });
+  }
+
+  // Obter transições disponíveis para a issue
+  static async getTransitions(key: string): Promise<JiraTransition[]> {
+    return invoke('get_jira_transitions', { key });
+  }
+
+  // Mover a issue para outro status
+  static async transitionIssue(
+    key: string,
+    transitionId: string,
+    fields?: Record<string, unknown>,
+    comment?: string
+  ): Promise<void> {
+    return invoke('transition_jira_issue', { key, transitionId, fields, comment });
+  }
+
+  // Listar comentários da issue
+  static async listComments(key: string): Promise<JiraComment[]> {
+    return invoke('list_jira_comments', { key });
+  }
+
+  // Adicionar comentário (Markdown)
+  static async addComment(key: string, body: string): Promise<JiraComment> {
+    return invoke('add_jira_comment', { key, body });
+  }
+
+  // Editar comentário (Markdown)
+  static async editComment(key: string, commentId: string, body: string): Promise<JiraComment> {
+    return invoke('edit_jira_comment', { key, commentId, body });
+  }
+
+  // Remover comentário
+  static async deleteComment(key: string, commentId: string): Promise<void> {
+    return invoke('delete_jira_comment', { key, commentId });
+  }
+
+  // Listar registros de trabalho da issue
+  static async listWorklogs(key: string): Promise<JiraWorklog[]> {
+    return invoke('list_jira_worklogs', { key });
+  }
+
+  // Registrar trabalho na issue
+  static async addWorklog(key: string, worklog: JiraWorklogInput): Promise<JiraWorklog> {
+    return invoke('add_jira_worklog', { key, worklog });
+  }
+
+  // Atualizar registro de trabalho
+  static async updateWorklog(key: string, worklogId: string, worklog: JiraWorklogInput): Promise<JiraWorklog> {
+    return invoke('update_jira_worklog', { key, worklogId, worklog });
+  }
+
+  // Remover registro de trabalho
+  static async deleteWorklog(key: string, worklogId: string): Promise<void> {
+    return invoke('delete_jira_worklog', { key, worklogId });
+  }
+
+  // Listar cronômetros de trabalho
+  static async getWorkTimers(): Promise<WorkTimer[]> {
+    return invoke('get_work_timers');
+  }
+
+  // Iniciar ou retomar cronômetro da issue
+  static async startWorkTimer(issueKey: string): Promise<WorkTimer> {
+    return invoke('start_work_timer', { issueKey });
+  }
+
+  // Pausar cronômetro da issue
+  static async pauseWorkTimer(issueKey: string): Promise<WorkTimer> {
+    return invoke('pause_work_timer', { issueKey });
+  }
+
+  // Encerrar cronômetro, opcionalmente enviando o tempo ao JIRA
+  static async stopWorkTimer(issueKey: string, submit: boolean, comment?: string): Promise<JiraWorklog | null> {
+    return invoke('stop_work_timer', { issueKey, comment, submit });
   }
 
   // Verificar status da conexão
@@ -59,7 +247,7 @@ export class JiraService {
         const user = await this.getCurrentUser();
         return {
           connected: true,
-          user,
+          user: user.data,
           lastChecked: new Date()
         };
       } else {
@@ -73,11 +261,77 @@ export class JiraService {
       return {
         connected: false,
         lastChecked: new Date(),
-        error: error instanceof Error ? error.message : 'Erro desconhecido'
+        error: this.getErrorMessage(error, 'Erro desconhecido')
       };
     }
   }
 
+  // Obter o orçamento de requisições mais recente do JIRA
+  static async getRateLimitStatus(): Promise<JiraRateLimitStatus> {
+    return invoke('get_jira_rate_limit_status');
+  }
+
+  // Verificar se há credenciais esquecidas em outro backend de armazenamento
+  static async getStorageMigrationStatus(): Promise<StorageMigrationStatus> {
+    return invoke('get_storage_migration_status');
+  }
+
+  // Mover as credenciais para o backend atual e apagar a cópia antiga
+  static async migrateCredentialStorage(): Promise<StorageMigrationReport> {
+    return invoke('migrate_credential_storage');
+  }
+
+  // Obter o estado do cofre protegido por senha mestra
+  static async getVaultStatus(): Promise<VaultStatus> {
+    return invoke('get_vault_status');
+  }
+
+  // Ativar a senha mestra, migrando as credenciais de todos os perfis
+  static async enableVaultPassphrase(passphrase: string): Promise<VaultStatus> {
+    return invoke('enable_vault_passphrase', { passphrase });
+  }
+
+  static async unlockVault(passphrase: string): Promise<VaultStatus> {
+    return invoke('unlock_vault', { passphrase });
+  }
+
+  static async lockVault(): Promise<VaultStatus> {
+    return invoke('lock_vault');
+  }
+
+  static async changeVaultPassphrase(currentPassphrase: string, newPassphrase: string): Promise<VaultStatus> {
+    return invoke('change_vault_passphrase', { currentPassphrase, newPassphrase });
+  }
+
+  // 0 desativa o bloqueio automático
+  static async setVaultAutoLock(minutes: number): Promise<VaultStatus> {
+    return invoke('set_vault_auto_lock', { minutes });
+  }
+
+  // Verificar se o valor rejeitado por um comando é um erro estruturado do backend
+  static isCommandError(error: unknown): error is JiraCommandError {
+    return typeof error === 'object' && error !== null
+      && typeof (error as JiraCommandError).code === 'string'
+      && typeof (error as JiraCommandError).message === 'string';
+  }
+
+  // Extrair mensagem legível de um erro de comando, Error ou string
+  static getErrorMessage(error: unknown, fallback: string): string {
+    if (this.isCommandError(error) && error.code === 'credentials_unrecoverable') {
+      return 'As credenciais salvas não puderam ser recuperadas. Informe o token do JIRA novamente.';
+    }
+    if (this.isCommandError(error) && error.code === 'vault_locked') {
+      return 'O cofre de credenciais está bloqueado. Desbloqueie-o com a senha mestra.';
+    }
+    if (this.isCommandError(error) && error.code === 'invalid_passphrase') {
+      return 'Senha mestra incorreta.';
+    }
+    if (this.isCommandError(error)) return error.message;
+    if (error instanceof Error) return error.message;
+    if (typeof error === 'string') return error;
+    return fallback;
+  }
+
   // Validar URL do JIRA
   static validateJiraUrl(url: string): { valid: boolean; error?: string } {
     if (!url.trim()) {
@@ -110,6 +364,19 @@ export class JiraService {
     return { valid: true };
   }
 
+  // Validar identificação do usuário conforme o modo de autenticação
+  static validateUser(authMode: JiraAuthMode, email: string): { valid: boolean; error?: string } {
+    switch (authMode) {
+      case JiraAuthMode.CloudApiToken:
+        return JiraService.validateEmail(email);
+      case JiraAuthMode.BasicPassword:
+        return email.trim() ? { valid: true } : { valid: false, error: 'Usuário não pode estar vazio' };
+      case JiraAuthMode.DataCenterPat:
+      case JiraAuthMode.OAuth:
+        return { valid: true };
+    }
+  }
+
   // Validar token
   static validateToken(token: string): { valid: boolean; error?: string } {
     if (!token.trim()) {
@@ -129,14 +396,61 @@ export class JiraService {
 
   // Obter informações do ambiente de segurança
   static async getEnvironmentInfo(): Promise<EnvironmentInfo> {
-    const envInfoJson: string = await invoke('get_jira_environment_info');
-    return JSON.parse(envInfoJson);
+    return invoke('get_jira_environment_info');
+  }
+
+  // Relatório de diagnóstico (redigido) para anexar a chamados
+  static async getDiagnosticsReport(): Promise<DiagnosticsReport> {
+    return invoke('get_diagnostics_report');
+  }
+
+  // Relatório como texto (JSON ou Markdown), pronto para copiar
+  static async renderDiagnosticsReport(format: DiagnosticsFormat): Promise<string> {
+    return invoke('render_diagnostics_report', { format });
+  }
+
+  // Salva o relatório em arquivo e retorna o caminho
+  static async saveDiagnosticsReport(format: DiagnosticsFormat): Promise<string> {
+    return invoke('save_diagnostics_report', { format });
+  }
+
+  // Sondar o keyring novamente e redetectar o ambiente
+  static async recheckKeyring(): Promise<EnvironmentInfo> {
+    return invoke('recheck_keyring');
+  }
+
+  // Explicar por que o keyring não pôde ser usado
+  static getKeyringFailureDescription(failure: KeyringFailure | null): string | null {
+    switch (failure) {
+      case KeyringFailure.NoDbusSession:
+        return 'Sem sessão D-Bus (comum em containers, SSH e CI)';
+      case KeyringFailure.NoProvider:
+        return 'Nenhum serviço de segredos ativo (gnome-keyring, KWallet)';
+      case KeyringFailure.CollectionLocked:
+        return 'Coleção do keyring bloqueada ou acesso negado';
+      case KeyringFailure.Timeout:
+        return 'O keyring não respondeu a tempo';
+      case KeyringFailure.Mismatch:
+        return 'O keyring devolveu um valor diferente do gravado';
+      case KeyringFailure.Other:
+        return 'Falha ao acessar o keyring';
+      default:
+        return null;
+    }
+  }
+
+  // Escolher o backend de armazenamento (validado no backend antes de salvar)
+  static async setStoragePreference(preference: StoragePreference): Promise<EnvironmentInfo> {
+    return invoke('set_storage_preference', { preference });
   }
 
   // Obter descrição do nível de segurança
-  static getSecurityDescription(securityLevel: SecurityLevel, isWsl: boolean): string {
+  static getSecurityDescription(securityLevel: SecurityLevel, isWsl: boolean, storageBackend?: StorageBackend): string {
     switch (securityLevel) {
       case SecurityLevel.High:
+        if (storageBackend === StorageBackend.PassphraseFile) {
+          return "Máxima segurança: credenciais criptografadas com chave derivada da senha mestra";
+        }
         return "Máxima segurança: credenciais armazenadas no keyring nativo do sistema";
       case SecurityLevel.Medium:
         if (isWsl) {
@@ -145,6 +459,9 @@ export class JiraService {
           return "Boa segurança: credenciais criptografadas em arquivo local";
         }
       case SecurityLevel.Low:
+        if (storageBackend === StorageBackend.EncryptedFile) {
+          return "Segurança básica: arquivo criptografado com a chave no mesmo container";
+        }
         return "Segurança básica: armazenamento temporário (apenas para desenvolvimento)";
       default:
         return "Nível de segurança desconhecido";
@@ -161,12 +478,32 @@ export class JiraService {
       suggestions.push("Depois execute: gnome-keyring-daemon --start --components=secrets");
     }
 
-    if (!envInfo.has_desktop_environment && !envInfo.is_wsl) {
+    if (envInfo.container) {
+      suggestions.push("Container detectado: monte ~/.config/goji como volume para manter as credenciais fora da imagem");
+    } else if (envInfo.is_headless && !envInfo.is_ssh_session) {
       suggestions.push("Sistema sem ambiente desktop detectado. Instale um gerenciador de keyring:");
       suggestions.push("Ubuntu/Debian: sudo apt install gnome-keyring");
       suggestions.push("Fedora: sudo dnf install gnome-keyring");
     }
 
+    if (envInfo.is_ssh_session && !envInfo.has_keyring) {
+      suggestions.push("Sessão SSH: o keyring do desktop remoto costuma estar bloqueado ou inacessível; prefira a senha mestra");
+    }
+
+    if (envInfo.sandbox === Sandbox.Flatpak && !envInfo.has_secret_portal) {
+      suggestions.push("Flatpak sem acesso ao Secret Service. Libere com:");
+      suggestions.push("flatpak override --user --talk-name=org.freedesktop.secrets <app-id>");
+    }
+
+    if (envInfo.sandbox === Sandbox.Snap && !envInfo.has_secret_portal) {
+      suggestions.push("Snap sem acesso ao keyring. Conecte a interface:");
+      suggestions.push("sudo snap connect goji:password-manager-service");
+    }
+
+    if (envInfo.storage_backend === StorageBackend.EncryptedFile) {
+      suggestions.push("Ative uma senha mestra para que a chave de criptografia não fique gravada em disco");
+    }
+
     return suggestions;
   }
 
@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { CheckResult } from "./CheckResult";
+
+export type ConnectivityReport = { host: string, authMode: string, apiVersion: string, dns: CheckResult, tls: CheckResult, auth: CheckResult, serverInfo: string | null, };
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ConnectivityReport } from "./ConnectivityReport";
+import type { EnvironmentInfo } from "./EnvironmentInfo";
+import type { PathCheck } from "./PathCheck";
+import type { SecuritySummary } from "./SecuritySummary";
+import type { SlotHealth } from "./SlotHealth";
+
+export type DiagnosticsReport = { generatedAt: string, appVersion: string, platform: string, environment: EnvironmentInfo, security: SecuritySummary, paths: Array<PathCheck>, storage: Array<SlotHealth>, connectivity: ConnectivityReport | null, };
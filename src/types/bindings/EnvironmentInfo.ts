@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ContainerRuntime } from "./ContainerRuntime";
+import type { KeyringProbe } from "./KeyringProbe";
+import type { Sandbox } from "./Sandbox";
+import type { SecurityLevel } from "./SecurityLevel";
+import type { StorageBackend } from "./StorageBackend";
+import type { StoragePreference } from "./StoragePreference";
+
+export type EnvironmentInfo = { is_wsl: boolean, is_wsl2: boolean, has_keyring: boolean, keyring_probe: KeyringProbe, has_desktop_environment: boolean, container: ContainerRuntime | null, sandbox: Sandbox | null, has_secret_portal: boolean, is_ssh_session: boolean, is_headless: boolean, storage_preference: StoragePreference, storage_backend: StorageBackend, security_level: SecurityLevel, };
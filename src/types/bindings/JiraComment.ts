@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { JiraUser } from "./JiraUser";
+
+export type JiraComment = { id: string, author: JiraUser | null, body: unknown, bodyMarkdown: string, bodyHtml: string, renderedBody: string | null, created: string, updated: string, };
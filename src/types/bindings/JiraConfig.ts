@@ -0,0 +1,5 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { JiraApiVersion } from "./JiraApiVersion";
+import type { JiraAuthMode } from "./JiraAuthMode";
+
+export type JiraConfig = { url: string, email: string, token: string, auth_mode: JiraAuthMode, api_version: JiraApiVersion | null, api_url: string | null, created_at: string, last_used: string | null, };
@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type JiraErrorCode = "keyring" | "encryption" | "decryption" | "credentials_unrecoverable" | "vault_locked" | "invalid_passphrase" | "serialization" | "base64" | "http" | "invalid_config" | "authentication_failed" | "validation" | "permission_denied" | "not_found" | "rate_limited" | "api_error" | "timeout" | "network" | "oauth" | "cache";
@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type JiraFieldMeta = { fieldId: string, name: string, required: boolean, schemaType: string | null, schemaItems: string | null, hasDefaultValue: boolean, allowedValues: unknown[], };
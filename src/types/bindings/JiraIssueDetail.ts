@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { JiraComment } from "./JiraComment";
+import type { JiraComponent } from "./JiraComponent";
+import type { JiraCustomField } from "./JiraCustomField";
+import type { JiraIssueLink } from "./JiraIssueLink";
+import type { JiraIssueRef } from "./JiraIssueRef";
+import type { JiraIssueType } from "./JiraIssueType";
+import type { JiraPriority } from "./JiraPriority";
+import type { JiraResolution } from "./JiraResolution";
+import type { JiraStatus } from "./JiraStatus";
+import type { JiraTransition } from "./JiraTransition";
+import type { JiraUser } from "./JiraUser";
+import type { JiraVersion } from "./JiraVersion";
+
+export type JiraIssueDetail = { id: string, key: string, summary: string, status: JiraStatus, issueType: JiraIssueType, priority: JiraPriority | null, resolution: JiraResolution | null, assignee: JiraUser | null, reporter: JiraUser | null, created: string, updated: string, dueDate: string | null, description: unknown, descriptionMarkdown: string | null, descriptionHtml: string | null, renderedDescription: string | null, labels: Array<string>, components: Array<JiraComponent>, fixVersions: Array<JiraVersion>, parent: JiraIssueRef | null, epicKey: string | null, subtasks: Array<JiraIssueRef>, issueLinks: Array<JiraIssueLink>, comments: Array<JiraComment>, transitions: Array<JiraTransition>, customFields: { [key in string]?: JiraCustomField }, };
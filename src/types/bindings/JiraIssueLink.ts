@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { JiraIssueRef } from "./JiraIssueRef";
+
+export type JiraIssueLink = { id: string, linkType: string, direction: "inward" | "outward", relation: string, issue: JiraIssueRef, };
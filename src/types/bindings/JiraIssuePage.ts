@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { JiraIssue } from "./JiraIssue";
+
+export type JiraIssuePage = { issues: Array<JiraIssue>, startAt: number, total: number | null, isLast: boolean, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { JiraIssueType } from "./JiraIssueType";
+import type { JiraPriority } from "./JiraPriority";
+import type { JiraStatus } from "./JiraStatus";
+
+export type JiraIssueRef = { id: string, key: string, summary: string | null, status: JiraStatus | null, issueType: JiraIssueType | null, priority: JiraPriority | null, };
@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type JiraRateLimitStatus = { limit: number | null, remaining: number | null, resetAt: string | null, nearLimit: boolean, retryAfter: number | null, throttledCount: number, updatedAt: string | null, };
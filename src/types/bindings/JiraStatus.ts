@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { JiraStatusCategory } from "./JiraStatusCategory";
+
+export type JiraStatus = { id: string, name: string, statusCategory: JiraStatusCategory | null, };
@@ -0,0 +1,5 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { JiraFieldMeta } from "./JiraFieldMeta";
+import type { JiraStatus } from "./JiraStatus";
+
+export type JiraTransition = { id: string, name: string, to: JiraStatus, hasScreen: boolean, isConditional: boolean, fields: Array<JiraFieldMeta>, };
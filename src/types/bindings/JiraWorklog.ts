@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { JiraUser } from "./JiraUser";
+
+export type JiraWorklog = { id: string, author: JiraUser | null, comment: unknown, commentMarkdown: string | null, started: string, timeSpent: string, timeSpentSeconds: number, created: string, updated: string, };
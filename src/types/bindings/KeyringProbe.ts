@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { KeyringFailure } from "./KeyringFailure";
+
+export type KeyringProbe = { available: boolean, locked: boolean, failure: KeyringFailure | null, message: string | null, elapsed_ms: number, };
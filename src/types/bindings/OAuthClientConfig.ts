@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { OAuthEndpoints } from "./OAuthEndpoints";
+
+export type OAuthClientConfig = { clientId: string, clientSecret?: string, scopes: Array<string>, endpoints: OAuthEndpoints, redirectPort: number, };
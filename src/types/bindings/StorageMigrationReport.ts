@@ -0,0 +1,5 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { StorageBackend } from "./StorageBackend";
+import type { StorageMigrationFailure } from "./StorageMigrationFailure";
+
+export type StorageMigrationReport = { sourceBackend: StorageBackend, targetBackend: StorageBackend, migrated: Array<string>, skipped: Array<string>, failed: Array<StorageMigrationFailure>, };
@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SyncPhase } from "./SyncPhase";
+
+export type SyncStatus = { phase: SyncPhase, lastSyncAt: string | null, nextSyncAt: string | null, lastChanged: number, lastError: string | null, consecutiveFailures: number, };
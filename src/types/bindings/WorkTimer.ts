@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TimerState } from "./TimerState";
+
+export type WorkTimer = { issueKey: string, state: TimerState, accumulatedSeconds: number, runningSince: string | null, startedAt: string, elapsedSeconds: number, };
@@ -1,36 +1,111 @@
-export interface JiraConfig {
-  url: string;
-  email: string;
-  token: string;
-  created_at: string;
-  last_used?: string;
-}
+// Tipos gerados a partir das structs Rust (ts-rs); regenerar com
+// `cargo test export_bindings` em src-tauri após alterá-las
+import type { StorageBackend as StorageBackendBinding } from './bindings/StorageBackend';
+import type { StoragePreference as StoragePreferenceBinding } from './bindings/StoragePreference';
+import type { SecurityLevel as SecurityLevelBinding } from './bindings/SecurityLevel';
+import type { KeyringFailure as KeyringFailureBinding } from './bindings/KeyringFailure';
+import type { ContainerRuntime as ContainerRuntimeBinding } from './bindings/ContainerRuntime';
+import type { Sandbox as SandboxBinding } from './bindings/Sandbox';
+import type { JiraAuthMode as JiraAuthModeBinding } from './bindings/JiraAuthMode';
+import type { JiraApiVersion as JiraApiVersionBinding } from './bindings/JiraApiVersion';
+import type { TimerState as TimerStateBinding } from './bindings/TimerState';
+import type { DiagnosticsFormat as DiagnosticsFormatBinding } from './bindings/DiagnosticsFormat';
 
-export interface JiraUser {
-  accountId: string;
-  displayName: string;
-  emailAddress?: string;
-  active: boolean;
-}
+import type { JiraUser } from './bindings/JiraUser';
+import type { JiraProject } from './bindings/JiraProject';
+import type { JiraIssue } from './bindings/JiraIssue';
+import type { JiraIssuePage } from './bindings/JiraIssuePage';
+import type { JiraIssueCount } from './bindings/JiraIssueCount';
+import type { KeyringProbe } from './bindings/KeyringProbe';
+import type { EnvironmentInfo } from './bindings/EnvironmentInfo';
+import type { JiraCached } from './bindings/JiraCached';
+import type { CacheKind } from './bindings/CacheKind';
+import type { CacheRefreshed } from './bindings/CacheRefreshed';
+import type { SyncSettings } from './bindings/SyncSettings';
+import type { SyncPhase } from './bindings/SyncPhase';
+import type { SyncStatus } from './bindings/SyncStatus';
+import type { LocalIssueMatch } from './bindings/LocalIssueMatch';
+import type { JiraConfig } from './bindings/JiraConfig';
+import type { OAuthEndpoints } from './bindings/OAuthEndpoints';
+import type { OAuthClientConfig } from './bindings/OAuthClientConfig';
+import type { JiraProfile } from './bindings/JiraProfile';
+import type { JiraProfileList } from './bindings/JiraProfileList';
+import type { JiraStatusCategory } from './bindings/JiraStatusCategory';
+import type { JiraStatus } from './bindings/JiraStatus';
+import type { JiraIssueType } from './bindings/JiraIssueType';
+import type { JiraPriority } from './bindings/JiraPriority';
+import type { JiraResolution } from './bindings/JiraResolution';
+import type { JiraComponent } from './bindings/JiraComponent';
+import type { JiraVersion } from './bindings/JiraVersion';
+import type { JiraIssueRef } from './bindings/JiraIssueRef';
+import type { JiraIssueLink } from './bindings/JiraIssueLink';
+import type { JiraComment } from './bindings/JiraComment';
+import type { JiraTransition } from './bindings/JiraTransition';
+import type { JiraCustomField } from './bindings/JiraCustomField';
+import type { JiraIssueDetail } from './bindings/JiraIssueDetail';
+import type { JiraFieldMeta } from './bindings/JiraFieldMeta';
+import type { JiraCreateMetaIssueType } from './bindings/JiraCreateMetaIssueType';
+import type { JiraIssueDraft } from './bindings/JiraIssueDraft';
+import type { JiraCreatedIssue } from './bindings/JiraCreatedIssue';
+import type { JiraWorklog } from './bindings/JiraWorklog';
+import type { JiraWorklogInput } from './bindings/JiraWorklogInput';
+import type { WorkTimer } from './bindings/WorkTimer';
+import type { JiraErrorCode } from './bindings/JiraErrorCode';
+import type { JiraCommandError } from './bindings/JiraCommandError';
+import type { JiraRateLimitStatus } from './bindings/JiraRateLimitStatus';
+import type { StorageMigrationStatus } from './bindings/StorageMigrationStatus';
+import type { StorageMigrationReport } from './bindings/StorageMigrationReport';
+import type { StorageMigrationFailure } from './bindings/StorageMigrationFailure';
+import type { VaultStatus } from './bindings/VaultStatus';
+import type { DiagnosticsReport } from './bindings/DiagnosticsReport';
+import type { SecuritySummary } from './bindings/SecuritySummary';
+import type { PathCheck } from './bindings/PathCheck';
+import type { SlotHealth } from './bindings/SlotHealth';
+import type { CheckResult } from './bindings/CheckResult';
+import type { ConnectivityReport } from './bindings/ConnectivityReport';
 
-export interface JiraProject {
-  id: string;
-  key: string;
-  name: string;
-  description?: string;
-  projectTypeKey: string;
-}
+export type { JiraUser, JiraProject, JiraIssue, JiraIssuePage, JiraIssueCount, KeyringProbe, EnvironmentInfo };
+export type { JiraCached, CacheKind, CacheRefreshed };
+export type { SyncSettings, SyncPhase, SyncStatus };
+export type { LocalIssueMatch };
+export type { JiraConfig, OAuthEndpoints, OAuthClientConfig, JiraProfile, JiraProfileList };
+export type { JiraStatusCategory, JiraStatus, JiraIssueType, JiraPriority, JiraResolution, JiraComponent, JiraVersion };
+export type { JiraIssueRef, JiraIssueLink, JiraComment, JiraTransition, JiraCustomField, JiraIssueDetail };
+export type { JiraFieldMeta, JiraCreateMetaIssueType, JiraIssueDraft, JiraCreatedIssue };
+export type { JiraWorklog, JiraWorklogInput, WorkTimer };
+// Erro estruturado retornado pelos comandos Tauri do JIRA
+export type { JiraErrorCode, JiraCommandError, JiraRateLimitStatus };
+export type { StorageMigrationStatus, StorageMigrationReport, StorageMigrationFailure, VaultStatus };
+export type { DiagnosticsReport, SecuritySummary, PathCheck, SlotHealth, CheckResult, ConnectivityReport };
 
-export interface JiraIssue {
-  id: string;
-  key: string;
-  summary: string;
-  status: string;
-  assignee?: string;
-  reporter?: string;
-  created: string;
-  updated: string;
-}
+// Delimitadores dos termos destacados em LocalIssueMatch.snippet (MATCH_START/MATCH_END no Rust)
+export const MATCH_START = '\u0002';
+export const MATCH_END = '\u0003';
+
+// Forma de autenticação da instância JIRA
+export type JiraAuthMode = JiraAuthModeBinding;
+export const JiraAuthMode = {
+  CloudApiToken: 'CloudApiToken',
+  DataCenterPat: 'DataCenterPat',
+  BasicPassword: 'BasicPassword',
+  OAuth: 'OAuth',
+} as const satisfies { [K in JiraAuthMode]: K };
+
+// Versão da REST API; v2 para Server/Data Center
+export type JiraApiVersion = JiraApiVersionBinding;
+export const JiraApiVersion = {
+  V2: 'V2',
+  V3: 'V3',
+} as const satisfies { [K in JiraApiVersion]: K };
+
+// App OAuth informado no formulário; escopos, endpoints e porta têm padrão no Rust
+export type OAuthClientInput = Pick<OAuthClientConfig, 'clientId' | 'clientSecret'> & Partial<OAuthClientConfig>;
+
+export type TimerState = TimerStateBinding;
+export const TimerState = {
+  Running: 'Running',
+  Paused: 'Paused',
+} as const satisfies { [K in TimerState]: K };
 
 export interface JiraConnectionStatus {
   connected: boolean;
@@ -39,23 +114,58 @@ export interface JiraConnectionStatus {
   error?: string;
 }
 
-export enum StorageBackend {
-  NativeKeyring = "NativeKeyring",
-  EncryptedFile = "EncryptedFile", 
-  InMemory = "InMemory"
-}
+export type StorageBackend = StorageBackendBinding;
+export const StorageBackend = {
+  NativeKeyring: 'NativeKeyring',
+  EncryptedFile: 'EncryptedFile',
+  PassphraseFile: 'PassphraseFile',
+  InMemory: 'InMemory',
+} as const satisfies { [K in StorageBackend]: K };
 
-export enum SecurityLevel {
-  High = "High",
-  Medium = "Medium",
-  Low = "Low"
-}
+// Backend escolhido pelo usuário; Auto segue a detecção de ambiente
+export type StoragePreference = StoragePreferenceBinding;
+export const StoragePreference = {
+  Auto: 'Auto',
+  NativeKeyring: 'NativeKeyring',
+  EncryptedFile: 'EncryptedFile',
+  PassphraseFile: 'PassphraseFile',
+} as const satisfies { [K in StoragePreference]: K };
+
+export type SecurityLevel = SecurityLevelBinding;
+export const SecurityLevel = {
+  High: 'High',
+  Medium: 'Medium',
+  Low: 'Low',
+} as const satisfies { [K in SecurityLevel]: K };
+
+// Motivo da falha na sondagem do keyring (gravar/ler/apagar um segredo de teste)
+export type KeyringFailure = KeyringFailureBinding;
+export const KeyringFailure = {
+  NoDbusSession: 'NoDbusSession',
+  NoProvider: 'NoProvider',
+  CollectionLocked: 'CollectionLocked',
+  Timeout: 'Timeout',
+  Mismatch: 'Mismatch',
+  Other: 'Other',
+} as const satisfies { [K in KeyringFailure]: K };
+
+export type ContainerRuntime = ContainerRuntimeBinding;
+export const ContainerRuntime = {
+  Docker: 'Docker',
+  Podman: 'Podman',
+  Kubernetes: 'Kubernetes',
+  Other: 'Other',
+} as const satisfies { [K in ContainerRuntime]: K };
+
+export type Sandbox = SandboxBinding;
+export const Sandbox = {
+  Flatpak: 'Flatpak',
+  Snap: 'Snap',
+} as const satisfies { [K in Sandbox]: K };
 
-export interface EnvironmentInfo {
-  is_wsl: boolean;
-  is_wsl2: boolean;
-  has_keyring: boolean;
-  has_desktop_environment: boolean;
-  storage_backend: StorageBackend;
-  security_level: SecurityLevel;
-}
\ No newline at end of file
+// Formato do relatório de diagnóstico
+export type DiagnosticsFormat = DiagnosticsFormatBinding;
+export const DiagnosticsFormat = {
+  Json: 'Json',
+  Markdown: 'Markdown',
+} as const satisfies { [K in DiagnosticsFormat]: K };